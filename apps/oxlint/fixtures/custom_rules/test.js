@@ -0,0 +1,4 @@
+moment();
+// eslint-disable-next-line custom/no-moment
+moment();
+export var a = 1;
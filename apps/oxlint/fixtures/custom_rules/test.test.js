@@ -0,0 +1 @@
+export var b = moment();
@@ -0,0 +1,2 @@
+debugger;
+export const list = [...[...[1, 2, 3]]];
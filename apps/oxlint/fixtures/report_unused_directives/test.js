@@ -0,0 +1,10 @@
+// eslint-disable-next-line no-debugger
+debugger;
+
+// eslint-disable-next-line no-debugger
+foo();
+
+debugger; // eslint-disable-line no-debugger, no-console
+
+// eslint-disable-next-line not-a-rule
+foo();
@@ -1,7 +1,7 @@
 use std::{path::PathBuf, str::FromStr};
 
 use bpaf::Bpaf;
-use oxc_linter::{AllowWarnDeny, FixKind};
+use oxc_linter::{AllowWarnDeny, BaselineMode, FixKind};
 
 use super::{
     expand_glob,
@@ -30,6 +30,15 @@ pub struct LintCommand {
     #[bpaf(external)]
     pub warning_options: WarningOptions,
 
+    #[bpaf(external)]
+    pub inline_config_options: InlineConfigOptions,
+
+    #[bpaf(external)]
+    pub baseline_options: BaselineOptions,
+
+    #[bpaf(external)]
+    pub cache_options: CacheOptions,
+
     #[bpaf(external)]
     pub output_options: OutputOptions,
 
@@ -37,6 +46,11 @@ pub struct LintCommand {
     #[bpaf(long("rules"), switch, hide_usage)]
     pub list_rules: bool,
 
+    /// Migrate the ESLint configuration in the current directory
+    /// (`.eslintrc.*` or `package.json#eslintConfig`) to `.oxlintrc.json`
+    #[bpaf(switch, hide_usage)]
+    pub migrate: bool,
+
     #[bpaf(external)]
     pub misc_options: MiscOptions,
 
@@ -129,13 +143,22 @@ pub struct FixOptions {
     /// Apply dangerous fixes and suggestions.
     #[bpaf(switch, hide_usage)]
     pub fix_dangerously: bool,
+
+    /// Print the fixes instead of writing them to disk. Implies `--fix`.
+    /// Combine with `--silent` to only print the fixes
+    #[bpaf(switch, hide_usage)]
+    pub fix_dry_run: bool,
+
+    /// Format of the `--fix-dry-run` output (diff, json)
+    #[bpaf(argument("FORMAT"), fallback(FixDryRunFormat::Diff), hide_usage)]
+    pub fix_dry_run_format: FixDryRunFormat,
 }
 
 impl FixOptions {
     pub fn fix_kind(&self) -> FixKind {
         let mut kind = FixKind::None;
 
-        if self.fix {
+        if self.fix || self.fix_dry_run {
             kind.set(FixKind::SafeFix, true);
         }
 
@@ -154,7 +177,27 @@ impl FixOptions {
     }
 
     pub fn is_enabled(&self) -> bool {
-        self.fix || self.fix_suggestions || self.fix_dangerously
+        self.fix || self.fix_suggestions || self.fix_dangerously || self.fix_dry_run
+    }
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum FixDryRunFormat {
+    /// Unified diff of each fixed file
+    Diff,
+    /// List of the applied fixes of each fixed file
+    Json,
+}
+
+impl FromStr for FixDryRunFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "diff" => Ok(Self::Diff),
+            "json" => Ok(Self::Json),
+            _ => Err(format!("'{s}' is not a known format")),
+        }
     }
 }
 
@@ -175,10 +218,78 @@ pub struct WarningOptions {
     pub max_warnings: Option<usize>,
 }
 
+/// Inline Configuration Comments
+#[derive(Debug, Clone, Bpaf)]
+pub struct InlineConfigOptions {
+    /// Report directive comments like `// eslint-disable-line` when no errors would have been
+    /// reported on that line anyway
+    #[bpaf(switch, hide_usage)]
+    pub report_unused_disable_directives: bool,
+
+    /// Same as `--report-unused-disable-directives`, but allows you to specify the severity level
+    /// of the reported errors (off, warn, error)
+    #[bpaf(argument::<String>("SEVERITY"), parse(|severity: String| parse_severity(&severity)), optional, hide_usage)]
+    pub report_unused_disable_directives_severity: Option<AllowWarnDeny>,
+}
+
+fn parse_severity(severity: &str) -> Result<AllowWarnDeny, String> {
+    AllowWarnDeny::try_from(severity).map_err(|err| err.to_string())
+}
+
+impl InlineConfigOptions {
+    /// Severity to report unused disable directives with, if they should be reported.
+    pub fn report_unused_directives(&self) -> Option<AllowWarnDeny> {
+        self.report_unused_disable_directives_severity
+            .or(self.report_unused_disable_directives.then_some(AllowWarnDeny::Deny))
+    }
+}
+
+/// Baseline
+#[derive(Debug, Clone, Bpaf)]
+pub struct BaselineOptions {
+    /// Only report diagnostics that are not in the baseline file,
+    /// and remove the entries that no longer occur from it
+    #[bpaf(switch, hide_usage)]
+    pub baseline: bool,
+
+    /// Record all current diagnostics in the baseline file
+    #[bpaf(switch, hide_usage)]
+    pub baseline_write: bool,
+
+    /// Path of the baseline file, defaults to `oxlint-baseline.json`
+    #[bpaf(argument("PATH"), hide_usage)]
+    pub baseline_file: Option<PathBuf>,
+}
+
+impl BaselineOptions {
+    pub fn mode(&self) -> Option<BaselineMode> {
+        if self.baseline_write {
+            Some(BaselineMode::Write)
+        } else if self.baseline {
+            Some(BaselineMode::Check)
+        } else {
+            None
+        }
+    }
+}
+
+/// Caching
+#[derive(Debug, Clone, Bpaf)]
+pub struct CacheOptions {
+    /// Only check changed files. Diagnostics of unchanged files are replayed from the cache.
+    /// Not used with `--fix`
+    #[bpaf(switch, hide_usage)]
+    pub cache: bool,
+
+    /// Path to the cache file or directory, defaults to `.oxlintcache`
+    #[bpaf(argument("PATH"), hide_usage)]
+    pub cache_location: Option<PathBuf>,
+}
+
 /// Output
 #[derive(Debug, Clone, Bpaf)]
 pub struct OutputOptions {
-    /// Use a specific output format (default, json, unix, checkstyle, github)
+    /// Use a specific output format (default, json, unix, checkstyle, github, sarif, junit, gitlab, stylish)
     #[bpaf(long, short, fallback(OutputFormat::Default), hide_usage)]
     pub format: OutputFormat,
 }
@@ -192,6 +303,16 @@ pub enum OutputFormat {
     Json,
     Unix,
     Checkstyle,
+    /// SARIF 2.1.0, e.g. for GitHub code scanning
+    /// <https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html>
+    Sarif,
+    Junit,
+    /// GitLab Code Quality
+    /// <https://docs.gitlab.com/ee/ci/testing/code_quality.html#code-quality-report-format>
+    Gitlab,
+    /// ESLint's default formatter
+    /// <https://eslint.org/docs/latest/use/formatters/#stylish>
+    Stylish,
 }
 
 impl FromStr for OutputFormat {
@@ -204,6 +325,10 @@ impl FromStr for OutputFormat {
             "unix" => Ok(Self::Unix),
             "checkstyle" => Ok(Self::Checkstyle),
             "github" => Ok(Self::Github),
+            "sarif" => Ok(Self::Sarif),
+            "junit" => Ok(Self::Junit),
+            "gitlab" => Ok(Self::Gitlab),
+            "stylish" => Ok(Self::Stylish),
             _ => Err(format!("'{s}' is not a known format")),
         }
     }
@@ -306,7 +431,7 @@ mod lint_options {
 
     use oxc_linter::AllowWarnDeny;
 
-    use super::{lint_command, LintCommand, OutputFormat};
+    use super::{lint_command, FixDryRunFormat, LintCommand, OutputFormat};
 
     fn get_lint_options(arg: &str) -> LintCommand {
         let args = arg.split(' ').map(std::string::ToString::to_string).collect::<Vec<_>>();
@@ -383,6 +508,17 @@ mod lint_options {
         assert!(options.fix_options.fix);
     }
 
+    #[test]
+    fn fix_dry_run() {
+        let options = get_lint_options("--fix-dry-run test.js");
+        assert!(options.fix_options.fix_dry_run);
+        assert!(options.fix_options.fix_kind().is_some());
+        assert_eq!(options.fix_options.fix_dry_run_format, FixDryRunFormat::Diff);
+
+        let options = get_lint_options("--fix-dry-run --fix-dry-run-format json test.js");
+        assert_eq!(options.fix_options.fix_dry_run_format, FixDryRunFormat::Json);
+    }
+
     #[test]
     fn filter() {
         let options =
@@ -403,6 +539,16 @@ mod lint_options {
         let options = get_lint_options("-f json");
         assert_eq!(options.output_options.format, OutputFormat::Json);
         assert!(options.paths.is_empty());
+
+        for (format, expected) in [
+            ("sarif", OutputFormat::Sarif),
+            ("junit", OutputFormat::Junit),
+            ("gitlab", OutputFormat::Gitlab),
+            ("stylish", OutputFormat::Stylish),
+        ] {
+            let options = get_lint_options(&format!("--format {format}"));
+            assert_eq!(options.output_options.format, expected);
+        }
     }
 
     #[test]
@@ -0,0 +1,115 @@
+use std::{io::Write, path::Path};
+
+use cow_utils::CowUtils;
+use oxc_linter::FixedFile;
+use serde_json::{json, Value};
+use similar::TextDiff;
+
+use crate::cli::FixDryRunFormat;
+
+/// Write the fixes of a `--fix-dry-run` to `stdout`.
+pub fn write_fixes(files: &[FixedFile], format: FixDryRunFormat, stdout: &mut dyn Write) {
+    match format {
+        FixDryRunFormat::Diff => write_diff(files, stdout),
+        FixDryRunFormat::Json => write_json(files, stdout),
+    }
+    stdout.flush().unwrap();
+}
+
+/// Unified diff of each file, with `a/` and `b/` path prefixes like `git diff`.
+fn write_diff(files: &[FixedFile], stdout: &mut dyn Write) {
+    for file in files {
+        let path = display_path(&file.path);
+        let diff = TextDiff::from_lines(&file.source_text, &file.fixed_source_text);
+        let original_header = format!("a/{path}");
+        let fixed_header = format!("b/{path}");
+        write!(stdout, "{}", diff.unified_diff().header(&original_header, &fixed_header)).unwrap();
+    }
+}
+
+fn write_json(files: &[FixedFile], stdout: &mut dyn Write) {
+    let files = files
+        .iter()
+        .map(|file| {
+            let applied_fixes = file
+                .fixes
+                .iter()
+                .map(|fix| {
+                    json!({
+                        "pass": fix.pass,
+                        "code": fix.code,
+                        "message": fix.message,
+                        "span": { "start": fix.span.start, "end": fix.span.end },
+                        "content": fix.content,
+                    })
+                })
+                .collect::<Vec<_>>();
+            json!({ "path": display_path(&file.path), "fixes": applied_fixes })
+        })
+        .collect::<Vec<Value>>();
+    writeln!(stdout, "{}", serde_json::to_string_pretty(&files).unwrap()).unwrap();
+}
+
+/// `path` with `/` separators on all platforms.
+fn display_path(path: &Path) -> String {
+    path.to_string_lossy().cow_replace('\\', "/").into_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use oxc_linter::{AppliedFix, FixedFile};
+    use oxc_span::Span;
+
+    use super::write_fixes;
+    use crate::cli::FixDryRunFormat;
+
+    fn fixed_file() -> FixedFile {
+        FixedFile {
+            path: PathBuf::from("src/index.js"),
+            source_text: "foo();\ndebugger;\nbar();\n".to_string(),
+            fixed_source_text: "foo();\n\nbar();\n".to_string(),
+            fixes: vec![AppliedFix {
+                pass: 1,
+                code: "eslint(no-debugger)".to_string(),
+                message: "`debugger` statement is not allowed".to_string(),
+                span: Span::new(7, 16),
+                content: String::new(),
+            }],
+        }
+    }
+
+    fn write(format: FixDryRunFormat) -> String {
+        let mut output = vec![];
+        write_fixes(&[fixed_file()], format, &mut output);
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn diff() {
+        assert_eq!(
+            write(FixDryRunFormat::Diff),
+            "--- a/src/index.js\n+++ b/src/index.js\n@@ -1,3 +1,3 @@\n foo();\n-debugger;\n+\n bar();\n"
+        );
+    }
+
+    #[test]
+    fn json() {
+        let output: serde_json::Value =
+            serde_json::from_str(&write(FixDryRunFormat::Json)).unwrap();
+        assert_eq!(
+            output,
+            serde_json::json!([{
+                "path": "src/index.js",
+                "fixes": [{
+                    "pass": 1,
+                    "code": "eslint(no-debugger)",
+                    "message": "`debugger` statement is not allowed",
+                    "span": { "start": 7, "end": 16 },
+                    "content": "",
+                }],
+            }])
+        );
+    }
+}
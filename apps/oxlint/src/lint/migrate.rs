@@ -0,0 +1,204 @@
+use std::{
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use oxc_linter::{EslintMigration, CONFIG_FILE_NAME};
+use saphyr::{Yaml, YamlLoader};
+use serde_json::Value;
+
+use crate::cli::CliRunResult;
+
+/// ESLint v8 configuration files, in the order ESLint looks them up.
+const ESLINT_CONFIG_FILES: &[&str] = &[
+    ".eslintrc.js",
+    ".eslintrc.cjs",
+    ".eslintrc.yaml",
+    ".eslintrc.yml",
+    ".eslintrc.json",
+    ".eslintrc",
+    "package.json",
+];
+
+/// Translate the ESLint configuration in `dir` into `dir/.oxlintrc.json`, and
+/// write a report of everything that could not be migrated to `stdout`.
+pub fn migrate(dir: &Path, stdout: &mut dyn Write) -> CliRunResult {
+    let oxlintrc_path = dir.join(CONFIG_FILE_NAME);
+    if oxlintrc_path.exists() {
+        return CliRunResult::InvalidOptions {
+            message: format!("{oxlintrc_path:?} already exists."),
+        };
+    }
+
+    let (eslintrc_path, eslintrc) = match read_eslint_config(dir) {
+        Ok(config) => config,
+        Err(message) => return CliRunResult::InvalidOptions { message },
+    };
+    let migration = match EslintMigration::from_eslintrc(&eslintrc) {
+        Ok(migration) => migration,
+        Err(diagnostic) => {
+            return CliRunResult::InvalidOptions {
+                message: format!("{diagnostic} in {eslintrc_path:?}"),
+            }
+        }
+    };
+
+    let mut json = serde_json::to_string_pretty(&migration.config).unwrap();
+    json.push('\n');
+    if let Err(err) = fs::write(&oxlintrc_path, json) {
+        return CliRunResult::InvalidOptions {
+            message: format!("Failed to write {oxlintrc_path:?}: {err}"),
+        };
+    }
+
+    let eslintrc_name = eslintrc_path.file_name().unwrap().to_string_lossy();
+    writeln!(stdout, "Migrated {eslintrc_name} to {CONFIG_FILE_NAME}.").unwrap();
+    if !migration.unsupported_rules.is_empty() {
+        let count = migration.unsupported_rules.len();
+        let s = if count == 1 { "" } else { "s" };
+        writeln!(stdout, "\n{count} rule{s} not implemented by oxlint:").unwrap();
+        for rule in &migration.unsupported_rules {
+            writeln!(stdout, "  - {rule}").unwrap();
+        }
+    }
+    if !migration.skipped.is_empty() {
+        writeln!(stdout, "\nNot migrated:").unwrap();
+        for skipped in &migration.skipped {
+            writeln!(stdout, "  - {skipped}").unwrap();
+        }
+    }
+    stdout.flush().unwrap();
+
+    CliRunResult::None
+}
+
+/// Find and parse the ESLint configuration in `dir`.
+fn read_eslint_config(dir: &Path) -> Result<(PathBuf, Value), String> {
+    for file_name in ESLINT_CONFIG_FILES {
+        let path = dir.join(file_name);
+        if !path.is_file() {
+            continue;
+        }
+        let source =
+            fs::read_to_string(&path).map_err(|err| format!("Failed to read {path:?}: {err}"))?;
+
+        let config = match *file_name {
+            ".eslintrc.js" | ".eslintrc.cjs" => {
+                return Err(format!(
+                    "Failed to migrate {path:?}: JavaScript configuration files are not supported, please convert it to .eslintrc.json first."
+                ));
+            }
+            ".eslintrc.yaml" | ".eslintrc.yml" => parse_yaml(&source),
+            ".eslintrc.json" => parse_json(source),
+            // Legacy `.eslintrc` files may be JSON or YAML.
+            ".eslintrc" => parse_json(source.clone()).or_else(|_| parse_yaml(&source)),
+            _ => match parse_json(source) {
+                Ok(mut package_json) => match package_json.get_mut("eslintConfig") {
+                    Some(config) => Ok(config.take()),
+                    None => continue,
+                },
+                Err(err) => Err(err),
+            },
+        }
+        .map_err(|err| format!("Failed to parse {path:?}: {err}"))?;
+
+        return Ok((path, config));
+    }
+
+    Err(format!("No ESLint configuration found in {dir:?}."))
+}
+
+fn parse_json(mut source: String) -> Result<Value, String> {
+    json_strip_comments::strip(&mut source).map_err(|err| err.to_string())?;
+    serde_json::from_str(&source).map_err(|err| err.to_string())
+}
+
+fn parse_yaml(source: &str) -> Result<Value, String> {
+    let documents = YamlLoader::load_from_str(source).map_err(|err| err.to_string())?;
+    Ok(documents.first().map_or(Value::Null, yaml_to_json))
+}
+
+fn yaml_to_json(yaml: &Yaml) -> Value {
+    match yaml {
+        Yaml::Real(_) => yaml.as_f64().map_or(Value::Null, Value::from),
+        Yaml::Integer(integer) => Value::from(*integer),
+        Yaml::String(string) => Value::from(string.as_str()),
+        Yaml::Boolean(boolean) => Value::from(*boolean),
+        Yaml::Array(array) => array.iter().map(yaml_to_json).collect(),
+        Yaml::Hash(hash) => Value::Object(
+            hash.iter()
+                .filter_map(|(key, value)| {
+                    let key = match key {
+                        Yaml::String(key) => key.clone(),
+                        Yaml::Integer(key) => key.to_string(),
+                        _ => return None,
+                    };
+                    Some((key, yaml_to_json(value)))
+                })
+                .collect(),
+        ),
+        Yaml::Alias(_) | Yaml::Null | Yaml::BadValue => Value::Null,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{env, fs};
+
+    use serde_json::json;
+
+    use super::{migrate, read_eslint_config};
+    use crate::cli::CliRunResult;
+
+    #[test]
+    fn read_yaml() {
+        let dir = env::current_dir().unwrap().join("fixtures/migrate/yaml");
+        let (path, config) = read_eslint_config(&dir).unwrap();
+        assert!(path.ends_with(".eslintrc.yaml"));
+        assert_eq!(config["extends"], json!(["eslint:recommended"]));
+        assert_eq!(config["env"], json!({ "browser": true }));
+        assert_eq!(config["rules"]["eqeqeq"], json!(["warn", "smart"]));
+        assert_eq!(config["rules"]["no-console"], json!(1));
+    }
+
+    #[test]
+    fn read_package_json() {
+        let dir = env::current_dir().unwrap().join("fixtures/migrate/package_json");
+        let (path, config) = read_eslint_config(&dir).unwrap();
+        assert!(path.ends_with("package.json"));
+        assert_eq!(config["rules"]["no-debugger"], json!("error"));
+    }
+
+    #[test]
+    fn write_oxlintrc() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join(".eslintrc.json"),
+            r#"{
+                // comments are allowed
+                "extends": ["eslint:recommended", "plugin:react/recommended"],
+                "ignorePatterns": ["dist/"],
+                "rules": { "import/no-unresolved": "error" }
+            }"#,
+        )
+        .unwrap();
+
+        let mut stdout: Vec<u8> = vec![];
+        assert!(matches!(migrate(dir.path(), &mut stdout), CliRunResult::None));
+        let report = String::from_utf8(stdout).unwrap();
+        assert!(report.starts_with("Migrated .eslintrc.json to .oxlintrc.json."));
+        assert!(report.contains("  - import/no-unresolved\n"));
+
+        let oxlintrc = fs::read_to_string(dir.path().join(".oxlintrc.json")).unwrap();
+        let oxlintrc: serde_json::Value = serde_json::from_str(&oxlintrc).unwrap();
+        assert_eq!(oxlintrc["ignorePatterns"], json!(["dist/"]));
+        assert_eq!(oxlintrc["rules"]["react/jsx-key"], json!("error"));
+
+        // An existing `.oxlintrc.json` is not overwritten.
+        assert!(matches!(
+            migrate(dir.path(), &mut Vec::<u8>::new()),
+            CliRunResult::InvalidOptions { .. }
+        ));
+    }
+}
@@ -1,10 +1,14 @@
-use std::{env, io::BufWriter, time::Instant};
+mod fix_dry_run;
+mod migrate;
+
+use std::{env, io::BufWriter, path::PathBuf, sync::Arc, time::Instant};
 
 use ignore::gitignore::Gitignore;
-use oxc_diagnostics::{DiagnosticService, GraphicalReportHandler};
+use oxc_diagnostics::{DiagnosticService, Error, GraphicalReportHandler};
 use oxc_linter::{
-    loader::LINT_PARTIAL_LOADER_EXT, AllowWarnDeny, InvalidFilterKind, LintFilter, LintService,
-    LintServiceOptions, Linter, OxlintOptions,
+    loader::LINT_PARTIAL_LOADER_EXT, AllowWarnDeny, Baseline, ConfigDiscovery, InvalidFilterKind,
+    LintCache, LintFilter, LintService, LintServiceOptions, Linter, OxlintOptions,
+    BASELINE_FILE_NAME, CACHE_FILE_NAME,
 };
 use oxc_span::VALID_EXTENSIONS;
 
@@ -34,11 +38,24 @@ impl Runner for LintRunner {
             return CliRunResult::None;
         }
 
+        if self.options.migrate {
+            let Ok(cwd) = env::current_dir() else {
+                return CliRunResult::InvalidOptions {
+                    message: "Failed to get current working directory.".to_string(),
+                };
+            };
+            let mut stdout = BufWriter::new(std::io::stdout());
+            return migrate::migrate(&cwd, &mut stdout);
+        }
+
         let LintCommand {
             paths,
             filter,
             basic_options,
             warning_options,
+            inline_config_options,
+            baseline_options,
+            cache_options,
             ignore_options,
             fix_options,
             enable_plugins,
@@ -81,6 +98,13 @@ impl Runner for LintRunner {
             }
         }
 
+        // Everything that affects the diagnostics of all files, except for the
+        // contents of the configuration file which are added below.
+        let cache_config = format!(
+            "{}\n{filter:?}\n{basic_options:?}\n{enable_plugins:?}\n{inline_config_options:?}",
+            env!("CARGO_PKG_VERSION")
+        );
+
         let filter = match Self::get_filters(filter) {
             Ok(filter) => filter,
             Err(e) => return e,
@@ -92,18 +116,43 @@ impl Runner for LintRunner {
             .copied()
             .collect::<Vec<&'static str>>();
 
-        let paths =
-            Walk::new(&paths, &ignore_options).with_extensions(Extensions(extensions)).paths();
+        let cwd = std::env::current_dir().unwrap();
+        let config_path = basic_options.config;
+
+        // `.oxlintrc.json` files found next to linted files are merged with the
+        // configuration passed with `-c`, which takes precedence.
+        let discovery = match ConfigDiscovery::new(config_path.as_deref()) {
+            Ok(discovery) => discovery,
+            Err(diagnostic) => return Self::invalid_config(&Error::new(diagnostic)),
+        };
+
+        let cache_config = format!("{cache_config}\n{:?}", discovery.base_hash());
+
+        // `ignorePatterns` of the configuration passed with `-c` and of
+        // discovered configuration files apply to the directory of each
+        // configuration file.
+        let discovery = Arc::new(discovery);
+        let mut walk = Walk::new(&paths, &ignore_options).with_extensions(Extensions(extensions));
+        if !ignore_options.no_ignore {
+            let discovery = Arc::clone(&discovery);
+            let cwd = cwd.clone();
+            walk = walk.with_ignore_filter(move |path, is_dir| {
+                discovery.is_ignored(&cwd.join(path), is_dir)
+            });
+        }
+        let paths = walk.paths();
 
         let number_of_files = paths.len();
 
-        let cwd = std::env::current_dir().unwrap();
-        let mut options =
-            LintServiceOptions::new(cwd, paths).with_cross_module(enable_plugins.import_plugin);
+        let mut options = LintServiceOptions::new(cwd, paths)
+            .with_cross_module(enable_plugins.import_plugin)
+            .with_config_discovery(discovery)
+            .with_fix_dry_run(fix_options.fix_dry_run);
         let lint_options = OxlintOptions::default()
             .with_filter(filter)
-            .with_config_path(basic_options.config)
+            .with_config_path(config_path.clone())
             .with_fix(fix_options.fix_kind())
+            .with_report_unused_directives(inline_config_options.report_unused_directives())
             .with_react_plugin(enable_plugins.react_plugin)
             .with_unicorn_plugin(enable_plugins.unicorn_plugin)
             .with_typescript_plugin(enable_plugins.typescript_plugin)
@@ -120,15 +169,8 @@ impl Runner for LintRunner {
             .with_security_plugin(enable_plugins.security_plugin);
 
         let linter = match Linter::from_options(lint_options) {
-            Ok(lint_service) => lint_service,
-            Err(diagnostic) => {
-                let handler = GraphicalReportHandler::new();
-                let mut err = String::new();
-                handler.render_report(&mut err, diagnostic.as_ref()).unwrap();
-                return CliRunResult::InvalidOptions {
-                    message: format!("Failed to parse configuration file.\n{err}"),
-                };
-            }
+            Ok(linter) => linter,
+            Err(diagnostic) => return Self::invalid_config(&diagnostic),
         };
 
         let tsconfig = basic_options.tsconfig;
@@ -145,6 +187,27 @@ impl Runner for LintRunner {
             }
         }
 
+        if let Some(mode) = baseline_options.mode() {
+            let path =
+                baseline_options.baseline_file.unwrap_or_else(|| PathBuf::from(BASELINE_FILE_NAME));
+            match Baseline::new(options.cwd(), path, mode) {
+                Ok(baseline) => options = options.with_baseline(baseline),
+                Err(diagnostic) => {
+                    return CliRunResult::InvalidOptions { message: diagnostic.to_string() }
+                }
+            }
+        }
+
+        if cache_options.cache {
+            let mut location =
+                cache_options.cache_location.unwrap_or_else(|| PathBuf::from(CACHE_FILE_NAME));
+            if location.is_dir() {
+                location.push(CACHE_FILE_NAME);
+            }
+            let cache = LintCache::new(options.cwd(), location, &cache_config);
+            options = options.with_cache(cache);
+        }
+
         let lint_service = LintService::new(linter, options);
         let mut diagnostic_service =
             Self::get_diagnostic_service(&warning_options, &output_options, &misc_options);
@@ -159,6 +222,24 @@ impl Runner for LintRunner {
         });
         diagnostic_service.run();
 
+        if let Some(cache) = lint_service.cache() {
+            if let Err(diagnostic) = cache.save() {
+                return CliRunResult::InvalidOptions { message: diagnostic.to_string() };
+            }
+        }
+
+        if let Some(baseline) = lint_service.baseline() {
+            if let Err(diagnostic) = baseline.save() {
+                return CliRunResult::InvalidOptions { message: diagnostic.to_string() };
+            }
+        }
+
+        if fix_options.fix_dry_run {
+            let mut stdout = BufWriter::new(std::io::stdout());
+            let fixed_files = lint_service.take_fixed_files();
+            fix_dry_run::write_fixes(&fixed_files, fix_options.fix_dry_run_format, &mut stdout);
+        }
+
         CliRunResult::LintResult(LintResult {
             duration: now.elapsed(),
             number_of_rules: lint_service.linter().number_of_rules(),
@@ -167,7 +248,9 @@ impl Runner for LintRunner {
             number_of_errors: diagnostic_service.errors_count(),
             max_warnings_exceeded: diagnostic_service.max_warnings_exceeded(),
             deny_warnings: warning_options.deny_warnings,
-            print_summary: matches!(output_options.format, OutputFormat::Default),
+            // Only the fixes are printed in a silent dry run, so they can be piped.
+            print_summary: matches!(output_options.format, OutputFormat::Default)
+                && !(fix_options.fix_dry_run && misc_options.silent),
         })
     }
 }
@@ -189,10 +272,23 @@ impl LintRunner {
             OutputFormat::Unix => diagnostic_service.set_unix_reporter(),
             OutputFormat::Checkstyle => diagnostic_service.set_checkstyle_reporter(),
             OutputFormat::Github => diagnostic_service.set_github_reporter(),
+            OutputFormat::Sarif => diagnostic_service.set_sarif_reporter(),
+            OutputFormat::Junit => diagnostic_service.set_junit_reporter(),
+            OutputFormat::Gitlab => diagnostic_service.set_gitlab_reporter(),
+            OutputFormat::Stylish => diagnostic_service.set_stylish_reporter(),
         }
         diagnostic_service
     }
 
+    fn invalid_config(diagnostic: &Error) -> CliRunResult {
+        let handler = GraphicalReportHandler::new();
+        let mut err = String::new();
+        handler.render_report(&mut err, diagnostic.as_ref()).unwrap();
+        CliRunResult::InvalidOptions {
+            message: format!("Failed to parse configuration file.\n{err}"),
+        }
+    }
+
     // moved into a separate function for readability, but it's only ever used
     // in one place.
     fn get_filters(
@@ -468,6 +564,202 @@ mod test {
         assert_eq!(result.number_of_errors, 0);
     }
 
+    #[test]
+    fn overrides() {
+        let args = &["-c", "fixtures/overrides/oxlintrc.json", "fixtures/overrides"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 3);
+        assert_eq!(result.number_of_warnings, 1);
+        assert_eq!(result.number_of_errors, 1);
+    }
+
+    #[test]
+    fn overrides_relative_to_config_file() {
+        // `src/*.js` is resolved against the directory of the config, and `*.js`
+        // against the directory of the config it extends.
+        let args =
+            &["-c", "fixtures/overrides_config_dir/oxlintrc.json", "fixtures/overrides_config_dir"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 3);
+        assert_eq!(result.number_of_warnings, 1);
+        assert_eq!(result.number_of_errors, 1);
+    }
+
+    #[test]
+    fn custom_rules() {
+        let args = &["-c", "fixtures/custom_rules/oxlintrc.json", "fixtures/custom_rules"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 2);
+        assert_eq!(result.number_of_warnings, 1);
+        assert_eq!(result.number_of_errors, 2);
+    }
+
+    #[test]
+    fn ignore_patterns() {
+        let args = &["-c", "fixtures/ignore_patterns/oxlintrc.json", "fixtures/ignore_patterns"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 1);
+        assert_eq!(result.number_of_warnings, 1);
+        assert_eq!(result.number_of_errors, 0);
+    }
+
+    #[test]
+    fn ignore_patterns_anchored_to_config_file() {
+        // `/debugger.js` only matches next to the configuration file.
+        let args = &[
+            "-c",
+            "fixtures/ignore_patterns_anchored/oxlintrc.json",
+            "fixtures/ignore_patterns_anchored",
+        ];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 1);
+        assert_eq!(result.number_of_warnings, 1);
+        assert_eq!(result.number_of_errors, 0);
+    }
+
+    #[test]
+    fn invalid_ignore_patterns() {
+        let args = &[
+            "-c",
+            "fixtures/ignore_patterns_invalid/oxlintrc.json",
+            "fixtures/ignore_patterns_invalid",
+        ];
+        assert!(test_invalid_options(args).contains("Failed to parse `ignorePatterns`"));
+    }
+
+    #[test]
+    fn report_unused_directives() {
+        let args = &["fixtures/report_unused_directives"];
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 0);
+        assert_eq!(result.number_of_errors, 0);
+
+        let args = &["--report-unused-disable-directives", "fixtures/report_unused_directives"];
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 0);
+        assert_eq!(result.number_of_errors, 3);
+
+        let args = &[
+            "--report-unused-disable-directives-severity=warn",
+            "fixtures/report_unused_directives",
+        ];
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 3);
+        assert_eq!(result.number_of_errors, 0);
+    }
+
+    #[test]
+    fn baseline() {
+        let dir = tempfile::tempdir().unwrap();
+        let baseline_file = dir.path().join("baseline.json");
+        let baseline_file = baseline_file.to_str().unwrap();
+
+        let args =
+            &["--baseline-write", "--baseline-file", baseline_file, "fixtures/linter/debugger.js"];
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 0);
+        let baseline = std::fs::read_to_string(baseline_file).unwrap();
+        assert!(baseline.contains("fixtures/linter/debugger.js"));
+        assert!(baseline.contains("eslint(no-debugger)"));
+
+        // Only `nan.js` is reported.
+        let args = &["--baseline", "--baseline-file", baseline_file, "fixtures/linter"];
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 1);
+
+        // Entries that no longer occur are pruned.
+        let args = &[
+            "--baseline",
+            "--baseline-file",
+            baseline_file,
+            "-W",
+            "correctness",
+            "-A",
+            "no-debugger",
+            "fixtures/linter",
+        ];
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 1);
+        let baseline = std::fs::read_to_string(baseline_file).unwrap();
+        assert_eq!(baseline, "{\n  \"files\": {}\n}\n");
+
+        let args = &["--baseline", "--baseline-file", "does-not-exist.json", "fixtures/linter"];
+        let options = lint_command().run_inner(args).unwrap();
+        assert!(matches!(LintRunner::new(options).run(), CliRunResult::InvalidOptions { .. }));
+    }
+
+    #[test]
+    fn cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let location = dir.path().to_str().unwrap();
+        let args = &["--cache", "--cache-location", location, "fixtures/linter"];
+
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 2);
+        assert!(dir.path().join(".oxlintcache").is_file());
+
+        // Diagnostics of unchanged files are replayed.
+        let result = test(args);
+        assert_eq!(result.number_of_files, 2);
+        assert_eq!(result.number_of_warnings, 2);
+        assert_eq!(result.number_of_errors, 0);
+
+        // The cache is invalidated and rewritten when the configuration changes.
+        let cache = std::fs::read_to_string(dir.path().join(".oxlintcache")).unwrap();
+        let args = &[
+            "--cache",
+            "--cache-location",
+            location,
+            "-W",
+            "correctness",
+            "-A",
+            "no-debugger",
+            "fixtures/linter",
+        ];
+        let result = test(args);
+        assert_eq!(result.number_of_warnings, 1);
+        let rewritten_cache = std::fs::read_to_string(dir.path().join(".oxlintcache")).unwrap();
+        assert_ne!(cache, rewritten_cache);
+        assert!(!rewritten_cache.contains("no-debugger"));
+    }
+
+    #[test]
+    fn fix_dry_run() {
+        let path = "fixtures/fix_dry_run/test.js";
+        let source_text = std::fs::read_to_string(path).unwrap();
+
+        let result = test(&[path]);
+        assert_eq!(result.number_of_warnings, 3);
+
+        // The nested spread is fixed in two passes, and the file is not written.
+        let result = test(&["--fix-dry-run", path]);
+        assert_eq!(result.number_of_warnings, 0);
+        assert_eq!(result.number_of_errors, 0);
+        assert_eq!(std::fs::read_to_string(path).unwrap(), source_text);
+    }
+
+    #[test]
+    fn nested_config() {
+        let args = &["fixtures/nested_config"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 4);
+        assert_eq!(result.number_of_warnings, 2);
+        assert_eq!(result.number_of_errors, 0);
+    }
+
+    #[test]
+    fn nested_config_overrides_and_ignore_patterns() {
+        // Globs in `overrides` and `ignorePatterns` are resolved against the
+        // directory of the configuration file declaring them, so `lib/*.js` of
+        // the parent config does not match `package/lib/console.js`, and
+        // `package/generated/` is ignored.
+        let args = &["fixtures/nested_overrides"];
+        let result = test(args);
+        assert_eq!(result.number_of_files, 3);
+        assert_eq!(result.number_of_warnings, 1);
+        assert_eq!(result.number_of_errors, 0);
+    }
+
     #[test]
     fn typescript_eslint() {
         let args = &[
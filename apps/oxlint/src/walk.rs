@@ -1,6 +1,6 @@
 use std::{
     path::{Path, PathBuf},
-    sync::mpsc,
+    sync::{mpsc, Arc},
 };
 
 use ignore::{overrides::OverrideBuilder, DirEntry};
@@ -17,15 +17,20 @@ impl Default for Extensions {
     }
 }
 
+/// Whether a file or directory (`true`) is ignored.
+type IgnoreFilter = Arc<dyn Fn(&Path, bool) -> bool + Send + Sync>;
+
 pub struct Walk {
     inner: ignore::WalkParallel,
     /// The file extensions to include during the traversal.
     extensions: Extensions,
+    ignore_filter: Option<IgnoreFilter>,
 }
 
 struct WalkBuilder {
     sender: mpsc::Sender<Vec<Box<Path>>>,
     extensions: Extensions,
+    ignore_filter: Option<IgnoreFilter>,
 }
 
 impl<'s> ignore::ParallelVisitorBuilder<'s> for WalkBuilder {
@@ -34,6 +39,7 @@ impl<'s> ignore::ParallelVisitorBuilder<'s> for WalkBuilder {
             paths: vec![],
             sender: self.sender.clone(),
             extensions: self.extensions.clone(),
+            ignore_filter: self.ignore_filter.clone(),
         })
     }
 }
@@ -42,6 +48,7 @@ struct WalkCollector {
     paths: Vec<Box<Path>>,
     sender: mpsc::Sender<Vec<Box<Path>>>,
     extensions: Extensions,
+    ignore_filter: Option<IgnoreFilter>,
 }
 
 impl Drop for WalkCollector {
@@ -55,9 +62,14 @@ impl ignore::ParallelVisitor for WalkCollector {
     fn visit(&mut self, entry: Result<ignore::DirEntry, ignore::Error>) -> ignore::WalkState {
         match entry {
             Ok(entry) => {
-                if entry.file_type().is_some_and(|ft| !ft.is_dir())
-                    && Walk::is_wanted_entry(&entry, &self.extensions)
+                let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+                // Paths given on the command line are never ignored.
+                if entry.depth() > 0
+                    && self.ignore_filter.as_ref().is_some_and(|f| f(entry.path(), is_dir))
                 {
+                    return ignore::WalkState::Skip;
+                }
+                if !is_dir && Walk::is_wanted_entry(&entry, &self.extensions) {
                     self.paths.push(entry.path().to_path_buf().into_boxed_path());
                 }
                 ignore::WalkState::Continue
@@ -106,12 +118,13 @@ impl Walk {
         // * it is super rare to have symlinked source code
         let inner =
             inner.ignore(false).git_global(false).follow_links(options.symlinks).build_parallel();
-        Self { inner, extensions: Extensions::default() }
+        Self { inner, extensions: Extensions::default(), ignore_filter: None }
     }
 
     pub fn paths(self) -> Vec<Box<Path>> {
         let (sender, receiver) = mpsc::channel::<Vec<Box<Path>>>();
-        let mut builder = WalkBuilder { sender, extensions: self.extensions };
+        let mut builder =
+            WalkBuilder { sender, extensions: self.extensions, ignore_filter: self.ignore_filter };
         self.inner.visit(&mut builder);
         drop(builder);
         receiver.into_iter().flatten().collect()
@@ -122,6 +135,16 @@ impl Walk {
         self
     }
 
+    /// Skip files and directories for which `ignore_filter` returns `true`,
+    /// e.g. those matching the `ignorePatterns` of nested configuration files.
+    pub fn with_ignore_filter<F>(mut self, ignore_filter: F) -> Self
+    where
+        F: Fn(&Path, bool) -> bool + Send + Sync + 'static,
+    {
+        self.ignore_filter = Some(Arc::new(ignore_filter));
+        self
+    }
+
     fn is_wanted_entry(dir_entry: &DirEntry, extensions: &Extensions) -> bool {
         let Some(file_type) = dir_entry.file_type() else { return false };
         if file_type.is_dir() {
@@ -2,7 +2,7 @@ use std::{mem, ops::ControlFlow, path::Path};
 
 use oxc_allocator::Allocator;
 use oxc_ast::{ast::Program, Trivias};
-use oxc_codegen::{CodeGenerator, CodegenOptions, CommentOptions};
+use oxc_codegen::{CodeGenerator, CodegenOptions, CommentOptions, LegalComment};
 use oxc_diagnostics::OxcDiagnostic;
 use oxc_mangler::{MangleOptions, Mangler};
 use oxc_minifier::{CompressOptions, Compressor};
@@ -236,7 +236,11 @@ pub trait CompilerInterface {
         mangler: Option<Mangler>,
         options: CodegenOptions,
     ) -> String {
-        let comment_options = CommentOptions { preserve_annotate_comments: true };
+        let comment_options = CommentOptions {
+            preserve_annotate_comments: true,
+            preserve_normal_comments: false,
+            legal_comments: LegalComment::Inline,
+        };
         CodeGenerator::new()
             .with_options(options)
             .with_mangler(mangler)
@@ -64,7 +64,7 @@ fn codegen(source_text: &str, ret: &ParserReturn<'_>, minify: bool) -> String {
         .enable_comment(
             source_text,
             ret.trivias.clone(),
-            CommentOptions { preserve_annotate_comments: true },
+            CommentOptions { preserve_annotate_comments: true, ..CommentOptions::default() },
         )
         .with_options(CodegenOptions { minify, ..CodegenOptions::default() })
         .build(&ret.program)
@@ -27,7 +27,7 @@ fn main() -> std::io::Result<()> {
         return Ok(());
     }
 
-    let CodegenReturn { source_text, source_map } = CodeGenerator::new()
+    let CodegenReturn { source_text, source_map, .. } = CodeGenerator::new()
         .enable_source_map(path.to_string_lossy().as_ref(), &source_text)
         .build(&ret.program);
 
@@ -56,7 +56,7 @@ fn print_binary_operator(op: BinaryOperator, p: &mut Codegen) {
         p.print_space_before_operator(op);
         p.print_str(operator);
         p.prev_op = Some(op);
-        p.prev_op_end = p.code().len();
+        p.prev_op_end = p.code_len();
     }
 }
 
@@ -197,6 +197,7 @@ impl<'a> BinaryExpressionVisitor<'a> {
     pub fn visit_right_and_finish(&self, p: &mut Codegen) {
         p.print_soft_space();
         self.operator.gen(p);
+        p.print_line_break_if_too_long();
         p.print_soft_space();
         self.e.right().gen_expr(p, self.right_precedence, self.ctx & Context::FORBID_IN);
         if self.wrap {
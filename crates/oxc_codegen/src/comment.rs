@@ -1,11 +1,13 @@
+use std::collections::BTreeMap;
+
 use daachorse::DoubleArrayAhoCorasick;
 use once_cell::sync::Lazy;
 use rustc_hash::FxHashMap;
 
-use oxc_ast::{Comment, CommentKind, Trivias};
+use oxc_ast::{Comment, CommentKind, CommentPosition, Trivias};
 use oxc_syntax::identifier::is_line_terminator;
 
-use crate::Codegen;
+use crate::{Codegen, LegalComment, NonAsciiEscape};
 
 static ANNOTATION_MATCHER: Lazy<DoubleArrayAhoCorasick<usize>> = Lazy::new(|| {
     let patterns = vec!["#__NO_SIDE_EFFECTS__", "@__NO_SIDE_EFFECTS__", "@__PURE__", "#__PURE__"];
@@ -13,17 +15,83 @@ static ANNOTATION_MATCHER: Lazy<DoubleArrayAhoCorasick<usize>> = Lazy::new(|| {
     DoubleArrayAhoCorasick::new(patterns).unwrap()
 });
 
+/// Leading comments, and dangling comments attached to a closing bracket.
 pub(crate) type CommentsMap = FxHashMap</* attached_to */ u32, Vec<Comment>>;
 
+/// Trailing comments, e.g. `a; // comment`.
+pub(crate) type TrailingCommentsMap =
+    BTreeMap</* end of the preceding token */ u32, Vec<Comment>>;
+
+/// Keys of [TrailingCommentsMap] by the token which follows the comments,
+/// to find the comments before an expression, e.g. `x = // comment`.
+pub(crate) type TrailingCommentsBeforeMap =
+    FxHashMap</* start of the next token */ u32, /* end of the preceding token */ u32>;
+
 impl<'a> Codegen<'a> {
     pub(crate) fn preserve_annotate_comments(&self) -> bool {
         self.comment_options.preserve_annotate_comments && !self.options.minify
     }
 
-    pub(crate) fn build_comments(&mut self, trivias: &Trivias) {
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) fn build_comments(&mut self, source_text: &'a str, trivias: &Trivias) {
+        // End of the previous trailing comment, and the token it is attached to.
+        let mut prev_trailing: Option<(u32, u32)> = None;
         for comment in trivias.comments().copied() {
+            if Self::is_legal_comment(&comment, source_text) {
+                match self.comment_options.legal_comments {
+                    LegalComment::None => continue,
+                    LegalComment::Inline => {}
+                    LegalComment::Eof | LegalComment::Linked(_) | LegalComment::External => {
+                        let text = comment.real_span().source_text(source_text);
+                        if !self.legal_comments.contains(&text) {
+                            self.legal_comments.push(text);
+                        }
+                        continue;
+                    }
+                }
+            }
+            if comment.position == CommentPosition::Trailing
+                && self.comment_options.preserve_normal_comments
+            {
+                let end = source_text[..comment.real_span_start() as usize].trim_end().len() as u32;
+                let attached_to = match prev_trailing {
+                    Some((prev_end, attached_to)) if prev_end == end => attached_to,
+                    _ => end,
+                };
+                prev_trailing = Some((comment.real_span_end(), attached_to));
+                self.trailing_comments.entry(attached_to).or_default().push(comment);
+                continue;
+            }
             self.comments.entry(comment.attached_to).or_default().push(comment);
         }
+        for (&attached_to, comments) in &self.trailing_comments {
+            let end = comments.last().map_or(attached_to, Comment::real_span_end);
+            self.trailing_comments_before
+                .insert(Self::next_token_start(source_text, end), attached_to);
+        }
+    }
+
+    /// Skip whitespace and comments from `start`.
+    #[allow(clippy::cast_possible_truncation)]
+    fn next_token_start(source_text: &str, start: u32) -> u32 {
+        let mut rest = &source_text[start as usize..];
+        loop {
+            rest = rest.trim_start();
+            if let Some(comment) = rest.strip_prefix("//") {
+                rest = comment.find(is_line_terminator).map_or("", |end| &comment[end..]);
+            } else if let Some(comment) = rest.strip_prefix("/*") {
+                rest = comment.find("*/").map_or("", |end| &comment[end + 2..]);
+            } else {
+                break;
+            }
+        }
+        (source_text.len() - rest.len()) as u32
+    }
+
+    /// `/*! ... */`, `//! ...`, or comments containing `@license` or `@preserve`.
+    fn is_legal_comment(comment: &Comment, source_text: &str) -> bool {
+        let text = comment.span.source_text(source_text);
+        text.starts_with('!') || text.contains("@license") || text.contains("@preserve")
     }
 
     pub(crate) fn has_comment(&self, start: u32) -> bool {
@@ -51,9 +119,23 @@ impl<'a> Codegen<'a> {
     }
 
     /// Weather to keep leading comments.
-    fn is_leading_comments(comment: &Comment, source_text: &str) -> bool {
-        (comment.is_jsdoc(source_text) || (comment.is_line() && Self::is_annotation_comment(comment, source_text)))
-            && comment.preceded_by_newline
+    /// Only inline legal comments are kept when minifying, and only JSDoc comments on their own
+    /// line without [CommentOptions::preserve_normal_comments].
+    ///
+    /// [CommentOptions::preserve_normal_comments]: crate::CommentOptions::preserve_normal_comments
+    fn is_leading_comments(&self, comment: &Comment, source_text: &str) -> bool {
+        if Self::is_legal_comment(comment, source_text) {
+            return true;
+        }
+        let keep = if self.comment_options.preserve_normal_comments {
+            comment.is_line() || !Self::is_annotation_comment(comment, source_text)
+        } else {
+            (comment.is_jsdoc(source_text)
+                || (comment.is_line() && Self::is_annotation_comment(comment, source_text)))
+                && comment.preceded_by_newline
+        };
+        !self.options.minify
+            && keep
             // webpack comment `/*****/`
             && !comment.span.source_text(source_text).chars().all(|c| c == '*')
     }
@@ -62,16 +144,20 @@ impl<'a> Codegen<'a> {
         let comment_source = comment.real_span().source_text(source_text);
         match comment.kind {
             CommentKind::Line => {
-                self.print_str(comment_source);
+                self.print_str_ascii_only(comment_source, NonAsciiEscape::CodePoint);
+            }
+            CommentKind::Block if self.reprint.is_some() => {
+                self.print_original_source(comment.real_span());
             }
             CommentKind::Block => {
-                // Print block comments with our own indentation.
-                let lines = comment_source.split(is_line_terminator);
+                // Print block comments with our own indentation and line endings.
+                let lines =
+                    comment_source.split("\r\n").flat_map(|line| line.split(is_line_terminator));
                 for line in lines {
                     if !line.starts_with("/*") {
                         self.print_indent();
                     }
-                    self.print_str(line.trim_start());
+                    self.print_str_ascii_only(line.trim_start(), NonAsciiEscape::CodePoint);
                     if !line.ends_with("*/") {
                         self.print_hard_newline();
                     }
@@ -80,10 +166,11 @@ impl<'a> Codegen<'a> {
         }
     }
 
+    /// Print the comments before a statement or class element, each on its own line.
+    ///
+    /// Statements call this at the start of a line before printing their indentation,
+    /// class elements call this after printing their indentation.
     pub(crate) fn print_leading_comments(&mut self, start: u32) {
-        if self.options.minify {
-            return;
-        }
         let Some(source_text) = self.source_text else { return };
         let Some(comments) = self.comments.remove(&start) else {
             return;
@@ -91,33 +178,198 @@ impl<'a> Codegen<'a> {
 
         let (comments, unused_comments): (Vec<_>, Vec<_>) = comments
             .into_iter()
-            .partition(|comment| Self::is_leading_comments(comment, source_text));
+            .partition(|comment| self.is_leading_comments(comment, source_text));
 
-        if comments.first().is_some_and(|c| c.preceded_by_newline) {
-            // Skip printing newline if this comment is already on a newline.
-            if self.peek_nth(0).is_some_and(|c| c != '\n' && c != '\t') {
+        let at_line_start = self.peek_nth(0).map_or(true, |c| c == '\n');
+        for (i, comment) in comments.iter().enumerate() {
+            if i == 0 && at_line_start {
+                self.print_indent();
+            } else if comment.preceded_by_newline
+                && (i > 0 || self.peek_nth(0) != Some(self.options.indent_char.as_byte().into()))
+            {
                 self.print_hard_newline();
                 self.print_indent();
+            } else if self.peek_nth(0).is_some_and(|c| !c.is_ascii_whitespace()) {
+                self.print_hard_space();
             }
+            self.print_comment(comment, source_text);
         }
 
-        for (i, comment) in comments.iter().enumerate() {
-            if i >= 1 && comment.preceded_by_newline {
+        if let Some(last) = comments.last() {
+            if last.is_line() || last.followed_by_newline {
                 self.print_hard_newline();
-                self.print_indent();
+                if at_line_start {
+                    self.print_next_indent_as_space = false;
+                } else {
+                    self.print_indent();
+                }
+            } else if at_line_start || self.print_next_indent_as_space {
+                self.print_next_indent_as_space = true;
+            } else {
+                self.print_hard_space();
             }
+        }
 
-            self.print_comment(comment, source_text);
+        if !unused_comments.is_empty() {
+            self.comments.insert(start, unused_comments);
         }
+    }
 
-        if comments.last().is_some_and(|c| c.is_line() || c.followed_by_newline) {
+    /// Print the comments before a closing bracket, each on its own line.
+    pub(crate) fn print_dangling_comments(&mut self, end: u32) {
+        if !self.preserve_normal_comments() || !self.has_comment(end) {
+            return;
+        }
+        if self.peek_nth(0).is_some_and(|c| c != '\n') {
             self.print_hard_newline();
-            self.print_indent();
         }
+        self.print_leading_comments(end);
+        if self.peek_nth(0).is_some_and(|c| c != '\n') {
+            self.print_hard_newline();
+        }
+        self.print_next_indent_as_space = false;
+    }
 
+    /// Print the comments before an expression, e.g. `x = /* comment */ y`,
+    /// followed by a space if `space_after` is set.
+    ///
+    /// Line comments are followed by a line break, so comments containing line breaks are not
+    /// printed after `return`, `throw` and `yield`, where a line break would terminate the
+    /// statement.
+    pub(crate) fn print_inline_comments(&mut self, start: u32, space_after: bool) {
+        if !self.preserve_normal_comments() {
+            return;
+        }
+        let Some(source_text) = self.source_text else { return };
+        // Trailing comments of the preceding token, e.g. `x = // comment`.
+        let trailing_comments = self
+            .trailing_comments_before
+            .remove(&start)
+            .and_then(|key| self.trailing_comments.remove(&key))
+            .unwrap_or_default();
+        let comments = self.comments.remove(&start).unwrap_or_default();
+        if trailing_comments.is_empty() && comments.is_empty() {
+            return;
+        }
+
+        let n = self.code_len();
+        let restricted = self.is_restricted_production();
+        let (comments, unused_comments): (Vec<_>, Vec<_>) =
+            trailing_comments.into_iter().chain(comments).partition(|comment| {
+                if Self::is_annotation_comment(comment, source_text) {
+                    return false;
+                }
+                !restricted
+                    || (comment.is_block()
+                        && !comment.span.source_text(source_text).contains(is_line_terminator))
+            });
+        let mut after_line_comment = false;
+        for (i, comment) in comments.iter().enumerate() {
+            if after_line_comment {
+                self.print_indent();
+            } else if i > 0
+                || self
+                    .peek_nth(0)
+                    .is_some_and(|c| !c.is_ascii_whitespace() && c != '(' && c != '{')
+            {
+                self.print_hard_space();
+            }
+            self.print_str_ascii_only(
+                comment.real_span().source_text(source_text),
+                NonAsciiEscape::CodePoint,
+            );
+            after_line_comment = comment.is_line();
+            if after_line_comment {
+                self.print_hard_newline();
+            }
+        }
+        if after_line_comment {
+            self.print_indent();
+        } else if space_after && !comments.is_empty() {
+            self.print_hard_space();
+        }
+        let unused_comments = unused_comments
+            .into_iter()
+            .filter(|comment| comment.position == CommentPosition::Leading)
+            .collect::<Vec<_>>();
         if !unused_comments.is_empty() {
             self.comments.insert(start, unused_comments);
         }
+
+        // Comments must not hide the start of a statement, arrow function body or default export
+        // from the checks for an object literal or function expression which needs parentheses.
+        let len = self.code_len();
+        if self.start_of_stmt == n {
+            self.start_of_stmt = len;
+        }
+        if self.start_of_arrow_expr == n {
+            self.start_of_arrow_expr = len;
+        }
+        if self.start_of_default_export == n {
+            self.start_of_default_export = len;
+        }
+    }
+
+    fn is_restricted_production(&self) -> bool {
+        // SAFETY: criteria of `from_utf8_unchecked` are met.
+        let code = unsafe { std::str::from_utf8_unchecked(self.code()) }.trim_end();
+        ["return", "throw", "yield", "yield*"].iter().any(|keyword| {
+            code.strip_suffix(keyword).is_some_and(|rest| {
+                !rest.ends_with(|c: char| c == '_' || c == '$' || c.is_alphanumeric())
+            })
+        })
+    }
+
+    pub(crate) fn preserve_normal_comments(&self) -> bool {
+        self.comment_options.preserve_normal_comments && !self.options.minify
+    }
+
+    /// Whether there are trailing comments attached to the tokens ending in `start..end`.
+    pub(crate) fn has_trailing_comments(&self, start: u32, end: u32) -> bool {
+        !self.options.minify
+            && start < end
+            && self.trailing_comments.range(start..end).next().is_some()
+    }
+
+    /// Print the trailing comments attached to the tokens ending in `start..end`, on the current line.
+    ///
+    /// Returns whether a line comment was printed, which must be followed by a line break.
+    pub(crate) fn print_trailing_comments(&mut self, start: u32, end: u32) -> bool {
+        if !self.has_trailing_comments(start, end) {
+            return false;
+        }
+        let Some(source_text) = self.source_text else { return false };
+        let keys =
+            self.trailing_comments.range(start..end).map(|(key, _)| *key).collect::<Vec<_>>();
+        let mut is_line = false;
+        for key in keys {
+            for comment in self.trailing_comments.remove(&key).unwrap_or_default() {
+                self.print_hard_space();
+                self.print_comment(&comment, source_text);
+                is_line = comment.is_line();
+            }
+        }
+        is_line
+    }
+
+    /// Print the trailing comments of a statement before its line break.
+    pub(crate) fn print_statement_trailing_comments(&mut self, end: u32) {
+        if !self.has_trailing_comments(end, end + 1) {
+            return;
+        }
+        let line_ending = self.options.line_ending.as_str();
+        let can_truncate = self.code.ends_with(line_ending.as_bytes())
+            && self
+                .sourcemap_builder
+                .as_ref()
+                .map_or(true, |builder| builder.can_truncate(self.code.len() - line_ending.len()));
+        if can_truncate {
+            self.code.truncate(self.code.len() - line_ending.len());
+            self.print_trailing_comments(end, end + 1);
+            self.print_hard_newline();
+        } else if self.print_trailing_comments(end, end + 1) {
+            self.print_hard_newline();
+        }
     }
 
     fn is_annotation_comment(comment: &Comment, source_text: &str) -> bool {
@@ -173,4 +425,33 @@ impl<'a> Codegen<'a> {
             true
         }
     }
+
+    /// Print the legal comments collected for [LegalComment::Eof] and [LegalComment::Linked].
+    pub(crate) fn print_legal_comments(&mut self) {
+        if self.legal_comments.is_empty() {
+            return;
+        }
+        match &self.comment_options.legal_comments {
+            LegalComment::Eof => {
+                for comment in std::mem::take(&mut self.legal_comments) {
+                    if self.peek_nth(0).is_some_and(|c| c != '\n') {
+                        self.print_hard_newline();
+                    }
+                    self.print_str_ascii_only(comment, NonAsciiEscape::CodePoint);
+                    self.print_hard_newline();
+                }
+            }
+            LegalComment::Linked(path) => {
+                let path = path.clone();
+                if self.peek_nth(0).is_some_and(|c| c != '\n') {
+                    self.print_hard_newline();
+                }
+                self.print_str("/*! For license information please see ");
+                self.print_str(&path);
+                self.print_str(" */");
+                self.print_hard_newline();
+            }
+            LegalComment::None | LegalComment::Inline | LegalComment::External => {}
+        }
+    }
 }
@@ -12,7 +12,8 @@ use oxc_syntax::{
 
 use crate::{
     binary_expr_visitor::{BinaryExpressionVisitor, Binaryish, BinaryishOperator},
-    Codegen, Context, Operator,
+    sourcemap_builder::ScopeKind,
+    Codegen, Context, NonAsciiEscape, Operator,
 };
 
 pub trait Gen: GetSpan {
@@ -33,6 +34,7 @@ pub trait GenExpr: GetSpan {
 
 impl<'a> Gen for Program<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
+        let scope = p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Global, None);
         if let Some(hashbang) = &self.hashbang {
             hashbang.print(p, ctx);
         }
@@ -43,26 +45,31 @@ impl<'a> Gen for Program<'a> {
             stmt.print(p, ctx);
             p.print_semicolon_if_needed();
         }
+        p.print_dangling_comments(self.span.end);
+        if scope {
+            p.leave_scope(self.span.end);
+        }
     }
 }
 
 impl<'a> Gen for Hashbang<'a> {
     fn gen(&self, p: &mut Codegen, _ctx: Context) {
         p.print_str("#!");
-        p.print_str(self.value.as_str());
+        p.print_str_ascii_only(self.value.as_str(), NonAsciiEscape::CodePoint);
         p.print_hard_newline();
     }
 }
 
 impl<'a> Gen for Directive<'a> {
     fn gen(&self, p: &mut Codegen, _ctx: Context) {
+        p.print_leading_comments(self.span.start);
         p.add_source_mapping(self.span.start);
         p.print_indent();
         // A Use Strict Directive may not contain an EscapeSequence or LineContinuation.
         // So here should print original `directive` value, the `expression` value is escaped str.
         // See https://github.com/babel/babel/blob/main/packages/babel-generator/src/generators/base.ts#L64
         p.wrap_quote(|p, _| {
-            p.print_str(self.directive.as_str());
+            p.print_str_ascii_only(self.directive.as_str(), NonAsciiEscape::CodeUnits);
         });
         p.print_char(b';');
         p.print_soft_newline();
@@ -71,7 +78,14 @@ impl<'a> Gen for Directive<'a> {
 
 impl<'a> Gen for Statement<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
+        p.flush_output();
+        p.print_line_break_if_too_long();
+        p.print_original_blank_line(self.span().start);
         p.print_leading_comments(self.span().start);
+        if p.print_original_statement(self) {
+            p.print_statement_trailing_comments(self.span().end);
+            return;
+        }
         match self {
             Self::BlockStatement(stmt) => stmt.print(p, ctx),
             Self::BreakStatement(stmt) => stmt.print(p, ctx),
@@ -140,6 +154,7 @@ impl<'a> Gen for Statement<'a> {
                 p.print_semicolon_after_statement();
             }
         }
+        p.print_statement_trailing_comments(self.span().end);
     }
 }
 
@@ -248,6 +263,7 @@ impl<'a> Gen for BlockStatement<'a> {
 
 impl<'a> Gen for ForStatement<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
+        let scope = p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Block, None);
         p.add_source_mapping(self.span.start);
         p.print_indent();
         p.print_str("for");
@@ -274,11 +290,15 @@ impl<'a> Gen for ForStatement<'a> {
 
         p.print_char(b')');
         p.print_body(&self.body, false, ctx);
+        if scope {
+            p.leave_scope(self.span.end);
+        }
     }
 }
 
 impl<'a> Gen for ForInStatement<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
+        let scope = p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Block, None);
         p.add_source_mapping(self.span.start);
         p.print_indent();
         p.print_str("for");
@@ -292,11 +312,15 @@ impl<'a> Gen for ForInStatement<'a> {
         p.print_expression(&self.right);
         p.print_char(b')');
         p.print_body(&self.body, false, ctx);
+        if scope {
+            p.leave_scope(self.span.end);
+        }
     }
 }
 
 impl<'a> Gen for ForOfStatement<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
+        let scope = p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Block, None);
         p.add_source_mapping(self.span.start);
         p.print_indent();
         p.print_str("for");
@@ -312,6 +336,9 @@ impl<'a> Gen for ForOfStatement<'a> {
         self.right.print_expr(p, Precedence::Comma, Context::empty());
         p.print_char(b')');
         p.print_body(&self.body, false, ctx);
+        if scope {
+            p.leave_scope(self.span.end);
+        }
     }
 }
 
@@ -424,12 +451,16 @@ impl<'a> Gen for SwitchStatement<'a> {
         p.print_expression(&self.discriminant);
         p.print_char(b')');
         p.print_soft_space();
+        let scope = p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Block, None);
         p.print_curly_braces(self.span, self.cases.is_empty(), |p| {
             for case in &self.cases {
                 p.add_source_mapping(case.span.start);
                 case.print(p, ctx);
             }
         });
+        if scope {
+            p.leave_scope(self.span.end);
+        }
         p.print_soft_newline();
         p.needs_semicolon = false;
     }
@@ -612,6 +643,9 @@ impl<'a> Gen for Function<'a> {
         p.print_annotation_comments(self.span.start);
         p.wrap(wrap, |p| {
             p.print_space_before_identifier();
+            let name = self.id.as_ref().map(|id| id.name.as_str());
+            let scope =
+                p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Function, name);
             p.add_source_mapping(self.span.start);
             if self.declare {
                 p.print_str("declare ");
@@ -651,6 +685,9 @@ impl<'a> Gen for Function<'a> {
             } else {
                 p.print_semicolon();
             }
+            if scope {
+                p.leave_scope(self.span.end);
+            }
         });
     }
 }
@@ -810,7 +847,7 @@ impl<'a> Gen for ImportAttribute<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
         match &self.key {
             ImportAttributeKey::Identifier(identifier) => {
-                p.print_str(identifier.name.as_str());
+                p.print_str_ascii_only(identifier.name.as_str(), NonAsciiEscape::CodePoint);
             }
             ImportAttributeKey::StringLiteral(literal) => literal.print(p, ctx),
         };
@@ -1002,6 +1039,10 @@ impl<'a> Gen for ExportDefaultDeclarationKind<'a> {
 
 impl<'a> GenExpr for Expression<'a> {
     fn gen_expr(&self, p: &mut Codegen, precedence: Precedence, ctx: Context) {
+        p.print_inline_comments(self.span().start, true);
+        if p.print_original_expression(self, precedence, ctx) {
+            return;
+        }
         match self {
             Self::BooleanLiteral(lit) => lit.print(p, ctx),
             Self::NullLiteral(lit) => lit.print(p, ctx),
@@ -1060,14 +1101,14 @@ impl<'a> Gen for IdentifierReference<'a> {
         let name = p.get_identifier_reference_name(self);
         p.print_space_before_identifier();
         p.add_source_mapping_for_name(self.span, name);
-        p.print_str(name);
+        p.print_str_ascii_only(name, NonAsciiEscape::CodePoint);
     }
 }
 
 impl<'a> Gen for IdentifierName<'a> {
     fn gen(&self, p: &mut Codegen, _ctx: Context) {
         p.add_source_mapping(self.span.start);
-        p.print_str(self.name.as_str());
+        p.print_str_ascii_only(self.name.as_str(), NonAsciiEscape::CodePoint);
     }
 }
 
@@ -1075,14 +1116,14 @@ impl<'a> Gen for BindingIdentifier<'a> {
     fn gen(&self, p: &mut Codegen, _ctx: Context) {
         let name = p.get_binding_identifier_name(self);
         p.add_source_mapping_for_name(self.span, name);
-        p.print_str(name);
+        p.print_str_ascii_only(name, NonAsciiEscape::CodePoint);
     }
 }
 
 impl<'a> Gen for LabelIdentifier<'a> {
     fn gen(&self, p: &mut Codegen, _ctx: Context) {
         p.add_source_mapping_for_name(self.span, &self.name);
-        p.print_str(self.name.as_str());
+        p.print_str_ascii_only(self.name.as_str(), NonAsciiEscape::CodePoint);
     }
 }
 
@@ -1206,10 +1247,16 @@ impl<'a> Gen for RegExpLiteral<'a> {
             p.print_hard_space();
         }
         p.print_char(b'/');
-        p.print_str(pattern_text.as_ref());
+        // `\u{...}` escapes are only allowed with the `u` and `v` flags.
+        let escape = if self.regex.flags.intersects(RegExpFlags::U | RegExpFlags::V) {
+            NonAsciiEscape::CodePoint
+        } else {
+            NonAsciiEscape::CodeUnits
+        };
+        p.print_str_ascii_only(pattern_text.as_ref(), escape);
         p.print_char(b'/');
         p.print_str(self.regex.flags.to_string().as_str());
-        p.prev_reg_exp_end = p.code().len();
+        p.prev_reg_exp_end = p.code_len();
     }
 }
 
@@ -1288,6 +1335,16 @@ fn print_unquoted_str(s: &str, quote: u8, p: &mut Codegen) {
             '\u{a0}' => {
                 p.print_str("\\xA0");
             }
+            _ if p.options.ascii_only && !c.is_ascii() => {
+                use std::io::Write;
+                if c as u32 <= 0xFF {
+                    write!(p.code, "\\x{:02X}", c as u32).unwrap();
+                } else {
+                    for unit in c.encode_utf16(&mut [0; 2]) {
+                        write!(p.code, "\\u{unit:04X}").unwrap();
+                    }
+                }
+            }
             _ => {
                 p.print_str(c.encode_utf8([0; 4].as_mut()));
             }
@@ -1387,10 +1444,11 @@ impl<'a> GenExpr for CallExpression<'a> {
             }
             p.print_char(b'(');
             let has_comment = (self.span.end > 0 && p.has_comment(self.span.end - 1))
-                || self.arguments.iter().any(|item| p.has_comment(item.span().start));
+                || self.arguments.iter().any(|item| p.has_comment(item.span().start))
+                || p.has_trailing_comments_in_list(&self.arguments, self.span.end);
             if has_comment {
                 p.indent();
-                p.print_list_with_comments(&self.arguments, ctx);
+                p.print_list_with_comments(&self.arguments, self.span.end - 1, ctx);
                 // Handle `/* comment */);`
                 if !p.print_expr_comments(self.span.end - 1) {
                     p.print_soft_newline();
@@ -1441,6 +1499,21 @@ impl<'a> Gen for ArrayExpression<'a> {
         let is_multi_line = self.elements.len() > 2;
         p.add_source_mapping(self.span.start);
         p.print_char(b'[');
+        if p.has_comments_in_list(&self.elements, self.span.end) {
+            p.indent();
+            p.print_list_with_comments(&self.elements, self.span.end - 1, ctx);
+            if matches!(self.elements.last(), Some(ArrayExpressionElement::Elision(_))) {
+                p.print_comma();
+            }
+            if !p.print_expr_comments(self.span.end - 1) {
+                p.print_soft_newline();
+            }
+            p.dedent();
+            p.print_indent();
+            p.add_source_mapping(self.span.end);
+            p.print_char(b']');
+            return;
+        }
         if is_multi_line {
             p.indent();
         }
@@ -1478,6 +1551,18 @@ impl<'a> GenExpr for ObjectExpression<'a> {
         p.wrap(wrap, |p| {
             p.add_source_mapping(self.span.start);
             p.print_char(b'{');
+            if p.has_comments_in_list(&self.properties, self.span.end) {
+                p.indent();
+                p.print_list_with_comments(&self.properties, self.span.end - 1, ctx);
+                if !p.print_expr_comments(self.span.end - 1) {
+                    p.print_soft_newline();
+                }
+                p.dedent();
+                p.print_indent();
+                p.add_source_mapping(self.span.end);
+                p.print_char(b'}');
+                return;
+            }
             if is_multi_line {
                 p.indent();
             }
@@ -1602,6 +1687,8 @@ impl<'a> GenExpr for ArrowFunctionExpression<'a> {
     fn gen_expr(&self, p: &mut Codegen, precedence: Precedence, ctx: Context) {
         p.wrap(precedence >= Precedence::Assign, |p| {
             p.print_annotation_comments(self.span.start);
+            let scope =
+                p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Function, None);
             if self.r#async {
                 p.add_source_mapping(self.span.start);
                 p.print_str("async");
@@ -1634,6 +1721,9 @@ impl<'a> GenExpr for ArrowFunctionExpression<'a> {
             } else {
                 self.body.print(p, ctx);
             }
+            if scope {
+                p.leave_scope(self.span.end);
+            }
         });
     }
 }
@@ -1667,14 +1757,14 @@ impl<'a> GenExpr for UpdateExpression<'a> {
                 p.print_space_before_operator(self.operator.into());
                 p.print_str(operator);
                 p.prev_op = Some(self.operator.into());
-                p.prev_op_end = p.code().len();
+                p.prev_op_end = p.code_len();
                 self.argument.print_expr(p, Precedence::Prefix, ctx);
             } else {
                 p.print_space_before_operator(self.operator.into());
                 self.argument.print_expr(p, Precedence::Postfix, ctx);
                 p.print_str(operator);
                 p.prev_op = Some(self.operator.into());
-                p.prev_op_end = p.code().len();
+                p.prev_op_end = p.code_len();
             }
         });
     }
@@ -1692,7 +1782,7 @@ impl<'a> GenExpr for UnaryExpression<'a> {
                 p.print_space_before_operator(self.operator.into());
                 p.print_str(operator);
                 p.prev_op = Some(self.operator.into());
-                p.prev_op_end = p.code().len();
+                p.prev_op_end = p.code_len();
             }
             self.argument.print_expr(p, Precedence::Exponentiation, ctx);
         });
@@ -1908,10 +1998,10 @@ impl<'a> Gen for AssignmentTargetPropertyIdentifier<'a> {
             self.binding.print(p, ctx);
         } else {
             // `({x: a} = y);`
-            p.print_str(self.binding.name.as_str());
+            p.print_str_ascii_only(self.binding.name.as_str(), NonAsciiEscape::CodePoint);
             p.print_colon();
             p.print_soft_space();
-            p.print_str(&ident_name);
+            p.print_str_ascii_only(&ident_name, NonAsciiEscape::CodePoint);
         }
         if let Some(expr) = &self.init {
             p.print_soft_space();
@@ -2002,22 +2092,31 @@ impl<'a> GenExpr for ImportExpression<'a> {
 
 impl<'a> Gen for TemplateLiteral<'a> {
     fn gen(&self, p: &mut Codegen, _ctx: Context) {
-        p.print_char(b'`');
-        let mut expressions = self.expressions.iter();
+        print_template_literal(self, false, p);
+    }
+}
 
-        for quasi in &self.quasis {
-            p.add_source_mapping(quasi.span.start);
-            p.print_str(quasi.value.raw.as_str());
+/// Tagged templates observe the raw text through `strings.raw`, so it is never escaped.
+fn print_template_literal(lit: &TemplateLiteral<'_>, tagged: bool, p: &mut Codegen) {
+    p.print_char(b'`');
+    let mut expressions = lit.expressions.iter();
 
-            if let Some(expr) = expressions.next() {
-                p.print_str("${");
-                p.print_expression(expr);
-                p.print_char(b'}');
-            }
+    for quasi in &lit.quasis {
+        p.add_source_mapping(quasi.span.start);
+        if tagged {
+            p.print_str(quasi.value.raw.as_str());
+        } else {
+            p.print_str_ascii_only(quasi.value.raw.as_str(), NonAsciiEscape::CodeUnits);
         }
 
-        p.print_char(b'`');
+        if let Some(expr) = expressions.next() {
+            p.print_str("${");
+            p.print_expression(expr);
+            p.print_char(b'}');
+        }
     }
+
+    p.print_char(b'`');
 }
 
 impl<'a> Gen for TaggedTemplateExpression<'a> {
@@ -2027,7 +2126,7 @@ impl<'a> Gen for TaggedTemplateExpression<'a> {
         if let Some(type_parameters) = &self.type_parameters {
             type_parameters.print(p, ctx);
         }
-        self.quasi.print(p, ctx);
+        print_template_literal(&self.quasi, true, p);
     }
 }
 
@@ -2073,10 +2172,11 @@ impl<'a> GenExpr for NewExpression<'a> {
             self.callee.print_expr(p, Precedence::New, Context::FORBID_CALL);
             p.print_char(b'(');
             let has_comment = p.has_comment(self.span.end - 1)
-                || self.arguments.iter().any(|item| p.has_comment(item.span().start));
+                || self.arguments.iter().any(|item| p.has_comment(item.span().start))
+                || p.has_trailing_comments_in_list(&self.arguments, self.span.end);
             if has_comment {
                 p.indent();
-                p.print_list_with_comments(&self.arguments, ctx);
+                p.print_list_with_comments(&self.arguments, self.span.end - 1, ctx);
                 // Handle `/* comment */);`
                 if !p.print_expr_comments(self.span.end - 1) {
                     p.print_soft_newline();
@@ -2177,6 +2277,8 @@ impl<'a> Gen for Class<'a> {
             if self.r#abstract {
                 p.print_str("abstract ");
             }
+            let name = self.id.as_ref().map(|id| id.name.as_str());
+            let scope = p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Class, name);
             p.print_str("class");
             if let Some(id) = &self.id {
                 p.print_hard_space();
@@ -2198,6 +2300,9 @@ impl<'a> Gen for Class<'a> {
             }
             p.print_soft_space();
             self.body.print(p, ctx);
+            if scope {
+                p.leave_scope(self.span.end);
+            }
             p.needs_semicolon = false;
         });
     }
@@ -2244,6 +2349,7 @@ impl<'a> Gen for ClassElement<'a> {
                 p.print_semicolon_after_statement();
             }
         }
+        p.print_statement_trailing_comments(self.span().end);
     }
 }
 
@@ -2328,6 +2434,10 @@ impl<'a> Gen for JSXExpressionContainer<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
         p.print_char(b'{');
         self.expression.print(p, ctx);
+        if matches!(self.expression, JSXExpression::EmptyExpression(_)) {
+            // `{/* comment */}`
+            p.print_inline_comments(self.span.end - 1, false);
+        }
         p.print_char(b'}');
     }
 }
@@ -2340,7 +2450,7 @@ impl<'a> Gen for JSXAttributeValue<'a> {
             Self::StringLiteral(lit) => {
                 let quote = if lit.value.contains('"') { b'\'' } else { b'"' };
                 p.print_char(quote);
-                p.print_str(&lit.value);
+                p.print_str_ascii_only(&lit.value, NonAsciiEscape::HtmlEntity);
                 p.print_char(quote);
             }
             Self::ExpressionContainer(expr_container) => expr_container.print(p, ctx),
@@ -2358,6 +2468,7 @@ impl<'a> Gen for JSXSpreadAttribute<'a> {
 
 impl<'a> Gen for JSXAttributeItem<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
+        p.print_inline_comments(self.span().start, true);
         match self {
             Self::Attribute(attr) => attr.print(p, ctx),
             Self::SpreadAttribute(spread_attr) => spread_attr.print(p, ctx),
@@ -2427,7 +2538,7 @@ impl Gen for JSXClosingFragment {
 impl<'a> Gen for JSXText<'a> {
     fn gen(&self, p: &mut Codegen, _ctx: Context) {
         p.add_source_mapping(self.span.start);
-        p.print_str(self.value.as_str());
+        p.print_str_ascii_only(self.value.as_str(), NonAsciiEscape::HtmlEntity);
     }
 }
 
@@ -2463,6 +2574,7 @@ impl<'a> Gen for JSXFragment<'a> {
 impl<'a> Gen for StaticBlock<'a> {
     fn gen(&self, p: &mut Codegen, ctx: Context) {
         p.add_source_mapping(self.span.start);
+        let scope = p.enter_scope(self.scope_id.get(), self.span.start, ScopeKind::Block, None);
         p.print_str("static");
         p.print_soft_space();
         p.print_curly_braces(self.span, self.body.is_empty(), |p| {
@@ -2471,6 +2583,9 @@ impl<'a> Gen for StaticBlock<'a> {
                 stmt.print(p, ctx);
             }
         });
+        if scope {
+            p.leave_scope(self.span.end);
+        }
         p.needs_semicolon = false;
     }
 }
@@ -2635,7 +2750,7 @@ impl<'a> Gen for PrivateIdentifier<'a> {
     fn gen(&self, p: &mut Codegen, _ctx: Context) {
         p.add_source_mapping_for_name(self.span, &self.name);
         p.print_char(b'#');
-        p.print_str(self.name.as_str());
+        p.print_str_ascii_only(self.name.as_str(), NonAsciiEscape::CodePoint);
     }
 }
 
@@ -3114,7 +3229,7 @@ impl<'a> Gen for TSTemplateLiteralType<'a> {
                     p.print_str("}");
                 }
             }
-            p.print_str(item.value.raw.as_str());
+            p.print_str_ascii_only(item.value.raw.as_str(), NonAsciiEscape::CodeUnits);
         }
         p.print_str("`");
     }
@@ -3239,7 +3354,7 @@ impl<'a> Gen for TSSignature<'a> {
                             key.print(p, ctx);
                         }
                         PropertyKey::PrivateIdentifier(key) => {
-                            p.print_str(key.name.as_str());
+                            p.print_str_ascii_only(key.name.as_str(), NonAsciiEscape::CodePoint);
                         }
                         key @ match_expression!(PropertyKey) => {
                             key.to_expression().print_expr(p, Precedence::Comma, ctx);
@@ -3305,7 +3420,7 @@ impl<'a> Gen for TSSignature<'a> {
                             key.print(p, ctx);
                         }
                         PropertyKey::PrivateIdentifier(key) => {
-                            p.print_str(key.name.as_str());
+                            p.print_str_ascii_only(key.name.as_str(), NonAsciiEscape::CodePoint);
                         }
                         key @ match_expression!(PropertyKey) => {
                             key.to_expression().print_expr(p, Precedence::Comma, ctx);
@@ -3431,7 +3546,7 @@ impl<'a> Gen for TSIndexSignature<'a> {
             if index != 0 {
                 p.print_str(" | ");
             }
-            p.print_str(parameter.name.as_str());
+            p.print_str_ascii_only(parameter.name.as_str(), NonAsciiEscape::CodePoint);
             p.print_colon();
             p.print_soft_space();
             parameter.type_annotation.print(p, ctx);
@@ -8,9 +8,13 @@ mod comment;
 mod context;
 mod gen;
 mod operator;
+mod reprint;
 mod sourcemap_builder;
 
-use std::borrow::Cow;
+use std::{
+    borrow::Cow,
+    io::{self, Write},
+};
 
 use oxc_ast::{
     ast::{BindingIdentifier, BlockStatement, Expression, IdentifierReference, Program, Statement},
@@ -22,11 +26,15 @@ use oxc_syntax::{
     identifier::is_identifier_part,
     operator::{BinaryOperator, UnaryOperator, UpdateOperator},
     precedence::Precedence,
+    scope::ScopeId,
 };
 
 use crate::{
-    binary_expr_visitor::BinaryExpressionVisitor, comment::CommentsMap, operator::Operator,
-    sourcemap_builder::SourcemapBuilder,
+    binary_expr_visitor::BinaryExpressionVisitor,
+    comment::{CommentsMap, TrailingCommentsBeforeMap, TrailingCommentsMap},
+    operator::Operator,
+    reprint::Reprint,
+    sourcemap_builder::{ScopeKind, SourcemapBuilder},
 };
 pub use crate::{
     context::Context,
@@ -36,7 +44,7 @@ pub use crate::{
 /// Code generator without whitespace removal.
 pub type CodeGenerator<'a> = Codegen<'a>;
 
-#[derive(Default, Clone, Copy)]
+#[derive(Clone, Copy)]
 pub struct CodegenOptions {
     /// Use single quotes instead of double quotes.
     ///
@@ -47,22 +55,161 @@ pub struct CodegenOptions {
     ///
     /// Default is `false`.
     pub minify: bool,
+
+    /// Character used for indentation.
+    ///
+    /// Default is [IndentChar::Tab].
+    pub indent_char: IndentChar,
+
+    /// Number of [Self::indent_char] printed per indentation level.
+    ///
+    /// Default is `1`.
+    pub indent_width: usize,
+
+    /// Line ending used for line breaks in the generated code.
+    ///
+    /// Line breaks inside template literals and JSX text are printed as they are.
+    ///
+    /// Default is [LineEnding::Lf].
+    pub line_ending: LineEnding,
+
+    /// Escape all non-ASCII characters, e.g. `é` is printed as `\xE9` in strings and `\u00E9`
+    /// in identifiers, template literals, regular expressions and comments.
+    ///
+    /// Default is `false`.
+    pub ascii_only: bool,
+
+    /// Break minified output into lines of at most this many bytes where possible,
+    /// by inserting line breaks at statement boundaries, after commas and after binary operators.
+    ///
+    /// Only used when [Self::minify] is `true`. Default is `None`.
+    pub max_line_len: Option<usize>,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            single_quote: false,
+            minify: false,
+            indent_char: IndentChar::default(),
+            indent_width: 1,
+            line_ending: LineEnding::default(),
+            ascii_only: false,
+            max_line_len: None,
+        }
+    }
 }
 
-#[derive(Default, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum IndentChar {
+    #[default]
+    Tab,
+    Space,
+}
+
+impl IndentChar {
+    fn as_byte(self) -> u8 {
+        match self {
+            Self::Tab => b'\t',
+            Self::Space => b' ',
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    /// `\n`
+    #[default]
+    Lf,
+    /// `\r\n`
+    Crlf,
+}
+
+impl LineEnding {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::Crlf => "\r\n",
+        }
+    }
+}
+
+/// How [Codegen::print_str_ascii_only] escapes a non-ASCII character.
+#[derive(Clone, Copy)]
+enum NonAsciiEscape {
+    /// `\u00E9`, and `\u{1F600}` for characters outside the Basic Multilingual Plane.
+    CodePoint,
+    /// `\u00E9`, and `\uD83D\uDE00` for characters outside the Basic Multilingual Plane.
+    CodeUnits,
+    /// `&#xE9;`
+    HtmlEntity,
+}
+
+#[derive(Clone)]
 pub struct CommentOptions {
     /// Enable preserve annotate comments, like `/* #__PURE__ */` and `/* #__NO_SIDE_EFFECTS__ */`.
     pub preserve_annotate_comments: bool,
+
+    /// Print all comments at their closest position, including trailing comments and comments
+    /// inside expressions, e.g. `x = /* comment */ y; // comment`.
+    /// Otherwise only JSDoc comments before statements are printed.
+    ///
+    /// Default is `true`.
+    pub preserve_normal_comments: bool,
+
+    /// How to print legal comments, see [LegalComment].
+    ///
+    /// Default is [LegalComment::Inline].
+    pub legal_comments: LegalComment,
+}
+
+impl Default for CommentOptions {
+    fn default() -> Self {
+        Self {
+            preserve_annotate_comments: false,
+            preserve_normal_comments: true,
+            legal_comments: LegalComment::default(),
+        }
+    }
+}
+
+/// Legal comments are comments which start with `/*!` or `//!`, or contain `@license` or `@preserve`.
+///
+/// They are kept even when minifying, see
+/// <https://esbuild.github.io/api/#legal-comments>
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub enum LegalComment {
+    /// Do not preserve any legal comments.
+    None,
+    /// Preserve all legal comments where they are.
+    #[default]
+    Inline,
+    /// Move all legal comments to the end of the file.
+    Eof,
+    /// Move all legal comments to a `.LEGAL.txt` file, and link to it with a comment
+    /// pointing to this path. The comments are returned in [CodegenReturn::legal_comments].
+    Linked(String),
+    /// Move all legal comments to a `.LEGAL.txt` file without linking to it.
+    /// The comments are returned in [CodegenReturn::legal_comments].
+    External,
 }
 
+/// Size of the output buffer of [Codegen::build_into] before it is written out.
+const OUTPUT_BUFFER_SIZE: usize = 64 * 1024;
+/// Bytes at the end of the output buffer which are kept when it is written out.
+const OUTPUT_BUFFER_KEEP: usize = 1024;
+
 /// Output from [`Codegen::build`]
 pub struct CodegenReturn {
-    /// The generated source code.
+    /// The generated source code. Empty for [`Codegen::build_into`].
     pub source_text: String,
     /// The source map from the input source code to the generated source code.
     ///
     /// You must use [`Codegen::enable_source_map`] for this to be [`Some`].
     pub source_map: Option<oxc_sourcemap::SourceMap>,
+    /// Deduplicated legal comments moved out of the generated code by
+    /// [LegalComment::Linked] and [LegalComment::External].
+    pub legal_comments: Vec<String>,
 }
 
 pub struct Codegen<'a> {
@@ -74,6 +221,11 @@ pub struct Codegen<'a> {
 
     trivias: Trivias,
     comments: CommentsMap,
+    trailing_comments: TrailingCommentsMap,
+    trailing_comments_before: TrailingCommentsBeforeMap,
+
+    /// Legal comments moved to the end of the file, see [LegalComment].
+    legal_comments: Vec<&'a str>,
 
     /// Start of comment that needs to be moved to the before VariableDeclarator
     ///
@@ -91,9 +243,20 @@ pub struct Codegen<'a> {
 
     mangler: Option<Mangler>,
 
+    /// Original AST for [Codegen::enable_reprint]
+    reprint: Option<Reprint<'a>>,
+
     /// Output Code
     code: Vec<u8>,
 
+    /// Writer of [Codegen::build_into], which the start of `code` is moved to while printing.
+    writer: Option<Box<dyn Write + 'a>>,
+    /// The first error returned by `writer`.
+    write_error: Option<io::Error>,
+    /// Length of the output moved to `writer`. Positions in the output such as
+    /// [Codegen::code_len] include it, and indices into `code` exclude it.
+    flushed_len: usize,
+
     // states
     prev_op_end: usize,
     prev_reg_exp_end: usize,
@@ -113,6 +276,9 @@ pub struct Codegen<'a> {
     /// Track the current indentation level
     indent: u32,
 
+    /// Start of the last line seen by [Self::print_line_break_if_too_long]
+    line_start: usize,
+
     /// Fast path for [CodegenOptions::single_quote]
     quote: u8,
 
@@ -148,9 +314,16 @@ impl<'a> Codegen<'a> {
             source_text: None,
             trivias: Trivias::default(),
             comments: CommentsMap::default(),
+            trailing_comments: TrailingCommentsMap::default(),
+            trailing_comments_before: TrailingCommentsBeforeMap::default(),
+            legal_comments: vec![],
             start_of_annotation_comment: None,
             mangler: None,
+            reprint: None,
             code: vec![],
+            writer: None,
+            write_error: None,
+            flushed_len: 0,
             needs_semicolon: false,
             need_space_before_dot: 0,
             print_next_indent_as_space: false,
@@ -162,6 +335,7 @@ impl<'a> Codegen<'a> {
             start_of_arrow_expr: 0,
             start_of_default_export: 0,
             indent: 0,
+            line_start: 0,
             quote: b'"',
             sourcemap_builder: None,
         }
@@ -206,7 +380,7 @@ impl<'a> Codegen<'a> {
         options: CommentOptions,
     ) -> Self {
         self.comment_options = options;
-        self.build_comments(&trivias);
+        self.build_comments(source_text, &trivias);
         self.trivias = trivias;
         self.with_source_text(source_text)
     }
@@ -219,6 +393,25 @@ impl<'a> Codegen<'a> {
         self
     }
 
+    /// Reprint a program transformed from `original` while preserving its formatting.
+    ///
+    /// Statements and expressions that are unchanged since parsing `source_text` into `original`
+    /// are printed from `source_text` as they are, re-indented to their new position.
+    /// New and modified nodes are printed by the code generator, so the parents of a modified node
+    /// lose their original formatting, but not its untouched siblings and children.
+    /// Blank lines between statements are preserved.
+    ///
+    /// `original` must not be modified, e.g. use [oxc_allocator::CloneIn] to keep a copy of the
+    /// program before transforming it. Combine with [Self::enable_comment] to print the comments
+    /// outside of untouched nodes. Not used with [CodegenOptions::minify].
+    #[must_use]
+    pub fn enable_reprint(mut self, source_text: &'a str, original: &'a Program<'a>) -> Self {
+        if !self.options.minify {
+            self.reprint = Some(Reprint::new(source_text, original));
+        }
+        self.with_source_text(source_text)
+    }
+
     #[must_use]
     pub fn with_mangler(mut self, mangler: Option<Mangler>) -> Self {
         self.mangler = mangler;
@@ -228,9 +421,47 @@ impl<'a> Codegen<'a> {
     #[must_use]
     pub fn build(mut self, program: &Program<'_>) -> CodegenReturn {
         program.print(&mut self, Context::default());
+        self.print_legal_comments();
         let source_text = self.into_source_text();
+        self.into_return(source_text)
+    }
+
+    /// Print the program into `writer` instead of [CodegenReturn::source_text].
+    ///
+    /// The output is written while printing once it exceeds a buffer size, so the whole output
+    /// is never held in memory. Prints the same output and source map as [Self::build].
+    ///
+    /// # Errors
+    ///
+    /// The first error returned by `writer`.
+    pub fn build_into<W: Write + 'a>(
+        mut self,
+        program: &Program<'_>,
+        writer: W,
+    ) -> io::Result<CodegenReturn> {
+        self.code = Vec::with_capacity(OUTPUT_BUFFER_SIZE * 2);
+        self.writer = Some(Box::new(writer));
+        program.print(&mut self, Context::default());
+        self.print_legal_comments();
+        if let Some(error) = self.write_error.take() {
+            return Err(error);
+        }
+        if let Some(mut writer) = self.writer.take() {
+            writer.write_all(&self.code)?;
+            writer.flush()?;
+        }
+        Ok(self.into_return(String::new()))
+    }
+
+    fn into_return(self, source_text: String) -> CodegenReturn {
         let source_map = self.sourcemap_builder.map(SourcemapBuilder::into_sourcemap);
-        CodegenReturn { source_text, source_map }
+        let legal_comments = match self.comment_options.legal_comments {
+            LegalComment::Linked(_) | LegalComment::External => {
+                self.legal_comments.iter().map(ToString::to_string).collect()
+            }
+            _ => vec![],
+        };
+        CodegenReturn { source_text, source_map, legal_comments }
     }
 
     #[must_use]
@@ -264,8 +495,46 @@ impl<'a> Codegen<'a> {
         &self.code
     }
 
+    /// The position at the end of the output, including the output moved to the writer of [Self::build_into].
     fn code_len(&self) -> usize {
-        self.code().len()
+        self.flushed_len + self.code.len()
+    }
+
+    /// Move the output to the writer of [Self::build_into] if it exceeds [OUTPUT_BUFFER_SIZE].
+    ///
+    /// Called at the start of statements. The last [OUTPUT_BUFFER_KEEP] bytes are kept for the
+    /// checks which look back at the end of the output.
+    fn flush_output(&mut self) {
+        if self.writer.is_none() || self.code.len() < OUTPUT_BUFFER_SIZE {
+            return;
+        }
+        let mut len = self.code.len() - OUTPUT_BUFFER_KEEP;
+        if self.reprint.is_some() {
+            // Re-indenting original source text looks back at the indentation of the current line.
+            let Some(pos) = self.code[..len].iter().rposition(|&b| b == b'\n') else { return };
+            len = pos + 1;
+        }
+        // Keep `code` valid UTF-8.
+        while len > 0 && self.code[len] & 0b1100_0000 == 0b1000_0000 {
+            len -= 1;
+        }
+        // Line breaks in template literals, comments and JSX text which
+        // `print_line_break_if_too_long` hasn't seen yet.
+        if let Some(pos) = self.code[..len].iter().rposition(|&b| b == b'\n') {
+            self.line_start = self.line_start.max(self.flushed_len + pos + 1);
+        }
+        if let Some(sourcemap_builder) = self.sourcemap_builder.as_mut() {
+            sourcemap_builder.drain_output(&self.code, len);
+        }
+        if self.write_error.is_none() {
+            if let Some(writer) = self.writer.as_mut() {
+                if let Err(error) = writer.write_all(&self.code[..len]) {
+                    self.write_error = Some(error);
+                }
+            }
+        }
+        self.code.drain(..len);
+        self.flushed_len += len;
     }
 
     #[inline]
@@ -283,13 +552,32 @@ impl<'a> Codegen<'a> {
     #[inline]
     fn print_soft_newline(&mut self) {
         if !self.options.minify {
-            self.print_char(b'\n');
+            self.print_hard_newline();
         }
     }
 
     #[inline]
     fn print_hard_newline(&mut self) {
-        self.print_char(b'\n');
+        self.print_str(self.options.line_ending.as_str());
+        self.line_start = self.code_len();
+    }
+
+    /// Print a line break if the current line exceeds [CodegenOptions::max_line_len].
+    ///
+    /// Must only be called where a line break cannot change the meaning of the program.
+    fn print_line_break_if_too_long(&mut self) {
+        let Some(max_line_len) = self.options.max_line_len else { return };
+        if !self.options.minify {
+            return;
+        }
+        // Template literals, comments and JSX text may contain line breaks.
+        let start = self.line_start.saturating_sub(self.flushed_len);
+        if let Some(pos) = self.code[start..].iter().rposition(|&b| b == b'\n') {
+            self.line_start = self.flushed_len + start + pos + 1;
+        }
+        if self.code_len() - self.line_start >= max_line_len {
+            self.print_hard_newline();
+        }
     }
 
     #[inline]
@@ -300,13 +588,14 @@ impl<'a> Codegen<'a> {
     #[inline]
     fn print_comma(&mut self) {
         self.print_char(b',');
+        self.print_line_break_if_too_long();
     }
 
     #[inline]
     fn print_space_before_identifier(&mut self) {
         if self
             .peek_nth(0)
-            .is_some_and(|ch| is_identifier_part(ch) || self.prev_reg_exp_end == self.code.len())
+            .is_some_and(|ch| is_identifier_part(ch) || self.prev_reg_exp_end == self.code_len())
         {
             self.print_hard_space();
         }
@@ -342,7 +631,8 @@ impl<'a> Codegen<'a> {
             self.print_next_indent_as_space = false;
             return;
         }
-        self.code.extend(std::iter::repeat(b'\t').take(self.indent as usize));
+        let len = self.indent as usize * self.options.indent_width;
+        self.code.extend(std::iter::repeat(self.options.indent_char.as_byte()).take(len));
     }
 
     #[inline]
@@ -350,7 +640,8 @@ impl<'a> Codegen<'a> {
         if self.options.minify {
             self.needs_semicolon = true;
         } else {
-            self.print_str(";\n");
+            self.print_semicolon();
+            self.print_hard_newline();
         }
     }
 
@@ -385,14 +676,24 @@ impl<'a> Codegen<'a> {
     }
 
     fn print_curly_braces<F: FnOnce(&mut Self)>(&mut self, span: Span, single_line: bool, op: F) {
+        // `{ // comment` and `/* comment */ }`
+        let has_comments = span.end > 0
+            && self.preserve_normal_comments()
+            && (self.has_comment(span.end - 1)
+                || self.has_trailing_comments(span.start + 1, span.start + 2));
+        let single_line = single_line && !has_comments;
         self.add_source_mapping(span.start);
         self.print_char(b'{');
         if !single_line {
+            self.print_trailing_comments(span.start + 1, span.start + 2);
             self.print_soft_newline();
             self.indent();
         }
         op(self);
         if !single_line {
+            if span.end > 0 {
+                self.print_dangling_comments(span.end - 1);
+            }
             self.dedent();
             self.print_indent();
         }
@@ -436,6 +737,7 @@ impl<'a> Codegen<'a> {
     }
 
     fn print_block_statement(&mut self, stmt: &BlockStatement<'_>, ctx: Context) {
+        let scope = self.enter_scope(stmt.scope_id.get(), stmt.span.start, ScopeKind::Block, None);
         self.print_curly_braces(stmt.span, stmt.body.is_empty(), |p| {
             for stmt in &stmt.body {
                 p.print_semicolon_if_needed();
@@ -443,6 +745,9 @@ impl<'a> Codegen<'a> {
             }
         });
         self.needs_semicolon = false;
+        if scope {
+            self.leave_scope(stmt.span.end);
+        }
     }
 
     // We tried optimizing this to move the `index != 0` check out of the loop:
@@ -468,10 +773,13 @@ impl<'a> Codegen<'a> {
         }
     }
 
-    fn print_list_with_comments<T: Gen + GetSpan>(&mut self, items: &[T], ctx: Context) {
+    /// Print a list with each item on its own line, preceded by its leading comments
+    /// and followed by its trailing comments. `end` is the position of the closing bracket.
+    fn print_list_with_comments<T: Gen + GetSpan>(&mut self, items: &[T], end: u32, ctx: Context) {
         for (index, item) in items.iter().enumerate() {
             if index != 0 {
                 self.print_comma();
+                self.print_trailing_comments(items[index - 1].span().end, item.span().start);
             }
             if self.has_non_annotation_comment(item.span().start) {
                 self.print_expr_comments(item.span().start);
@@ -482,6 +790,26 @@ impl<'a> Codegen<'a> {
             }
             item.print(self, ctx);
         }
+        if let Some(last) = items.last() {
+            self.print_trailing_comments(last.span().end, end);
+        }
+    }
+
+    /// Whether there are trailing comments between the items of a list, or after the last item.
+    fn has_trailing_comments_in_list<T: GetSpan>(&self, items: &[T], end: u32) -> bool {
+        items.iter().enumerate().any(|(index, item)| {
+            let next = items.get(index + 1).map_or(end, |next| next.span().start);
+            self.has_trailing_comments(item.span().end, next)
+        })
+    }
+
+    /// Whether a list needs to be printed with [Self::print_list_with_comments].
+    fn has_comments_in_list<T: GetSpan>(&self, items: &[T], end: u32) -> bool {
+        !self.options.minify
+            && end > 0
+            && (self.has_non_annotation_comment(end - 1)
+                || items.iter().any(|item| self.has_non_annotation_comment(item.span().start))
+                || self.has_trailing_comments_in_list(items, end))
     }
 
     fn print_expressions<T: GenExpr>(&mut self, items: &[T], precedence: Precedence, ctx: Context) {
@@ -518,7 +846,7 @@ impl<'a> Codegen<'a> {
     }
 
     fn print_space_before_operator(&mut self, next: Operator) {
-        if self.prev_op_end != self.code.len() {
+        if self.prev_op_end != self.code_len() {
             return;
         }
         let Some(prev) = self.prev_op else { return };
@@ -560,6 +888,44 @@ impl<'a> Codegen<'a> {
         }
     }
 
+    /// Print `s`, escaping non-ASCII characters with [CodegenOptions::ascii_only].
+    ///
+    /// A non-ASCII character preceded by a backslash, e.g. `\é` in a regular expression or
+    /// template literal, reuses the backslash for its escape sequence.
+    fn print_str_ascii_only(&mut self, s: &str, escape: NonAsciiEscape) {
+        use std::io::Write;
+
+        if !self.options.ascii_only || s.is_ascii() {
+            self.print_str(s);
+            return;
+        }
+        let mut escaped = false;
+        for c in s.chars() {
+            if c.is_ascii() {
+                self.print_char(c as u8);
+                escaped = c == '\\' && !escaped;
+                continue;
+            }
+            let backslash = if escaped { "" } else { "\\" };
+            let code = &mut self.code;
+            match escape {
+                NonAsciiEscape::HtmlEntity => write!(code, "&#x{:X};", c as u32),
+                NonAsciiEscape::CodePoint if c as u32 > 0xFFFF => {
+                    write!(code, "{backslash}u{{{:X}}}", c as u32)
+                }
+                NonAsciiEscape::CodePoint | NonAsciiEscape::CodeUnits => {
+                    let mut units = [0; 2];
+                    let units = c.encode_utf16(&mut units);
+                    units.iter().enumerate().try_for_each(|(i, unit)| {
+                        write!(code, "{}u{unit:04X}", if i == 0 { backslash } else { "\\" })
+                    })
+                }
+            }
+            .unwrap();
+            escaped = false;
+        }
+    }
+
     #[inline]
     fn wrap_quote<F: FnMut(&mut Self, u8)>(&mut self, mut f: F) {
         self.print_char(self.quote);
@@ -578,4 +944,35 @@ impl<'a> Codegen<'a> {
             sourcemap_builder.add_source_mapping_for_name(&self.code, span, name);
         }
     }
+
+    /// Record the original scope starting at `position` in the source map, so that debuggers can
+    /// show the original names of the variables mangled by the [Mangler].
+    ///
+    /// Block scopes without bindings are skipped. Returns whether the scope was entered, in which
+    /// case [Self::leave_scope] must be called after printing the scope.
+    fn enter_scope(
+        &mut self,
+        scope_id: Option<ScopeId>,
+        position: u32,
+        kind: ScopeKind,
+        name: Option<&str>,
+    ) -> bool {
+        let (Some(mangler), Some(sourcemap_builder), Some(scope_id)) =
+            (&self.mangler, self.sourcemap_builder.as_mut(), scope_id)
+        else {
+            return false;
+        };
+        let Some(bindings) = mangler.get_scope_bindings(scope_id) else { return false };
+        if kind == ScopeKind::Block && bindings.len() == 0 {
+            return false;
+        }
+        sourcemap_builder.enter_scope(&self.code, position, kind, name, bindings);
+        true
+    }
+
+    fn leave_scope(&mut self, position: u32) {
+        if let Some(sourcemap_builder) = self.sourcemap_builder.as_mut() {
+            sourcemap_builder.leave_scope(&self.code, position);
+        }
+    }
 }
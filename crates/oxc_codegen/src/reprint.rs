@@ -0,0 +1,436 @@
+//! Reuse the original source text of untouched nodes, see [Codegen::enable_reprint].
+
+use std::hash::Hasher;
+
+use rustc_hash::{FxHashMap, FxHasher};
+
+use oxc_ast::{
+    ast::{
+        AssignmentTarget, BinaryExpression, ChainElement, Expression, ParenthesizedExpression,
+        PrivateInExpression, Program, SimpleAssignmentTarget, Statement, StringLiteral,
+        TemplateLiteral,
+    },
+    Visit,
+};
+use oxc_span::{hash::ContentHash, GetSpan, Span};
+use oxc_syntax::{
+    identifier::is_identifier_part,
+    operator::{BinaryOperator, UnaryOperator},
+    precedence::{GetPrecedence, Precedence},
+};
+
+use crate::{Codegen, Context};
+
+/// Content hashes of the statements and expressions of the original AST, indexed by their span.
+pub(crate) struct Reprint<'a> {
+    source_text: &'a str,
+    statements: FxHashMap<Span, u64>,
+    expressions: FxHashMap<Span, u64>,
+    /// Outermost template and string literals, sorted by start.
+    /// Lines inside them are part of their value and must not be re-indented.
+    literals: Vec<Span>,
+    /// Whether the last printed statement is an original one without a trailing semicolon,
+    /// e.g. `a = 1` followed by a new `(b || c).d()` needs a `;` to not be parsed as a call.
+    needs_semicolon: bool,
+}
+
+impl<'a> Reprint<'a> {
+    pub(crate) fn new(source_text: &'a str, program: &'a Program<'a>) -> Self {
+        let mut reprint = Self {
+            source_text,
+            statements: FxHashMap::default(),
+            expressions: FxHashMap::default(),
+            literals: vec![],
+            needs_semicolon: false,
+        };
+        reprint.visit_program(program);
+        reprint
+    }
+
+    fn is_in_literal(&self, position: u32) -> bool {
+        let index = self.literals.partition_point(|span| span.start < position);
+        index > 0 && position < self.literals[index - 1].end
+    }
+
+    fn add_literal(&mut self, span: Span) {
+        if self.literals.last().map_or(true, |last| last.end <= span.start) {
+            self.literals.push(span);
+        }
+    }
+}
+
+/// Hash of a node ignoring its spans, to find nodes modified after parsing.
+fn content_hash<T: ContentHash>(node: &T) -> u64 {
+    let mut hasher = FxHasher::default();
+    node.content_hash(&mut hasher);
+    hasher.finish()
+}
+
+impl<'a> Visit<'a> for Reprint<'a> {
+    fn visit_statement(&mut self, it: &Statement<'a>) {
+        self.statements.entry(it.span()).or_insert_with(|| content_hash(it));
+        oxc_ast::visit::walk::walk_statement(self, it);
+    }
+
+    fn visit_expression(&mut self, it: &Expression<'a>) {
+        self.expressions.entry(it.span()).or_insert_with(|| content_hash(it));
+        oxc_ast::visit::walk::walk_expression(self, it);
+    }
+
+    fn visit_template_literal(&mut self, it: &TemplateLiteral<'a>) {
+        self.add_literal(it.span);
+        oxc_ast::visit::walk::walk_template_literal(self, it);
+    }
+
+    fn visit_string_literal(&mut self, it: &StringLiteral<'a>) {
+        self.add_literal(it.span);
+    }
+}
+
+impl<'a> Codegen<'a> {
+    /// Print an untouched statement from the original source text.
+    ///
+    /// Returns `false` if the statement was created or modified after parsing.
+    pub(crate) fn print_original_statement(&mut self, stmt: &Statement<'_>) -> bool {
+        let Some(reprint) = self.reprint.as_mut() else { return false };
+        let needs_semicolon = std::mem::take(&mut reprint.needs_semicolon);
+        let span = stmt.span();
+        let original = reprint.statements.get(&span).copied();
+        if span.is_empty() || original != Some(content_hash(stmt)) {
+            if let Statement::ExpressionStatement(stmt) = stmt {
+                if needs_semicolon && starts_with_asi_hazard(&stmt.expression) {
+                    self.print_asi_semicolon();
+                }
+            }
+            return false;
+        }
+        self.print_indent();
+        self.add_source_mapping(span.start);
+        // Keep the original spacing before trailing comments, e.g. `x;   // comment`.
+        let end = self
+            .trailing_comments
+            .remove(&span.end)
+            .and_then(|comments| comments.last().map(|comment| comment.real_span().end))
+            .unwrap_or(span.end);
+        self.print_original_source(Span::new(span.start, end));
+        if let Some(reprint) = self.reprint.as_mut() {
+            let text = span.source_text(reprint.source_text);
+            reprint.needs_semicolon = !text.ends_with([';', '}']);
+        }
+        self.print_soft_newline();
+        true
+    }
+
+    /// Print an untouched expression from the original source text.
+    ///
+    /// Returns `false` if the expression was created or modified after parsing, or if it would
+    /// need to be wrapped in parentheses at this position.
+    pub(crate) fn print_original_expression(
+        &mut self,
+        expr: &Expression<'_>,
+        precedence: Precedence,
+        ctx: Context,
+    ) -> bool {
+        let Some(reprint) = self.reprint.as_ref() else { return false };
+        let span = expr.span();
+        if span.is_empty() || reprint.expressions.get(&span) != Some(&content_hash(expr)) {
+            return false;
+        }
+        let text = span.source_text(reprint.source_text);
+        let expr_precedence = match expr {
+            Expression::SequenceExpression(_)
+            | Expression::YieldExpression(_)
+            | Expression::ConditionalExpression(_)
+            | Expression::AssignmentExpression(_)
+            | Expression::LogicalExpression(_)
+            | Expression::BinaryExpression(_)
+            | Expression::UnaryExpression(_)
+            | Expression::UpdateExpression(_)
+            | Expression::AwaitExpression(_)
+            | Expression::NewExpression(_)
+            | Expression::CallExpression(_) => expr.precedence(),
+            Expression::ArrowFunctionExpression(_) => Precedence::Assign,
+            Expression::PrivateInExpression(_)
+            | Expression::TSAsExpression(_)
+            | Expression::TSSatisfiesExpression(_) => Precedence::Compare,
+            Expression::TSTypeAssertion(_) => Precedence::Prefix,
+            Expression::ChainExpression(_) => Precedence::Postfix,
+            Expression::ImportExpression(_) => Precedence::Call,
+            _ if expr.is_member_expression() => expr.precedence(),
+            _ => Precedence::Member,
+        };
+        let len = self.code_len();
+        let at_start = self.start_of_stmt == len
+            || self.start_of_arrow_expr == len
+            || self.start_of_default_export == len;
+        if expr_precedence <= precedence
+            || (ctx.forbid_in() && contains_in_operator(expr))
+            || (ctx.forbid_call() && contains_call(expr))
+            || (at_start && starts_with_statement_token(expr))
+        {
+            return false;
+        }
+        self.print_space_before_original(text);
+        self.add_source_mapping(span.start);
+        self.print_original_source(span);
+        true
+    }
+
+    /// Print the blank lines before a statement in the original source text.
+    pub(crate) fn print_original_blank_line(&mut self, start: u32) {
+        let Some(reprint) = self.reprint.as_ref() else { return };
+        if start == 0 || self.options.minify {
+            return;
+        }
+        let start = self
+            .comments
+            .get(&start)
+            .and_then(|comments| comments.iter().map(|comment| comment.real_span().start).min())
+            .unwrap_or(start);
+        let before = &reprint.source_text[..start as usize];
+        let whitespace = &before[before.trim_end().len()..];
+        let blank_lines = whitespace.matches('\n').count().saturating_sub(1);
+        let code = self.code();
+        if blank_lines > 0
+            && code.ends_with(b"\n")
+            && !code.ends_with(b"\n\n")
+            && !code.ends_with(b"{\n")
+            && !code.ends_with(b"\n\r\n")
+            && !code.ends_with(b"{\r\n")
+        {
+            for _ in 0..blank_lines {
+                self.print_hard_newline();
+            }
+        }
+    }
+
+    /// Print the original source text of `span`, re-indenting its lines from the indentation of
+    /// its first line in the original source text to the indentation of the current line.
+    pub(crate) fn print_original_source(&mut self, span: Span) {
+        let Some(reprint) = self.reprint.as_ref() else { return };
+        let source_text = reprint.source_text;
+        let text = span.source_text(source_text);
+        let original_indent = line_indentation(source_text.as_bytes(), span.start as usize);
+        // SAFETY: the indentation is ASCII whitespace.
+        let original_indent = unsafe { std::str::from_utf8_unchecked(original_indent) };
+        let indent = line_indentation(self.code(), self.code().len()).to_vec();
+        if original_indent.as_bytes() == indent || !text.contains('\n') {
+            self.print_str(text);
+            return;
+        }
+        let mut position = span.start;
+        for (i, line) in text.split_inclusive('\n').enumerate() {
+            let in_literal = self.reprint.as_ref().is_some_and(|r| r.is_in_literal(position));
+            match line.strip_prefix(original_indent) {
+                Some(rest) if i > 0 && !in_literal => {
+                    self.code.extend(&indent);
+                    self.print_str(rest);
+                }
+                _ => self.print_str(line),
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            {
+                position += line.len() as u32;
+            }
+        }
+    }
+
+    /// Print a space if the original source text would otherwise be joined with the previous
+    /// token, e.g. `typeof` followed by `x`, or `-` followed by `-x`.
+    fn print_space_before_original(&mut self, text: &str) {
+        let Some(first) = text.chars().next() else { return };
+        let Some(last) = self.peek_nth(0) else { return };
+        if (is_identifier_part(first)
+            && (is_identifier_part(last) || self.prev_reg_exp_end == self.code_len()))
+            || (first == last && matches!(first, '+' | '-' | '/'))
+            || (last == '<' && text.starts_with("!--"))
+        {
+            self.print_hard_space();
+        }
+    }
+
+    /// Terminate the previous original statement, which has no trailing semicolon.
+    fn print_asi_semicolon(&mut self) {
+        let line_ending = self.options.line_ending.as_str();
+        let can_truncate = self.code.ends_with(line_ending.as_bytes())
+            && self
+                .sourcemap_builder
+                .as_ref()
+                .map_or(true, |builder| builder.can_truncate(self.code.len() - line_ending.len()));
+        if can_truncate {
+            self.code.truncate(self.code.len() - line_ending.len());
+            self.print_semicolon();
+            self.print_hard_newline();
+        } else {
+            // An empty statement on its own line.
+            self.print_indent();
+            self.print_semicolon();
+            self.print_hard_newline();
+        }
+    }
+}
+
+/// Whether an expression statement starts with a token that continues the previous statement
+/// when it has no trailing semicolon, i.e. `(`, `[`, `` ` ``, `+`, `-`, `/` or `<`.
+fn starts_with_asi_hazard(expr: &Expression<'_>) -> bool {
+    // Parentheses are printed where needed, which is handled by `callee_starts_with_asi_hazard`.
+    match expr.without_parentheses() {
+        Expression::ArrayExpression(_)
+        | Expression::TemplateLiteral(_)
+        | Expression::RegExpLiteral(_)
+        | Expression::TSTypeAssertion(_)
+        // Wrapped in parentheses at the start of a statement.
+        | Expression::ObjectExpression(_)
+        | Expression::FunctionExpression(_)
+        | Expression::ClassExpression(_) => true,
+        Expression::ArrowFunctionExpression(arrow) => !arrow.r#async,
+        Expression::UnaryExpression(e) => {
+            matches!(e.operator, UnaryOperator::UnaryPlus | UnaryOperator::UnaryNegation)
+        }
+        Expression::UpdateExpression(e) => e.prefix || target_starts_with_asi_hazard(&e.argument),
+        Expression::BinaryExpression(e) => starts_with_asi_hazard(&e.left),
+        Expression::LogicalExpression(e) => starts_with_asi_hazard(&e.left),
+        Expression::ConditionalExpression(e) => starts_with_asi_hazard(&e.test),
+        Expression::SequenceExpression(e) => e.expressions.first().is_some_and(starts_with_asi_hazard),
+        Expression::AssignmentExpression(e) => match &e.left {
+            AssignmentTarget::ArrayAssignmentTarget(_)
+            | AssignmentTarget::ObjectAssignmentTarget(_) => true,
+            target => target.as_simple_assignment_target().is_some_and(target_starts_with_asi_hazard),
+        },
+        Expression::CallExpression(e) => callee_starts_with_asi_hazard(&e.callee),
+        Expression::TaggedTemplateExpression(e) => callee_starts_with_asi_hazard(&e.tag),
+        Expression::ChainExpression(e) => match &e.expression {
+            ChainElement::CallExpression(call) => callee_starts_with_asi_hazard(&call.callee),
+            element => element
+                .as_member_expression()
+                .is_some_and(|member| callee_starts_with_asi_hazard(member.object())),
+        },
+        Expression::TSAsExpression(e) => starts_with_asi_hazard(&e.expression),
+        Expression::TSSatisfiesExpression(e) => starts_with_asi_hazard(&e.expression),
+        Expression::TSNonNullExpression(e) => starts_with_asi_hazard(&e.expression),
+        Expression::TSInstantiationExpression(e) => starts_with_asi_hazard(&e.expression),
+        expr => expr
+            .as_member_expression()
+            .is_some_and(|member| callee_starts_with_asi_hazard(member.object())),
+    }
+}
+
+/// Like [starts_with_asi_hazard], for the object of a member expression or the callee of a call,
+/// where operator expressions are wrapped in parentheses.
+fn callee_starts_with_asi_hazard(expr: &Expression<'_>) -> bool {
+    starts_with_asi_hazard(expr)
+        || matches!(
+            expr.without_parentheses(),
+            Expression::SequenceExpression(_)
+                | Expression::YieldExpression(_)
+                | Expression::AwaitExpression(_)
+                | Expression::ConditionalExpression(_)
+                | Expression::AssignmentExpression(_)
+                | Expression::LogicalExpression(_)
+                | Expression::BinaryExpression(_)
+                | Expression::PrivateInExpression(_)
+                | Expression::UnaryExpression(_)
+                | Expression::UpdateExpression(_)
+                | Expression::TSAsExpression(_)
+                | Expression::TSSatisfiesExpression(_)
+        )
+}
+
+fn target_starts_with_asi_hazard(target: &SimpleAssignmentTarget<'_>) -> bool {
+    match target {
+        SimpleAssignmentTarget::AssignmentTargetIdentifier(_) => false,
+        target => target
+            .as_member_expression()
+            .map_or(true, |member| callee_starts_with_asi_hazard(member.object())),
+    }
+}
+
+/// Whether an expression contains an `in` operator outside of parentheses, which is not allowed in
+/// the initializer of a `for` statement.
+fn contains_in_operator(expr: &Expression<'_>) -> bool {
+    #[derive(Default)]
+    struct InOperatorFinder {
+        found: bool,
+    }
+
+    impl<'a> Visit<'a> for InOperatorFinder {
+        fn visit_binary_expression(&mut self, it: &BinaryExpression<'a>) {
+            self.found |= it.operator == BinaryOperator::In;
+            oxc_ast::visit::walk::walk_binary_expression(self, it);
+        }
+
+        fn visit_private_in_expression(&mut self, _it: &PrivateInExpression<'a>) {
+            self.found = true;
+        }
+
+        fn visit_parenthesized_expression(&mut self, _it: &ParenthesizedExpression<'a>) {}
+    }
+
+    let mut finder = InOperatorFinder::default();
+    finder.visit_expression(expr);
+    finder.found
+}
+
+/// Whether the callee of a `new` expression would end at a call in this expression,
+/// e.g. `a().b` in `new (a().b)()`.
+fn contains_call(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::CallExpression(_) | Expression::ChainExpression(_) => true,
+        Expression::TaggedTemplateExpression(e) => contains_call(&e.tag),
+        Expression::TSNonNullExpression(e) => contains_call(&e.expression),
+        Expression::TSInstantiationExpression(e) => contains_call(&e.expression),
+        expr => expr.as_member_expression().is_some_and(|member| contains_call(member.object())),
+    }
+}
+
+/// Whether an expression starts with a token that is parsed as a declaration or a block at the
+/// start of a statement, an arrow function body or a default export, e.g. `{`, `function` or `let [`.
+fn starts_with_statement_token(expr: &Expression<'_>) -> bool {
+    match expr {
+        Expression::ObjectExpression(_)
+        | Expression::FunctionExpression(_)
+        | Expression::ClassExpression(_) => true,
+        Expression::Identifier(ident) => ident.name == "let",
+        Expression::UpdateExpression(e) => {
+            !e.prefix
+                && e.argument
+                    .as_member_expression()
+                    .is_some_and(|member| starts_with_statement_token(member.object()))
+        }
+        Expression::BinaryExpression(e) => starts_with_statement_token(&e.left),
+        Expression::LogicalExpression(e) => starts_with_statement_token(&e.left),
+        Expression::ConditionalExpression(e) => starts_with_statement_token(&e.test),
+        Expression::SequenceExpression(e) => {
+            e.expressions.first().is_some_and(starts_with_statement_token)
+        }
+        Expression::AssignmentExpression(e) => match &e.left {
+            AssignmentTarget::ObjectAssignmentTarget(_) => true,
+            AssignmentTarget::AssignmentTargetIdentifier(ident) => ident.name == "let",
+            target => target
+                .as_member_expression()
+                .is_some_and(|member| starts_with_statement_token(member.object())),
+        },
+        Expression::CallExpression(e) => starts_with_statement_token(&e.callee),
+        Expression::TaggedTemplateExpression(e) => starts_with_statement_token(&e.tag),
+        Expression::ChainExpression(e) => match &e.expression {
+            ChainElement::CallExpression(call) => starts_with_statement_token(&call.callee),
+            element => element
+                .as_member_expression()
+                .is_some_and(|member| starts_with_statement_token(member.object())),
+        },
+        Expression::TSAsExpression(e) => starts_with_statement_token(&e.expression),
+        Expression::TSSatisfiesExpression(e) => starts_with_statement_token(&e.expression),
+        Expression::TSNonNullExpression(e) => starts_with_statement_token(&e.expression),
+        Expression::TSInstantiationExpression(e) => starts_with_statement_token(&e.expression),
+        expr => expr
+            .as_member_expression()
+            .is_some_and(|member| starts_with_statement_token(member.object())),
+    }
+}
+
+/// Leading whitespace of the line containing `position`.
+fn line_indentation(text: &[u8], position: usize) -> &[u8] {
+    let line_start = text[..position].iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+    let len = text[line_start..position].iter().take_while(|&&b| b == b' ' || b == b'\t').count();
+    &text[line_start..line_start + len]
+}
@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use nonmax::NonMaxU32;
 use oxc_index::{Idx, IndexVec};
+use oxc_sourcemap::{GeneratedRange, OriginalScope};
 use oxc_span::Span;
 use oxc_syntax::identifier::{LS, PS};
 
@@ -11,6 +12,27 @@ const LS_OR_PS_SECOND: u8 = 0x80;
 const LS_THIRD: u8 = 0xA8;
 const PS_THIRD: u8 = 0xA9;
 
+/// The kind of an original scope, see [SourcemapBuilder::enter_scope].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScopeKind {
+    Global,
+    Function,
+    Class,
+    /// Block statements, `for` statements, `switch` statements and class static blocks.
+    Block,
+}
+
+impl ScopeKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Global => "global",
+            Self::Function => "function",
+            Self::Class => "class",
+            Self::Block => "block",
+        }
+    }
+}
+
 /// Index into vec of `ColumnOffsets`
 #[derive(Debug, Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub struct ColumnOffsetsId(NonMaxU32);
@@ -71,6 +93,11 @@ pub struct SourcemapBuilder {
     sourcemap_builder: oxc_sourcemap::SourceMapBuilder,
     generated_line: u32,
     generated_column: u32,
+    /// The scopes being printed, with the generated range of each.
+    scope_stack: Vec<(OriginalScope, GeneratedRange)>,
+    /// The number of original scopes entered, for the index of a scope in pre-order.
+    scope_count: u32,
+    root_scope: Option<(OriginalScope, GeneratedRange)>,
 }
 
 impl Default for SourcemapBuilder {
@@ -84,6 +111,9 @@ impl Default for SourcemapBuilder {
             sourcemap_builder: oxc_sourcemap::SourceMapBuilder::default(),
             generated_line: 0,
             generated_column: 0,
+            scope_stack: vec![],
+            scope_count: 0,
+            root_scope: None,
         }
     }
 }
@@ -95,10 +125,72 @@ impl SourcemapBuilder {
         self.original_source = source.into();
     }
 
-    pub fn into_sourcemap(self) -> oxc_sourcemap::SourceMap {
+    pub fn into_sourcemap(mut self) -> oxc_sourcemap::SourceMap {
+        if let Some((original_scope, generated_range)) = self.root_scope {
+            self.sourcemap_builder.set_original_scopes(vec![Some(original_scope)]);
+            self.sourcemap_builder.set_generated_ranges(vec![generated_range]);
+        }
         self.sourcemap_builder.into_sourcemap()
     }
 
+    /// Start an original scope at `position` and its generated range at the end of `output`.
+    ///
+    /// `bindings` are the original and generated names of the variables declared in the scope.
+    pub fn enter_scope<'b, I>(
+        &mut self,
+        output: &[u8],
+        position: u32,
+        kind: ScopeKind,
+        name: Option<&str>,
+        bindings: I,
+    ) where
+        I: Iterator<Item = (&'b str, &'b str)>,
+    {
+        let is_stack_frame = kind == ScopeKind::Function;
+        let (variables, bindings) = bindings
+            .map(|(original, generated)| {
+                let variable = self.sourcemap_builder.add_name(original);
+                let binding = self.sourcemap_builder.add_name(generated);
+                (variable, Some(binding))
+            })
+            .unzip();
+        let original_scope = OriginalScope {
+            start: self.search_original_line_and_column(position),
+            kind: Some(self.sourcemap_builder.add_name(kind.as_str())),
+            name: name.map(|name| self.sourcemap_builder.add_name(name)),
+            is_stack_frame,
+            variables,
+            ..OriginalScope::default()
+        };
+        self.update_generated_line_and_column(output);
+        let generated_range = GeneratedRange {
+            start: (self.generated_line, self.generated_column),
+            definition: Some((self.source_id, self.scope_count)),
+            is_stack_frame,
+            bindings,
+            ..GeneratedRange::default()
+        };
+        self.scope_count += 1;
+        self.scope_stack.push((original_scope, generated_range));
+    }
+
+    /// End the last entered original scope at `position` and its generated range at the end of `output`.
+    pub fn leave_scope(&mut self, output: &[u8], position: u32) {
+        let Some((mut original_scope, mut generated_range)) = self.scope_stack.pop() else {
+            return;
+        };
+        original_scope.end = self.search_original_line_and_column(position);
+        self.update_generated_line_and_column(output);
+        generated_range.end = (self.generated_line, self.generated_column);
+        match self.scope_stack.last_mut() {
+            Some((parent_scope, parent_range)) => {
+                parent_scope.children.push(original_scope);
+                parent_range.children.push(generated_range);
+            }
+            None => self.root_scope = Some((original_scope, generated_range)),
+        }
+    }
+
     pub fn add_source_mapping_for_name(&mut self, output: &[u8], span: Span, name: &str) {
         debug_assert!(
             (span.end as usize) <= self.original_source.len(),
@@ -115,6 +207,20 @@ impl SourcemapBuilder {
         self.add_source_mapping(output, span.start, token_name);
     }
 
+    /// Remove the first `len` bytes of `output` from the output tracked by the builder,
+    /// after they were written out.
+    pub fn drain_output(&mut self, output: &[u8], len: usize) {
+        if self.last_generated_update < len {
+            self.update_generated_line_and_column(&output[..len]);
+        }
+        self.last_generated_update -= len;
+    }
+
+    /// Whether the output can be truncated to `len` without invalidating the generated positions.
+    pub fn can_truncate(&self, len: usize) -> bool {
+        self.last_generated_update <= len
+    }
+
     pub fn add_source_mapping(&mut self, output: &[u8], position: u32, name: Option<Arc<str>>) {
         if matches!(self.last_position, Some(last_position) if last_position == position) {
             return;
@@ -0,0 +1,75 @@
+use std::fmt::Write;
+
+use oxc_allocator::{Allocator, CloneIn};
+use oxc_codegen::{CodeGenerator, CodegenOptions, CodegenReturn, CommentOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn source_text() -> String {
+    // Large enough for the output to be written out several times while printing.
+    let mut source_text = String::new();
+    for i in 0..2000 {
+        writeln!(
+            source_text,
+            "// comment {i}\nfunction f{i}(a, b) {{\n  const s = `line\n  é {i}`;\n  return a + b + s.length;\n}}"
+        )
+        .unwrap();
+    }
+    source_text
+}
+
+fn test(options: CodegenOptions, reprint: bool) {
+    let source_text = source_text();
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, &source_text, SourceType::mjs()).parse();
+    let original = ret.program.clone_in(&allocator);
+    let codegen = || {
+        let codegen = CodeGenerator::new()
+            .with_options(options)
+            .enable_comment(&source_text, ret.trivias.clone(), CommentOptions::default())
+            .enable_source_map("test.js", &source_text);
+        if reprint {
+            codegen.enable_reprint(&source_text, &original)
+        } else {
+            codegen
+        }
+    };
+
+    let expected = codegen().build(&ret.program);
+    let mut output = vec![];
+    let CodegenReturn { source_text: empty, source_map, .. } =
+        codegen().build_into(&ret.program, &mut output).unwrap();
+    assert!(empty.is_empty());
+    assert_eq!(String::from_utf8(output).unwrap(), expected.source_text);
+    assert_eq!(source_map.unwrap().to_json_string(), expected.source_map.unwrap().to_json_string());
+}
+
+#[test]
+fn build_into() {
+    test(CodegenOptions::default(), false);
+    test(CodegenOptions::default(), true);
+    test(CodegenOptions { minify: true, ..CodegenOptions::default() }, false);
+    test(
+        CodegenOptions { minify: true, max_line_len: Some(80), ..CodegenOptions::default() },
+        false,
+    );
+}
+
+#[test]
+fn build_into_error() {
+    struct FailingWriter;
+    impl std::io::Write for FailingWriter {
+        fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("failed"))
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let source_text = source_text();
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, &source_text, SourceType::mjs()).parse();
+    let result = CodeGenerator::new().build_into(&ret.program, FailingWriter);
+    assert_eq!(result.err().unwrap().to_string(), "failed");
+}
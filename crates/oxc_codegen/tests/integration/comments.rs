@@ -0,0 +1,84 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::{CodeGenerator, CommentOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::{snapshot, tester::test};
+
+#[test]
+fn comments() {
+    let cases = vec![
+        r"
+function f() {
+  /* leading */ a(); // trailing
+  // line
+  b();
+  /* dangling */
+}
+function g() { // after brace
+}
+// end of file
+",
+        r"
+const o = {
+  // leading property
+  a: 1, // trailing property
+  b: 2,
+  /* dangling */
+};
+const a = [
+  1, // one
+  /* two */ 2,
+];
+foo(a, // first
+  b);
+",
+        r"
+x = /* inline */ y;
+x = // line
+  y;
+if (a) // after test
+  b();
+",
+        r"
+class A {
+  // leading member
+  a = 1; // trailing member
+  /* dangling */
+}
+",
+        r"
+function f() {
+  return /* inline */ a;
+}
+function g() {
+  return (/* multi
+  line */ a);
+}
+() => /* object */ ({});
+",
+    ];
+
+    snapshot("comments", &cases);
+}
+
+#[test]
+fn jsx() {
+    test(
+        "const el = <div /* attribute */ a='1'>{/* child */}{ // line\n}</div>;",
+        "const el = <div /* attribute */ a=\"1\">{/* child */}{// line\n}</div>;\n",
+    );
+}
+
+#[test]
+fn without_normal_comments() {
+    let source_text = "try {} catch (e) { // ignore\n}\nfunction f() { /* empty */ }\n";
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::mjs()).parse();
+    let options = CommentOptions { preserve_normal_comments: false, ..CommentOptions::default() };
+    let result = CodeGenerator::new()
+        .enable_comment(source_text, ret.trivias, options)
+        .build(&ret.program)
+        .source_text;
+    assert_eq!(result, "try {} catch (e) {}\nfunction f() {}\n");
+}
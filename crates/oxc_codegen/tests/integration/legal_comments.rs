@@ -0,0 +1,103 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::{CodeGenerator, CodegenOptions, CodegenReturn, CommentOptions, LegalComment};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn codegen(source_text: &str, legal_comments: LegalComment, minify: bool) -> CodegenReturn {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::mjs()).parse();
+    CodeGenerator::new()
+        .with_options(CodegenOptions { minify, ..CodegenOptions::default() })
+        .enable_comment(
+            source_text,
+            ret.trivias,
+            CommentOptions { legal_comments, ..CommentOptions::default() },
+        )
+        .build(&ret.program)
+}
+
+fn test(source_text: &str, expected: &str, legal_comments: LegalComment) {
+    let ret = codegen(source_text, legal_comments, false);
+    assert_eq!(ret.source_text, expected, "\nfor source: {source_text:?}");
+}
+
+fn test_minify(source_text: &str, expected: &str, legal_comments: LegalComment) {
+    let ret = codegen(source_text, legal_comments, true);
+    assert_eq!(ret.source_text, expected, "\nfor minify source: {source_text:?}");
+}
+
+const SOURCE: &str = "/*! Copyright 1 */
+'use strict';
+foo;
+/**
+ * @license Apache-2.0
+ */
+bar;
+//! Copyright 1
+baz;
+/* @preserve */
+/*! Copyright 1 */
+qux;
+// not a legal comment
+quux;
+";
+
+#[test]
+fn none() {
+    test(
+        SOURCE,
+        "\"use strict\";\nfoo;\nbar;\nbaz;\nqux;\n// not a legal comment\nquux;\n",
+        LegalComment::None,
+    );
+    test_minify(SOURCE, "\"use strict\";foo;bar;baz;qux;quux;", LegalComment::None);
+}
+
+#[test]
+fn inline() {
+    test(
+        SOURCE,
+        "/*! Copyright 1 */\n\"use strict\";\nfoo;\n/**\n* @license Apache-2.0\n*/\nbar;\n//! Copyright 1\nbaz;\n/* @preserve */\n/*! Copyright 1 */\nqux;\n// not a legal comment\nquux;\n",
+        LegalComment::Inline,
+    );
+    test_minify(
+        SOURCE,
+        "/*! Copyright 1 */\n\"use strict\";foo;\n/**\n* @license Apache-2.0\n*/\nbar;\n//! Copyright 1\nbaz;\n/* @preserve */\n/*! Copyright 1 */\nqux;quux;",
+        LegalComment::Inline,
+    );
+}
+
+#[test]
+fn eof() {
+    test_minify(
+        SOURCE,
+        "\"use strict\";foo;bar;baz;qux;quux;\n/*! Copyright 1 */\n/**\n * @license Apache-2.0\n */\n//! Copyright 1\n/* @preserve */\n",
+        LegalComment::Eof,
+    );
+}
+
+#[test]
+fn linked() {
+    let ret = codegen(SOURCE, LegalComment::Linked("out.js.LEGAL.txt".to_string()), true);
+    assert_eq!(
+        ret.source_text,
+        "\"use strict\";foo;bar;baz;qux;quux;\n/*! For license information please see out.js.LEGAL.txt */\n"
+    );
+    assert_eq!(
+        ret.legal_comments,
+        [
+            "/*! Copyright 1 */",
+            "/**\n * @license Apache-2.0\n */",
+            "//! Copyright 1",
+            "/* @preserve */"
+        ]
+    );
+}
+
+#[test]
+fn external() {
+    let ret = codegen(SOURCE, LegalComment::External, true);
+    assert_eq!(ret.source_text, "\"use strict\";foo;bar;baz;qux;quux;");
+    assert_eq!(ret.legal_comments.len(), 4);
+    let ret = codegen("foo;", LegalComment::External, true);
+    assert!(ret.legal_comments.is_empty());
+}
@@ -1,7 +1,12 @@
 #![allow(clippy::missing_panics_doc)]
+pub mod build_into;
+pub mod comments;
 pub mod esbuild;
 pub mod jsdoc;
+pub mod legal_comments;
+pub mod options;
 pub mod pure_comments;
+pub mod reprint;
 pub mod tester;
 pub mod ts;
 pub mod unit;
@@ -20,7 +25,7 @@ pub fn codegen(source_text: &str) -> String {
         .enable_comment(
             source_text,
             ret.trivias,
-            CommentOptions { preserve_annotate_comments: true },
+            CommentOptions { preserve_annotate_comments: true, ..CommentOptions::default() },
         )
         .build(&ret.program)
         .source_text
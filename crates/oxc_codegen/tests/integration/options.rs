@@ -0,0 +1,207 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::{CodeGenerator, CodegenOptions, CommentOptions, IndentChar, LineEnding};
+use oxc_mangler::{MangleOptions, Mangler};
+use oxc_parser::Parser;
+use oxc_sourcemap::OriginalScope;
+use oxc_span::SourceType;
+
+use crate::tester::test_options;
+
+#[test]
+fn indent() {
+    let source = "function foo() {\n\tif (x) {\n\t\ty();\n\t}\n}\n";
+    let options =
+        CodegenOptions { indent_char: IndentChar::Space, indent_width: 2, ..Default::default() };
+    test_options(source, "function foo() {\n  if (x) {\n    y();\n  }\n}\n", options);
+    let options = CodegenOptions { indent_width: 2, ..Default::default() };
+    test_options(source, "function foo() {\n\t\tif (x) {\n\t\t\t\ty();\n\t\t}\n}\n", options);
+
+    let source = "class A {\n  // comment\n  foo() {}\n}\n";
+    let options =
+        CodegenOptions { indent_char: IndentChar::Space, indent_width: 4, ..Default::default() };
+    test_options(source, "class A {\n    // comment\n    foo() {}\n}\n", options);
+}
+
+#[test]
+fn line_ending() {
+    let options = CodegenOptions { line_ending: LineEnding::Crlf, ..Default::default() };
+    test_options(
+        "function foo() {\n  x; // comment\n  /**\n   * doc\n   */\n  y;\n}\n",
+        "function foo() {\r\n\tx; // comment\r\n\t/**\r\n\t* doc\r\n\t*/\r\n\ty;\r\n}\r\n",
+        options,
+    );
+    // CRLF in the source does not duplicate lines of block comments.
+    test_options("/*\r\n * a\r\n */\r\nx;\r\n", "/*\r\n* a\r\n*/\r\nx;\r\n", options);
+    // Line breaks in template literals are part of the value.
+    test_options("`a\nb`;", "`a\nb`;\r\n", options);
+}
+
+#[test]
+fn ascii_only() {
+    let options = CodegenOptions { ascii_only: true, ..Default::default() };
+    test_options("x = 'café ✓ 😀'", "x = \"caf\\xE9 \\u2713 \\uD83D\\uDE00\";\n", options);
+    test_options("let café = ಠ_ಠ;", "let caf\\u00E9 = \\u0CA0_\\u0CA0;\n", options);
+    test_options("let 𐊧;", "let \\u{102A7};\n", options);
+    test_options("class A { #é }", "class A {\n\t#\\u00E9;\n}\n", options);
+    test_options("`é\\é${x}😀`", "`\\u00E9\\u00E9${x}\\uD83D\\uDE00`;\n", options);
+    // The raw text of tagged templates is observable through `String.raw`.
+    test_options("String.raw`é\\é`", "String.raw`é\\é`;\n", options);
+    test_options("/é\\é😀/; /😀/u", "/\\u00E9\\u00E9\\uD83D\\uDE00/;\n/\\u{1F600}/u;\n", options);
+    test_options("// é\nx;", "// \\u00E9\nx;\n", options);
+    test_options(
+        "<div title=\"é\">é {x}</div>",
+        "<div title=\"&#xE9;\">&#xE9; {x}</div>;\n",
+        options,
+    );
+}
+
+#[test]
+fn max_line_len() {
+    let options = CodegenOptions { minify: true, max_line_len: Some(20), ..Default::default() };
+    test_options(
+        "const alpha = 1, beta = 2; foo(alpha, beta, gamma, delta); return_ + veryLongIdentifier + another;",
+        "const alpha=1,beta=2;\nfoo(alpha,beta,gamma,\ndelta);return_+veryLongIdentifier+\nanother;",
+        options,
+    );
+    // Never break after `return`.
+    test_options(
+        "function fooBarBaz() { return aaaaaaaaaaaaaaaaaaaaaaaaaa }",
+        "function fooBarBaz(){\nreturn aaaaaaaaaaaaaaaaaaaaaaaaaa}",
+        options,
+    );
+    // Ignored without `minify`.
+    let options = CodegenOptions { max_line_len: Some(1), ..Default::default() };
+    test_options("foo(a, b);", "foo(a, b);\n", options);
+}
+
+/// Every mapping must point at the same identifier in the original and the generated code.
+#[test]
+fn source_map() {
+    let source_text = "const café = 1;\r\nfunction foo(a, b) {\r\n  // comment\r\n  return a + b + café;\r\n}\r\nfoo(`x\r\ny`, /é/);\r\n";
+    let options = [
+        CodegenOptions { line_ending: LineEnding::Crlf, ..Default::default() },
+        CodegenOptions { ascii_only: true, line_ending: LineEnding::Crlf, ..Default::default() },
+        CodegenOptions { indent_char: IndentChar::Space, indent_width: 4, ..Default::default() },
+        CodegenOptions { minify: true, max_line_len: Some(10), ..Default::default() },
+        CodegenOptions {
+            minify: true,
+            max_line_len: Some(10),
+            line_ending: LineEnding::Crlf,
+            ..Default::default()
+        },
+    ];
+    for options in options {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, source_text, SourceType::mjs()).parse();
+        let result = CodeGenerator::new()
+            .with_options(options)
+            .enable_comment(source_text, ret.trivias, CommentOptions::default())
+            .enable_source_map("test.js", source_text)
+            .build(&ret.program);
+        let output = result.source_text;
+        let source_map = result.source_map.unwrap();
+        let mut checked = 0;
+        for token in source_map.get_tokens() {
+            let original = text_at(source_text, token.get_src_line(), token.get_src_col());
+            let generated = text_at(&output, token.get_dst_line(), token.get_dst_col());
+            let ident = |s: &str| s.chars().take_while(char::is_ascii_alphanumeric).collect();
+            let original: String = ident(&original);
+            if original.is_empty() {
+                continue;
+            }
+            // Statements are mapped before their indentation.
+            let generated: String = ident(generated.trim_start());
+            if options.ascii_only && original == "caf" {
+                assert_eq!(generated, "caf");
+            } else {
+                assert_eq!(original, generated, "\n{output}");
+            }
+            checked += 1;
+        }
+        assert!(checked >= 8, "{output}");
+    }
+}
+
+#[test]
+fn source_map_scopes() {
+    let source_text = "function foo(first, second) {\n  for (let i = 0; i < 1; i++) {}\n  return first + second;\n}\nfoo(1, 2);\n";
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::mjs()).parse();
+    let mangler = Mangler::new()
+        .with_options(MangleOptions { scope_bindings: true, ..MangleOptions::default() })
+        .build(&ret.program);
+    let result = CodeGenerator::new()
+        .with_options(CodegenOptions { minify: true, ..Default::default() })
+        .with_mangler(Some(mangler))
+        .enable_source_map("test.js", source_text)
+        .build(&ret.program);
+    let output = result.source_text;
+    let source_map = result.source_map.unwrap();
+    let name = |id: Option<u32>| id.and_then(|id| source_map.get_name(id));
+
+    let [Some(global)] = source_map.get_original_scopes().unwrap() else { unreachable!() };
+    assert_eq!((global.start, global.end, name(global.kind)), ((0, 0), (5, 0), Some("global")));
+    let [function] = global.children.as_slice() else { unreachable!() };
+    assert_eq!((function.start, function.end), ((0, 0), (3, 1)));
+    assert_eq!((name(function.name), name(function.kind)), (Some("foo"), Some("function")));
+    assert!(function.is_stack_frame);
+    let variables =
+        function.variables.iter().map(|&id| name(Some(id)).unwrap()).collect::<Vec<_>>();
+    assert_eq!(variables, ["first", "second"]);
+    let [for_scope] = function.children.as_slice() else { unreachable!() };
+    assert_eq!(
+        (for_scope.start, for_scope.end, name(for_scope.kind)),
+        ((1, 2), (1, 32), Some("block"))
+    );
+
+    let [global_range] = source_map.get_generated_ranges().unwrap() else { unreachable!() };
+    assert_eq!(global_range.definition, Some((0, 0)));
+    let [function_range] = global_range.children.as_slice() else { unreachable!() };
+    assert_eq!(function_range.definition, Some((0, 1)));
+    let line = output.lines().next().unwrap();
+    let (start, end) = (function_range.start.1 as usize, function_range.end.1 as usize);
+    assert_eq!(&line[start..end], "function foo(b,c){for(let d=0;d<1;d++){}return b+c}");
+    let bindings = function_range.bindings.iter().map(|&id| name(id).unwrap()).collect::<Vec<_>>();
+    assert_eq!(bindings, ["b", "c"]);
+    let [for_range] = function_range.children.as_slice() else { unreachable!() };
+    assert_eq!(for_range.definition, Some((0, 2)));
+    assert_eq!(for_range.bindings.iter().map(|&id| name(id).unwrap()).collect::<Vec<_>>(), ["d"]);
+}
+
+#[test]
+fn source_map_scope_kinds() {
+    let source_text = "class Foo {\n  static { let a; }\n}\ntry {} catch (err) {}\nswitch (x) { case 1: let b; }\n";
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::mjs()).parse();
+    let mangler = Mangler::new()
+        .with_options(MangleOptions { scope_bindings: true, ..MangleOptions::default() })
+        .build(&ret.program);
+    let source_map = CodeGenerator::new()
+        .with_mangler(Some(mangler))
+        .enable_source_map("test.js", source_text)
+        .build(&ret.program)
+        .source_map
+        .unwrap();
+    let name = |id: Option<u32>| id.and_then(|id| source_map.get_name(id));
+    let variables = |scope: &OriginalScope| {
+        scope.variables.iter().map(|&id| name(Some(id)).unwrap()).collect::<Vec<_>>()
+    };
+
+    let [Some(global)] = source_map.get_original_scopes().unwrap() else { unreachable!() };
+    let [class, catch, switch] = global.children.as_slice() else { unreachable!() };
+    assert_eq!((name(class.name), name(class.kind)), (Some("Foo"), Some("class")));
+    assert!(!class.is_stack_frame);
+    let [static_block] = class.children.as_slice() else { unreachable!() };
+    assert_eq!((name(static_block.kind), variables(static_block)), (Some("block"), vec!["a"]));
+    // The catch parameter is declared in the scope of the catch body.
+    assert_eq!((catch.start, catch.end), ((3, 19), (3, 21)));
+    assert_eq!((name(catch.kind), variables(catch)), (Some("block"), vec!["err"]));
+    assert_eq!((name(switch.kind), variables(switch)), (Some("block"), vec!["b"]));
+}
+
+/// The text at a line and UTF-16 column.
+fn text_at(text: &str, line: u32, column: u32) -> String {
+    let line = text.split('\n').nth(line as usize).unwrap();
+    let units = line.encode_utf16().skip(column as usize).collect::<Vec<_>>();
+    String::from_utf16(&units).unwrap()
+}
@@ -0,0 +1,139 @@
+use oxc_allocator::{Allocator, CloneIn};
+use oxc_ast::{
+    ast::{IdentifierReference, LogicalOperator, Program, Statement},
+    visit::walk_mut,
+    AstBuilder, VisitMut, NONE,
+};
+use oxc_codegen::{CodeGenerator, CodegenOptions, CommentOptions, IndentChar};
+use oxc_parser::Parser;
+use oxc_span::{SourceType, SPAN};
+
+fn reprint<F>(source_text: &str, transform: F) -> String
+where
+    F: for<'a> FnOnce(&mut Program<'a>, AstBuilder<'a>),
+{
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::mjs()).parse();
+    let original = ret.program.clone_in(&allocator);
+    let mut program = ret.program;
+    transform(&mut program, AstBuilder::new(&allocator));
+    CodeGenerator::new()
+        .with_options(CodegenOptions {
+            indent_char: IndentChar::Space,
+            indent_width: 2,
+            ..CodegenOptions::default()
+        })
+        .enable_comment(source_text, ret.trivias, CommentOptions::default())
+        .enable_reprint(source_text, &original)
+        .build(&program)
+        .source_text
+}
+
+struct Rename(&'static str, &'static str);
+
+impl<'a> VisitMut<'a> for Rename {
+    fn visit_identifier_reference(&mut self, it: &mut IdentifierReference<'a>) {
+        if it.name == self.0 {
+            it.name = self.1.into();
+        }
+        walk_mut::walk_identifier_reference(self, it);
+    }
+}
+
+#[test]
+fn untouched() {
+    let source = "import { foo } from \"./foo\"\n\nconst x  =  foo( 1,2 )   // keep\n\n\nfunction  keep ( a ) {\n    return a*2\n}\n";
+    assert_eq!(reprint(source, |_, _| {}), source);
+}
+
+#[test]
+fn modified() {
+    let source = r"import { foo } from './foo'
+
+/* leading */
+const x  =  foo( 1,2 )   // keep
+
+function change(a) {
+  if (a) {
+    console.log( 'yes' );
+  }
+  return   oldName( a,
+    `multi
+  line`);
+}
+";
+    let expected = r"import { foo } from './foo'
+
+/* leading */
+const x  =  foo( 1,2 )   // keep
+
+function change(a) {
+  if (a) {
+    console.log( 'yes' );
+  }
+  return newName(a, `multi
+  line`);
+}
+";
+    assert_eq!(
+        reprint(source, |program, _| Rename("oldName", "newName").visit_program(program)),
+        expected
+    );
+}
+
+#[test]
+fn reindent() {
+    // Moved into a new block.
+    let source = "const a = {\n  b: 1,\n  c: `x\n  y`\n};\n";
+    let expected = "{\n  const a = {\n    b: 1,\n    c: `x\n  y`\n  };\n}\n";
+    let result = reprint(source, |program, ast| {
+        let body = std::mem::replace(&mut program.body, ast.vec());
+        program.body.push(ast.statement_block(SPAN, body));
+    });
+    assert_eq!(result, expected);
+}
+
+#[test]
+fn inserted() {
+    fn call<'a>(ast: AstBuilder<'a>, name: &'static str) -> Statement<'a> {
+        let callee = ast.expression_identifier_reference(SPAN, name);
+        ast.statement_expression(SPAN, ast.expression_call(SPAN, callee, NONE, ast.vec(), false))
+    }
+
+    let source = "a  =  1\n\nb()\n";
+    let result = reprint(source, |program, ast| {
+        program.body.insert(1, call(ast, "inserted"));
+    });
+    assert_eq!(result, "a  =  1\ninserted();\n\nb()\n");
+
+    // A `;` is needed before a statement starting with `(`.
+    let result = reprint(source, |program, ast| {
+        let left = ast.expression_identifier_reference(SPAN, "f");
+        let right = ast.expression_identifier_reference(SPAN, "g");
+        let callee = ast.expression_logical(SPAN, left, LogicalOperator::Or, right);
+        let stmt = ast
+            .statement_expression(SPAN, ast.expression_call(SPAN, callee, NONE, ast.vec(), false));
+        program.body.insert(1, stmt);
+    });
+    assert_eq!(result, "a  =  1;\n(f || g)();\n\nb()\n");
+}
+
+#[test]
+fn context() {
+    let rename =
+        |source| reprint(source, |program, _| Rename("oldName", "newName").visit_program(program));
+    // Identifiers containing `in` are not `in` operators.
+    assert_eq!(
+        rename("for (var i = index  +  1, x = oldName;;) {}\n"),
+        "for (var i = index  +  1, x = newName;;) {}\n"
+    );
+    assert_eq!(
+        rename("for (var i = (a  in  b), x = oldName;;) {}\n"),
+        "for (var i = (a  in  b), x = newName;;) {}\n"
+    );
+    // Calls in a `new` callee need parentheses, arguments do not.
+    assert_eq!(rename("new (a( 1 ).b)(oldName);\n"), "new (a( 1 ).b)(newName);\n");
+    assert_eq!(rename("new a.b(f( 1 ), oldName);\n"), "new a.b(f( 1 ), newName);\n");
+    // Object literals at the start of an arrow function body.
+    assert_eq!(rename("(() => ({ a:1 }).a  ||  oldName);\n"), "() => ({ a:1 }).a || newName;\n");
+}
@@ -11,7 +11,7 @@ pub fn test(source_text: &str, expected: &str) {
         .enable_comment(
             source_text,
             ret.trivias,
-            CommentOptions { preserve_annotate_comments: true },
+            CommentOptions { preserve_annotate_comments: true, ..CommentOptions::default() },
         )
         .build(&ret.program)
         .source_text;
@@ -36,3 +36,15 @@ pub fn test_minify(source_text: &str, expected: &str) {
         .source_text;
     assert_eq!(result, expected, "\nfor minify source: {source_text}");
 }
+
+pub fn test_options(source_text: &str, expected: &str, options: CodegenOptions) {
+    let source_type = SourceType::jsx();
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, source_type).parse();
+    let result = CodeGenerator::new()
+        .with_options(options)
+        .enable_comment(source_text, ret.trivias, CommentOptions::default())
+        .build(&ret.program)
+        .source_text;
+    assert_eq!(result, expected, "\nfor source: {source_text:?}");
+}
@@ -128,6 +128,8 @@ pub struct OxcDiagnosticInner {
     pub severity: Severity,
     pub code: OxcCode,
     pub url: Option<Cow<'static, str>>,
+    /// Whether the problem can be fixed automatically, e.g. with `oxlint --fix`.
+    pub fixable: bool,
 }
 
 impl fmt::Display for OxcDiagnostic {
@@ -184,6 +186,7 @@ impl OxcDiagnostic {
                 severity: Severity::Error,
                 code: OxcCode::default(),
                 url: None,
+                fixable: false,
             }),
         }
     }
@@ -198,6 +201,7 @@ impl OxcDiagnostic {
                 severity: Severity::Warning,
                 code: OxcCode::default(),
                 url: None,
+                fixable: false,
             }),
         }
     }
@@ -338,6 +342,15 @@ impl OxcDiagnostic {
         self
     }
 
+    /// Mark whether this diagnostic can be fixed automatically.
+    ///
+    /// Reporters that support it, such as SARIF and `stylish`, include this in
+    /// their output.
+    pub fn with_fixable(mut self, fixable: bool) -> Self {
+        self.inner.fixable = fixable;
+        self
+    }
+
     /// Add source code to this diagnostic and convert it into an [`Error`].
     ///
     /// You should use a [`NamedSource`] if you have a file name as well as the source code.
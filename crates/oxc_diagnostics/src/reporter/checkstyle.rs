@@ -10,8 +10,9 @@ pub struct CheckstyleReporter {
 }
 
 impl DiagnosticReporter for CheckstyleReporter {
+    #[allow(clippy::print_stdout)]
     fn finish(&mut self) {
-        format_checkstyle(&self.diagnostics);
+        println!("{}", format_checkstyle(&self.diagnostics));
     }
 
     fn render_diagnostics(&mut self, _s: &[u8]) {}
@@ -22,8 +23,7 @@ impl DiagnosticReporter for CheckstyleReporter {
     }
 }
 
-#[allow(clippy::print_stdout)]
-fn format_checkstyle(diagnostics: &[Error]) {
+fn format_checkstyle(diagnostics: &[Error]) -> String {
     let infos = diagnostics.iter().map(Info::new).collect::<Vec<_>>();
     let mut grouped: FxHashMap<String, Vec<Info>> = FxHashMap::default();
     for info in infos {
@@ -47,13 +47,13 @@ fn format_checkstyle(diagnostics: &[Error]) {
          let filename = &infos[0].filename;
          format!(r#"<file name="{filename}">{messages}</file>"#)
      }).collect::<Vec<_>>().join(" ");
-    println!(
+    format!(
         r#"<?xml version="1.0" encoding="utf-8"?><checkstyle version="4.3">{messages}</checkstyle>"#
-    );
+    )
 }
 
 /// <https://github.com/tafia/quick-xml/blob/6e34a730853fe295d68dc28460153f08a5a12955/src/escapei.rs#L84-L86>
-fn xml_escape(raw: &str) -> Cow<str> {
+pub(super) fn xml_escape(raw: &str) -> Cow<str> {
     xml_escape_impl(raw, |ch| matches!(ch, b'<' | b'>' | b'&' | b'\'' | b'\"'))
 }
 
@@ -102,3 +102,16 @@ fn xml_escape_impl<F: Fn(u8) -> bool>(raw: &str, escape_chars: F) -> Cow<str> {
         Cow::Borrowed(raw)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::{super::test_utils::diagnostics, format_checkstyle};
+
+    #[test]
+    fn checkstyle() {
+        let output = format_checkstyle(&diagnostics());
+        insta::with_settings!({ prepend_module_to_snapshot => false }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+}
@@ -33,7 +33,7 @@ impl DiagnosticReporter for GithubReporter {
 }
 
 fn format_github(diagnostic: &Error) -> String {
-    let Info { line, column, filename, message, severity, rule_id } = Info::new(diagnostic);
+    let Info { line, column, filename, message, severity, rule_id, .. } = Info::new(diagnostic);
     let severity = match severity {
         Severity::Error => "error",
         Severity::Warning | miette::Severity::Advice => "warning",
@@ -79,3 +79,16 @@ fn escape_property(value: &str) -> String {
     }
     result
 }
+
+#[cfg(test)]
+mod test {
+    use super::{super::test_utils::diagnostics, format_github};
+
+    #[test]
+    fn github() {
+        let output = diagnostics().iter().map(format_github).collect::<String>();
+        insta::with_settings!({ prepend_module_to_snapshot => false }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+}
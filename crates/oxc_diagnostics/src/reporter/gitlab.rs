@@ -0,0 +1,67 @@
+use serde_json::{json, Value};
+
+use super::{DiagnosticReporter, Fingerprints, Info};
+use crate::{Error, Severity};
+
+/// Renders reports as a [GitLab Code Quality](https://docs.gitlab.com/ee/ci/testing/code_quality.html#code-quality-report-format)
+/// report, which is shown in merge request widgets.
+#[derive(Default)]
+pub struct GitlabReporter {
+    diagnostics: Vec<Error>,
+}
+
+impl DiagnosticReporter for GitlabReporter {
+    #[allow(clippy::print_stdout)]
+    fn finish(&mut self) {
+        println!("{}", format_gitlab(&self.diagnostics));
+    }
+
+    fn render_diagnostics(&mut self, _s: &[u8]) {}
+
+    fn render_error(&mut self, error: Error) -> Option<String> {
+        self.diagnostics.push(error);
+        None
+    }
+}
+
+fn format_gitlab(diagnostics: &[Error]) -> String {
+    let mut fingerprints = Fingerprints::default();
+    let issues = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let info = Info::new(diagnostic);
+            let fingerprint = fingerprints.next(&info);
+            let severity = match info.severity {
+                Severity::Error => "major",
+                _ => "minor",
+            };
+            json!({
+                "description": info.message,
+                "check_name": info.rule_id.as_deref().unwrap_or("oxlint"),
+                "fingerprint": fingerprint,
+                "severity": severity,
+                "location": {
+                    "path": info.filename,
+                    "lines": { "begin": info.line.max(1), "end": info.end_line.max(1) }
+                }
+            })
+        })
+        .collect::<Vec<Value>>();
+    serde_json::to_string_pretty(&issues).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        super::test_utils::{diagnostics, sort_json},
+        format_gitlab,
+    };
+
+    #[test]
+    fn gitlab() {
+        let output = sort_json(&format_gitlab(&diagnostics()));
+        insta::with_settings!({ prepend_module_to_snapshot => false }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+}
@@ -0,0 +1,69 @@
+use std::collections::BTreeMap;
+
+use super::{checkstyle::xml_escape, DiagnosticReporter, Info};
+use crate::{Error, Severity};
+
+/// Renders reports as JUnit XML, with a test suite for each file and a failed test case for
+/// each diagnostic.
+#[derive(Default)]
+pub struct JunitReporter {
+    diagnostics: Vec<Error>,
+}
+
+impl DiagnosticReporter for JunitReporter {
+    #[allow(clippy::print_stdout)]
+    fn finish(&mut self) {
+        println!("{}", format_junit(&self.diagnostics));
+    }
+
+    fn render_diagnostics(&mut self, _s: &[u8]) {}
+
+    fn render_error(&mut self, error: Error) -> Option<String> {
+        self.diagnostics.push(error);
+        None
+    }
+}
+
+/// <https://github.com/eslint/eslint/blob/v8.57.0/lib/cli-engine/formatters/junit.js>
+fn format_junit(diagnostics: &[Error]) -> String {
+    let mut grouped: BTreeMap<String, Vec<Info>> = BTreeMap::new();
+    for info in diagnostics.iter().map(Info::new) {
+        grouped.entry(info.filename.clone()).or_default().push(info);
+    }
+    let mut output = String::from("<?xml version=\"1.0\" encoding=\"utf-8\"?>\n<testsuites>\n");
+    for (filename, infos) in &grouped {
+        let filename = xml_escape(filename);
+        let count = infos.len();
+        output.push_str(&format!(
+            "<testsuite package=\"org.oxlint\" time=\"0\" tests=\"{count}\" errors=\"{count}\" name=\"{filename}\">\n"
+        ));
+        for Info { line, column, message, severity, rule_id, .. } in infos {
+            let severity = match severity {
+                Severity::Error => "Error",
+                _ => "Warning",
+            };
+            let rule_id = rule_id.as_deref().unwrap_or_default();
+            let name = xml_escape(rule_id);
+            let message = xml_escape(message);
+            output.push_str(&format!(
+                "<testcase time=\"0\" name=\"org.oxlint.{name}\" classname=\"{filename}\"><failure message=\"{message}\">line {line}, col {column}, {severity} - {message} ({name})</failure></testcase>\n"
+            ));
+        }
+        output.push_str("</testsuite>\n");
+    }
+    output.push_str("</testsuites>");
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::test_utils::diagnostics, format_junit};
+
+    #[test]
+    fn junit() {
+        let output = format_junit(&diagnostics());
+        insta::with_settings!({ prepend_module_to_snapshot => false }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+}
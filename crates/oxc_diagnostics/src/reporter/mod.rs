@@ -2,17 +2,25 @@
 
 mod checkstyle;
 mod github;
+mod gitlab;
 mod graphical;
 mod json;
+mod junit;
+mod sarif;
+mod stylish;
 mod unix;
 
 use std::io::{BufWriter, Stdout};
 
+use miette::SourceSpan;
+use rustc_hash::FxHashMap;
+
 pub use self::{
-    checkstyle::CheckstyleReporter, github::GithubReporter, graphical::GraphicalReporter,
-    json::JsonReporter, unix::UnixReporter,
+    checkstyle::CheckstyleReporter, github::GithubReporter, gitlab::GitlabReporter,
+    graphical::GraphicalReporter, json::JsonReporter, junit::JunitReporter, sarif::SarifReporter,
+    stylish::StylishReporter, unix::UnixReporter,
 };
-use crate::{Error, Severity};
+use crate::{service::SourcedDiagnostic, Error, Severity};
 
 /// stdio is blocked by LineWriter, use a BufWriter to reduce syscalls.
 /// See `https://github.com/rust-lang/rust/issues/60673`.
@@ -93,42 +101,163 @@ pub trait DiagnosticReporter {
 struct Info {
     line: usize,
     column: usize,
+    end_line: usize,
+    end_column: usize,
     filename: String,
     message: String,
     severity: Severity,
     rule_id: Option<String>,
+    /// Documentation of the rule that reported the diagnostic
+    url: Option<String>,
+    fixable: bool,
+    /// Hash of the file name, rule, message and reported source code. It does not depend on the
+    /// position of the diagnostic, so it is stable across unrelated edits of the file.
+    fingerprint: u64,
 }
 
 impl Info {
     fn new(diagnostic: &Error) -> Self {
         let mut line = 0;
         let mut column = 0;
+        let mut end_line = 0;
+        let mut end_column = 0;
         let mut filename = String::new();
-        let mut message = String::new();
-        let mut severity = Severity::Warning;
-        let mut rule_id = None;
+        let mut snippet = String::new();
+        let severity = match diagnostic.severity() {
+            Some(Severity::Error) => Severity::Error,
+            _ => Severity::Warning,
+        };
+        let msg = diagnostic.to_string();
+        let (rule_id, message) = match diagnostic.code() {
+            Some(code) => (Some(code.to_string()), msg),
+            // Diagnostics without a code usually come with `eslint(rule): message`
+            None => msg.split_once(':').map_or_else(
+                || (None, msg.to_string()),
+                |(id, msg)| (Some(id.to_string()), msg.trim().to_string()),
+            ),
+        };
         if let Some(mut labels) = diagnostic.labels() {
             if let Some(source) = diagnostic.source_code() {
                 if let Some(label) = labels.next() {
                     if let Ok(span_content) = source.read_span(label.inner(), 0, 0) {
                         line = span_content.line() + 1;
                         column = span_content.column() + 1;
+                        (end_line, end_column) = (line, column);
                         if let Some(name) = span_content.name() {
                             filename = name.to_string();
                         };
-                        if matches!(diagnostic.severity(), Some(Severity::Error)) {
-                            severity = Severity::Error;
-                        }
-                        let msg = diagnostic.to_string();
-                        // Our messages usually comes with `eslint(rule): message`
-                        (rule_id, message) = msg.split_once(':').map_or_else(
-                            || (None, msg.to_string()),
-                            |(id, msg)| (Some(id.to_string()), msg.trim().to_string()),
-                        );
+                        snippet = String::from_utf8_lossy(span_content.data()).into_owned();
+                    }
+                    let end = SourceSpan::from(label.offset() + label.len());
+                    if let Ok(span_content) = source.read_span(&end, 0, 0) {
+                        end_line = span_content.line() + 1;
+                        end_column = span_content.column() + 1;
                     }
                 }
             }
         }
-        Self { line, column, filename, message, severity, rule_id }
+        let url = diagnostic.url().map(|url| url.to_string());
+        let fixable = diagnostic
+            .downcast_ref::<SourcedDiagnostic>()
+            .is_some_and(|sourced| sourced.diagnostic.fixable);
+        let fingerprint = stable_hash(
+            [filename.as_str(), rule_id.as_deref().unwrap_or_default(), message.as_str()]
+                .into_iter()
+                .flat_map(|part| part.bytes().chain([0]))
+                .chain(snippet.split_whitespace().flat_map(|word| word.bytes().chain([b' ']))),
+        );
+        Self {
+            line,
+            column,
+            end_line,
+            end_column,
+            filename,
+            message,
+            severity,
+            rule_id,
+            url,
+            fixable,
+            fingerprint,
+        }
+    }
+}
+
+/// Fingerprints of diagnostics that are unique within a report.
+///
+/// Identical problems in the same file, e.g. two `debugger;` statements, get the same
+/// [`Info::fingerprint`]. They are told apart by the order in which they occur.
+#[derive(Default)]
+struct Fingerprints {
+    occurrences: FxHashMap<u64, usize>,
+}
+
+impl Fingerprints {
+    fn next(&mut self, info: &Info) -> String {
+        let occurrence = self.occurrences.entry(info.fingerprint).or_default();
+        *occurrence += 1;
+        if *occurrence == 1 {
+            format!("{:016x}", info.fingerprint)
+        } else {
+            format!("{:016x}-{occurrence}", info.fingerprint)
+        }
+    }
+}
+
+/// 64-bit FNV-1a hash of `bytes`, which is stable across Rust versions and platforms.
+fn stable_hash<I: IntoIterator<Item = u8>>(bytes: I) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes
+        .into_iter()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod test_utils {
+    use serde_json::Value;
+
+    use crate::{service::DiagnosticService, Error, LabeledSpan, OxcDiagnostic};
+
+    /// Diagnostics covering the cases reporters handle differently: a fixable warning with a
+    /// documentation link, the same warning reported twice, an error spanning several lines and
+    /// a diagnostic without a code or labels.
+    pub(super) fn diagnostics() -> Vec<Error> {
+        let source_text = "debugger;\nif (a) {\n  debugger;\n}\nlet x = `a\nb` < 1;\n";
+        let debugger = |start: usize| {
+            OxcDiagnostic::warn("`debugger` statement is not allowed")
+                .with_error_code("eslint", "no-debugger")
+                .with_url("https://oxc.rs/docs/guide/usage/linter/rules/eslint/no-debugger.html")
+                .with_label(LabeledSpan::underline(start..start + 9))
+                .with_fixable(true)
+        };
+        let diagnostics = vec![
+            debugger(0),
+            debugger(21),
+            OxcDiagnostic::error("Unexpected comparison: `<` with a template literal")
+                .with_error_code("eslint", "no-compare")
+                .with_label(LabeledSpan::underline(41..50)),
+            OxcDiagnostic::warn("File is ignored because of a matching ignore pattern"),
+        ];
+        DiagnosticService::wrap_diagnostics("src/index.js", source_text, diagnostics).1
+    }
+
+    /// Pretty-print `json` with sorted keys, whether or not `serde_json` preserves the order
+    /// of insertion.
+    pub(super) fn sort_json(json: &str) -> String {
+        fn sort(value: Value) -> Value {
+            match value {
+                Value::Object(map) => {
+                    let mut entries = map.into_iter().collect::<Vec<_>>();
+                    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+                    Value::Object(
+                        entries.into_iter().map(|(key, value)| (key, sort(value))).collect(),
+                    )
+                }
+                Value::Array(items) => Value::Array(items.into_iter().map(sort).collect()),
+                value => value,
+            }
+        }
+        serde_json::to_string_pretty(&sort(serde_json::from_str(json).unwrap())).unwrap()
     }
 }
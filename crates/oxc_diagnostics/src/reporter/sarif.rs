@@ -0,0 +1,116 @@
+use cow_utils::CowUtils;
+use rustc_hash::FxHashMap;
+use serde_json::{json, Map, Value};
+
+use super::{DiagnosticReporter, Fingerprints, Info};
+use crate::{Error, Severity};
+
+/// Renders reports as a [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+/// log, e.g. for [GitHub code scanning](https://docs.github.com/en/code-security/code-scanning/integrating-with-code-scanning/uploading-a-sarif-file-to-github).
+///
+/// Rules link to their documentation, results carry a stable fingerprint and are marked as
+/// `fixable` in their properties when a fix is available.
+#[derive(Default)]
+pub struct SarifReporter {
+    diagnostics: Vec<Error>,
+}
+
+impl DiagnosticReporter for SarifReporter {
+    #[allow(clippy::print_stdout)]
+    fn finish(&mut self) {
+        println!("{}", format_sarif(&self.diagnostics));
+    }
+
+    fn render_diagnostics(&mut self, _s: &[u8]) {}
+
+    fn render_error(&mut self, error: Error) -> Option<String> {
+        self.diagnostics.push(error);
+        None
+    }
+}
+
+fn format_sarif(diagnostics: &[Error]) -> String {
+    let mut rules = Vec::new();
+    let mut rule_indices: FxHashMap<String, usize> = FxHashMap::default();
+    let mut fingerprints = Fingerprints::default();
+    let results = diagnostics
+        .iter()
+        .map(|diagnostic| {
+            let info = Info::new(diagnostic);
+            let mut result = Map::new();
+            if let Some(rule_id) = &info.rule_id {
+                let index = *rule_indices.entry(rule_id.clone()).or_insert_with(|| {
+                    let mut rule = json!({ "id": rule_id });
+                    if let Some(url) = &info.url {
+                        rule["helpUri"] = json!(url);
+                    }
+                    rules.push(rule);
+                    rules.len() - 1
+                });
+                result.insert("ruleId".into(), json!(rule_id));
+                result.insert("ruleIndex".into(), json!(index));
+            }
+            let level = match info.severity {
+                Severity::Error => "error",
+                _ => "warning",
+            };
+            result.insert("level".into(), json!(level));
+            result.insert("message".into(), json!({ "text": &info.message }));
+            if !info.filename.is_empty() {
+                let mut location = json!({
+                    "physicalLocation": {
+                        "artifactLocation": { "uri": info.filename.cow_replace('\\', "/") }
+                    }
+                });
+                if info.line > 0 {
+                    location["physicalLocation"]["region"] = json!({
+                        "startLine": info.line,
+                        "startColumn": info.column,
+                        "endLine": info.end_line,
+                        "endColumn": info.end_column,
+                    });
+                }
+                result.insert("locations".into(), json!([location]));
+            }
+            result.insert(
+                "partialFingerprints".into(),
+                json!({ "oxlint/v1": fingerprints.next(&info) }),
+            );
+            if info.fixable {
+                result.insert("properties".into(), json!({ "fixable": true }));
+            }
+            Value::Object(result)
+        })
+        .collect::<Vec<_>>();
+    let sarif = json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "oxlint",
+                    "informationUri": "https://oxc.rs",
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    });
+    serde_json::to_string_pretty(&sarif).unwrap()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{
+        super::test_utils::{diagnostics, sort_json},
+        format_sarif,
+    };
+
+    #[test]
+    fn sarif() {
+        let output = sort_json(&format_sarif(&diagnostics()));
+        insta::with_settings!({ prepend_module_to_snapshot => false }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+}
@@ -0,0 +1,135 @@
+use std::{
+    collections::BTreeMap,
+    io::{BufWriter, IsTerminal, Stdout, Write},
+};
+
+use owo_colors::OwoColorize;
+use unicode_width::UnicodeWidthStr;
+
+use super::{writer, DiagnosticReporter, Info};
+use crate::{graphical_theme::ThemeStyles, Error, GraphicalTheme, Severity};
+
+/// Renders reports like ESLint's default `stylish` formatter, grouped by file with a summary of
+/// problems and how many of them are fixable.
+pub struct StylishReporter {
+    diagnostics: Vec<Error>,
+    styles: ThemeStyles,
+    writer: BufWriter<Stdout>,
+}
+
+impl Default for StylishReporter {
+    fn default() -> Self {
+        let styles = GraphicalTheme::new(std::io::stdout().is_terminal()).styles;
+        Self { diagnostics: Vec::new(), styles, writer: writer() }
+    }
+}
+
+impl DiagnosticReporter for StylishReporter {
+    fn finish(&mut self) {
+        let output = format_stylish(&self.diagnostics, &self.styles);
+        self.writer.write_all(output.as_bytes()).unwrap();
+        self.writer.flush().unwrap();
+    }
+
+    fn render_diagnostics(&mut self, _s: &[u8]) {}
+
+    fn render_error(&mut self, error: Error) -> Option<String> {
+        self.diagnostics.push(error);
+        None
+    }
+}
+
+/// <https://github.com/eslint/eslint/blob/v8.57.0/lib/cli-engine/formatters/stylish.js>
+fn format_stylish(diagnostics: &[Error], styles: &ThemeStyles) -> String {
+    let mut grouped: BTreeMap<String, Vec<Info>> = BTreeMap::new();
+    for info in diagnostics.iter().map(Info::new) {
+        grouped.entry(info.filename.clone()).or_default().push(info);
+    }
+
+    let mut output = String::new();
+    let (mut errors, mut warnings) = (0, 0);
+    let (mut fixable_errors, mut fixable_warnings) = (0, 0);
+    for (filename, infos) in &grouped {
+        output.push_str(&format!("\n{}\n", filename.style(styles.link)));
+
+        let rows = infos
+            .iter()
+            .map(|info| {
+                let (severity, style) = match info.severity {
+                    Severity::Error => ("error", styles.error),
+                    _ => ("warning", styles.warning),
+                };
+                let message = info.message.lines().collect::<Vec<_>>().join(" ");
+                (info, severity, style, message)
+            })
+            .collect::<Vec<_>>();
+        let line_width = rows.iter().map(|row| row.0.line.to_string().len()).max().unwrap_or(0);
+        let column_width = rows.iter().map(|row| row.0.column.to_string().len()).max().unwrap_or(0);
+        let severity_width = rows.iter().map(|row| row.1.len()).max().unwrap_or(0);
+        let message_width = rows.iter().map(|row| row.3.width()).max().unwrap_or(0);
+
+        for (info, severity, style, message) in rows {
+            if info.severity == Severity::Error {
+                errors += 1;
+                fixable_errors += usize::from(info.fixable);
+            } else {
+                warnings += 1;
+                fixable_warnings += usize::from(info.fixable);
+            }
+            let position = format!("{:>line_width$}:{:<column_width$}", info.line, info.column);
+            let severity = format!("{severity:<severity_width$}");
+            let padding = " ".repeat(message_width - message.width());
+            let rule_id = info.rule_id.as_deref().unwrap_or_default();
+            output.push_str(&format!(
+                "  {}  {}  {message}{padding}  {}\n",
+                position.style(styles.linum),
+                severity.style(style),
+                rule_id.style(styles.linum),
+            ));
+        }
+    }
+
+    let total = errors + warnings;
+    if total == 0 {
+        return output;
+    }
+    let style = if errors > 0 { styles.error } else { styles.warning };
+    let summary = format!(
+        "\n\u{2716} {total} {} ({errors} {}, {warnings} {})\n",
+        pluralize("problem", total),
+        pluralize("error", errors),
+        pluralize("warning", warnings),
+    );
+    output.push_str(&summary.style(style).to_string());
+    if fixable_errors + fixable_warnings > 0 {
+        let fixable = format!(
+            "  {fixable_errors} {} and {fixable_warnings} {} potentially fixable with the `--fix` option.\n",
+            pluralize("error", fixable_errors),
+            pluralize("warning", fixable_warnings),
+        );
+        output.push_str(&fixable.style(style).to_string());
+    }
+    output
+}
+
+fn pluralize(word: &str, count: usize) -> String {
+    if count == 1 {
+        word.to_string()
+    } else {
+        format!("{word}s")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{super::test_utils::diagnostics, format_stylish};
+    use crate::graphical_theme::ThemeStyles;
+
+    #[test]
+    fn stylish() {
+        let output = format_stylish(&diagnostics(), &ThemeStyles::none());
+        insta::with_settings!({ prepend_module_to_snapshot => false }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+}
@@ -39,7 +39,7 @@ impl DiagnosticReporter for UnixReporter {
 
 /// <https://github.com/fregante/eslint-formatters/tree/main/packages/eslint-formatter-unix>
 fn format_unix(diagnostic: &Error) -> String {
-    let Info { line, column, filename, message, severity, rule_id } = Info::new(diagnostic);
+    let Info { line, column, filename, message, severity, rule_id, .. } = Info::new(diagnostic);
     let severity = match severity {
         Severity::Error => "Error",
         _ => "Warning",
@@ -48,3 +48,16 @@ fn format_unix(diagnostic: &Error) -> String {
         rule_id.map_or_else(|| Cow::Borrowed(""), |rule_id| Cow::Owned(format!("/{rule_id}")));
     format!("{filename}:{line}:{column}: {message} [{severity}{rule_id}]\n")
 }
+
+#[cfg(test)]
+mod test {
+    use super::{super::test_utils::diagnostics, format_unix};
+
+    #[test]
+    fn unix() {
+        let output = diagnostics().iter().map(format_unix).collect::<String>();
+        insta::with_settings!({ prepend_module_to_snapshot => false }, {
+            insta::assert_snapshot!(output);
+        });
+    }
+}
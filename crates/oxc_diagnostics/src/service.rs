@@ -1,13 +1,16 @@
 use std::{
     cell::Cell,
+    fmt,
     path::{Path, PathBuf},
     sync::{mpsc, Arc},
 };
 
+use miette::{Diagnostic, LabeledSpan, SourceCode};
+
 use crate::{
     reporter::{
-        CheckstyleReporter, DiagnosticReporter, GithubReporter, GraphicalReporter, JsonReporter,
-        UnixReporter,
+        CheckstyleReporter, DiagnosticReporter, GithubReporter, GitlabReporter, GraphicalReporter,
+        JsonReporter, JunitReporter, SarifReporter, StylishReporter, UnixReporter,
     },
     Error, NamedSource, OxcDiagnostic, Severity,
 };
@@ -116,6 +119,27 @@ impl DiagnosticService {
         self.reporter = Box::<GithubReporter>::default();
     }
 
+    /// Configure this service to format reports as a [SARIF 2.1.0](https://sarifweb.azurewebsites.net/) log.
+    pub fn set_sarif_reporter(&mut self) {
+        self.reporter = Box::<SarifReporter>::default();
+    }
+
+    /// Configure this service to format reports as JUnit XML.
+    pub fn set_junit_reporter(&mut self) {
+        self.reporter = Box::<JunitReporter>::default();
+    }
+
+    /// Configure this service to format reports as a [GitLab Code
+    /// Quality](https://docs.gitlab.com/ee/ci/testing/code_quality.html) report.
+    pub fn set_gitlab_reporter(&mut self) {
+        self.reporter = Box::<GitlabReporter>::default();
+    }
+
+    /// Configure this service to format reports like ESLint's `stylish` formatter.
+    pub fn set_stylish_reporter(&mut self) {
+        self.reporter = Box::<StylishReporter>::default();
+    }
+
     /// Set to `true` to only report errors and ignore warnings.
     ///
     /// Use [`with_silent`](DiagnosticService::with_silent) to disable reporting entirely.
@@ -190,7 +214,9 @@ impl DiagnosticService {
         let source = Arc::new(NamedSource::new(path.to_string_lossy(), source_text.to_owned()));
         let diagnostics = diagnostics
             .into_iter()
-            .map(|diagnostic| diagnostic.with_source_code(Arc::clone(&source)))
+            .map(|diagnostic| {
+                Error::new(SourcedDiagnostic { diagnostic, source_code: Arc::clone(&source) })
+            })
             .collect();
         (path.to_path_buf(), diagnostics)
     }
@@ -246,3 +272,48 @@ impl DiagnosticService {
         self.reporter.finish();
     }
 }
+
+/// An [`OxcDiagnostic`] along with the source code its labels point into.
+///
+/// Unlike [`Error::with_source_code`], the diagnostic can still be read back with
+/// [`Error::downcast_ref`], so reporters have access to data that [`Diagnostic`] has no
+/// accessor for, such as [`OxcDiagnosticInner::fixable`](crate::OxcDiagnosticInner::fixable).
+#[derive(Debug)]
+pub(crate) struct SourcedDiagnostic {
+    pub(crate) diagnostic: OxcDiagnostic,
+    source_code: Arc<NamedSource<String>>,
+}
+
+impl fmt::Display for SourcedDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.diagnostic)
+    }
+}
+
+impl std::error::Error for SourcedDiagnostic {}
+
+impl Diagnostic for SourcedDiagnostic {
+    fn code<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.diagnostic.code()
+    }
+
+    fn severity(&self) -> Option<Severity> {
+        self.diagnostic.severity()
+    }
+
+    fn help<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.diagnostic.help()
+    }
+
+    fn url<'a>(&'a self) -> Option<Box<dyn fmt::Display + 'a>> {
+        self.diagnostic.url()
+    }
+
+    fn source_code(&self) -> Option<&dyn SourceCode> {
+        Some(&*self.source_code)
+    }
+
+    fn labels(&self) -> Option<Box<dyn Iterator<Item = LabeledSpan> + '_>> {
+        self.diagnostic.labels()
+    }
+}
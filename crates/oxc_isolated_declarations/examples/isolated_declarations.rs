@@ -43,7 +43,11 @@ fn main() {
         .enable_comment(
             &source_text,
             ret.trivias,
-            CommentOptions { preserve_annotate_comments: false },
+            CommentOptions {
+                preserve_annotate_comments: false,
+                preserve_normal_comments: false,
+                ..CommentOptions::default()
+            },
         )
         .build(&id_ret.program)
         .source_text;
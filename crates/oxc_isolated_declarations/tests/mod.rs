@@ -24,7 +24,11 @@ fn transform(path: &Path, source_text: &str) -> String {
         .enable_comment(
             source_text,
             parser_ret.trivias,
-            CommentOptions { preserve_annotate_comments: false },
+            CommentOptions {
+                preserve_annotate_comments: false,
+                preserve_normal_comments: false,
+                ..CommentOptions::default()
+            },
         )
         .build(&id_ret.program)
         .source_text;
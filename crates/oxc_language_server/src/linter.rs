@@ -6,12 +6,12 @@ use std::{
     sync::{Arc, OnceLock},
 };
 
-use log::debug;
+use log::{debug, error};
 use oxc_allocator::Allocator;
 use oxc_diagnostics::{Error, NamedSource, Severity};
 use oxc_linter::{
     loader::{JavaScriptSource, Loader},
-    FixKind, Linter,
+    ConfigDiscovery, DiscoveredConfig, FixKind, Linter,
 };
 use oxc_parser::{ParseOptions, Parser};
 use oxc_semantic::SemanticBuilder;
@@ -153,12 +153,26 @@ pub struct FixedContent {
 
 pub struct IsolatedLintHandler {
     linter: Arc<Linter>,
+    discovered: Option<Arc<DiscoveredConfig>>,
+    root: PathBuf,
     loader: Loader,
 }
 
 impl IsolatedLintHandler {
     pub fn new(linter: Arc<Linter>) -> Self {
-        Self { linter, loader: Loader }
+        Self { linter, discovered: None, root: PathBuf::new(), loader: Loader }
+    }
+
+    /// Lint with the configuration discovered for the document, and match the
+    /// `overrides` of the workspace config against paths relative to `root`.
+    pub fn with_discovered_config(
+        mut self,
+        discovered: Option<Arc<DiscoveredConfig>>,
+        root: PathBuf,
+    ) -> Self {
+        self.discovered = discovered;
+        self.root = root;
+        self
     }
 
     pub fn run_single(
@@ -278,7 +292,12 @@ impl IsolatedLintHandler {
                 return Some(Self::wrap_diagnostics(path, &source_text, reports, start));
             };
 
-            let result = self.linter.run(path, Rc::new(semantic_ret.semantic));
+            let result = self.linter.run_with_discovered(
+                path,
+                Rc::new(semantic_ret.semantic),
+                self.discovered.as_deref(),
+                &self.root,
+            );
 
             let reports = result
                 .into_iter()
@@ -356,21 +375,47 @@ fn offset_to_position(offset: usize, source_text: &str) -> Option<Position> {
 
 pub struct ServerLinter {
     linter: Arc<Linter>,
+    config_discovery: Option<ConfigDiscovery>,
+    root: PathBuf,
 }
 
 impl ServerLinter {
     pub fn new() -> Self {
         let linter = Linter::default().with_fix(FixKind::SafeFix);
-        Self { linter: Arc::new(linter) }
+        Self::new_with_linter(linter)
     }
 
     pub fn new_with_linter(linter: Linter) -> Self {
-        Self { linter: Arc::new(linter) }
+        Self { linter: Arc::new(linter), config_discovery: None, root: PathBuf::new() }
+    }
+
+    /// Lint each document with the `.oxlintrc.json` closest to it. Globs of
+    /// the workspace config `overrides` are matched relative to `root`.
+    pub fn with_config_discovery(
+        mut self,
+        config_discovery: ConfigDiscovery,
+        root: PathBuf,
+    ) -> Self {
+        self.config_discovery = Some(config_discovery);
+        self.root = root;
+        self
     }
 
     pub fn run_single(&self, uri: &Url, content: Option<String>) -> Option<Vec<DiagnosticReport>> {
+        let path = uri.to_file_path().unwrap();
         IsolatedLintHandler::new(Arc::clone(&self.linter))
-            .run_single(&uri.to_file_path().unwrap(), content)
+            .with_discovered_config(self.discovered_config(&path), self.root.clone())
+            .run_single(&path, content)
+    }
+
+    fn discovered_config(&self, path: &Path) -> Option<Arc<DiscoveredConfig>> {
+        let (Some(discovery), Some(dir)) = (&self.config_discovery, path.parent()) else {
+            return None;
+        };
+        discovery.config_for(dir).unwrap_or_else(|err| {
+            error!("Failed to load config for {path:?}: {err:?}");
+            None
+        })
     }
 }
 
@@ -7,7 +7,7 @@ use futures::future::join_all;
 use globset::Glob;
 use ignore::gitignore::Gitignore;
 use log::{debug, error, info};
-use oxc_linter::{FixKind, Linter, OxlintOptions};
+use oxc_linter::{ConfigDiscovery, FixKind, Linter, OxlintOptions, CONFIG_FILE_NAME};
 use serde::{Deserialize, Serialize};
 use tokio::sync::{Mutex, OnceCell, RwLock, SetError};
 use tower_lsp::{
@@ -15,11 +15,13 @@ use tower_lsp::{
     lsp_types::{
         CodeAction, CodeActionKind, CodeActionOptions, CodeActionOrCommand, CodeActionParams,
         CodeActionProviderCapability, CodeActionResponse, ConfigurationItem, Diagnostic,
-        DidChangeConfigurationParams, DidChangeTextDocumentParams, DidCloseTextDocumentParams,
-        DidOpenTextDocumentParams, DidSaveTextDocumentParams, InitializeParams, InitializeResult,
-        InitializedParams, OneOf, ServerCapabilities, ServerInfo, TextDocumentSyncCapability,
-        TextDocumentSyncKind, TextEdit, Url, WorkDoneProgressOptions, WorkspaceEdit,
-        WorkspaceFoldersServerCapabilities, WorkspaceServerCapabilities,
+        DidChangeConfigurationParams, DidChangeTextDocumentParams, DidChangeWatchedFilesParams,
+        DidChangeWatchedFilesRegistrationOptions, DidCloseTextDocumentParams,
+        DidOpenTextDocumentParams, DidSaveTextDocumentParams, FileSystemWatcher, GlobPattern,
+        InitializeParams, InitializeResult, InitializedParams, OneOf, Registration,
+        ServerCapabilities, ServerInfo, TextDocumentSyncCapability, TextDocumentSyncKind, TextEdit,
+        Url, WorkDoneProgressOptions, WorkspaceEdit, WorkspaceFoldersServerCapabilities,
+        WorkspaceServerCapabilities,
     },
     Client, LanguageServer, LspService, Server,
 };
@@ -174,6 +176,41 @@ impl LanguageServer for Backend {
 
     async fn initialized(&self, _params: InitializedParams) {
         debug!("oxc initialized.");
+        // Nested config files are cached once discovered, so reload them when they change.
+        let watchers = vec![FileSystemWatcher {
+            glob_pattern: GlobPattern::String(format!("**/{CONFIG_FILE_NAME}")),
+            kind: None,
+        }];
+        let registration = Registration {
+            id: "oxc-config-watcher".into(),
+            method: "workspace/didChangeWatchedFiles".into(),
+            register_options: serde_json::to_value(DidChangeWatchedFilesRegistrationOptions {
+                watchers,
+            })
+            .ok(),
+        };
+        if let Err(err) = self.client.register_capability(vec![registration]).await {
+            error!("Failed to watch config files: {err:?}");
+        }
+    }
+
+    async fn did_change_watched_files(&self, _params: DidChangeWatchedFilesParams) {
+        debug!("config files changed");
+        self.init_linter_config().await;
+        let run_level = { self.options.lock().await.get_lint_level() };
+        if run_level == SyntheticRunLevel::Disable {
+            return;
+        }
+        let opened_files = self
+            .diagnostics_report_map
+            .iter()
+            .filter_map(|entry| Url::from_str(entry.key()).ok())
+            .collect::<Vec<_>>();
+        for uri in opened_files {
+            if !self.is_ignored(&uri).await {
+                self.handle_file_update(uri, None, None).await;
+            }
+        }
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -342,17 +379,27 @@ impl Backend {
         if config.exists() {
             config_path = Some(config);
         }
-        if let Some(config_path) = config_path {
-            let mut linter = self.server_linter.write().await;
-            *linter = ServerLinter::new_with_linter(
-                Linter::from_options(
-                    OxlintOptions::default()
-                        .with_fix(FixKind::SafeFix)
-                        .with_config_path(Some(config_path)),
-                )
-                .expect("should have initialized linter with new options"),
-            );
-        }
+        // Documents are linted with the `.oxlintrc.json` closest to them, merged
+        // with the workspace config.
+        let config_discovery = match ConfigDiscovery::new(config_path.as_deref()) {
+            Ok(config_discovery) => config_discovery,
+            Err(err) => {
+                error!("Failed to load config: {err:?}");
+                return;
+            }
+        };
+        let linter = match config_path {
+            Some(config_path) => Linter::from_options(
+                OxlintOptions::default()
+                    .with_fix(FixKind::SafeFix)
+                    .with_config_path(Some(config_path)),
+            )
+            .expect("should have initialized linter with new options"),
+            None => Linter::default().with_fix(FixKind::SafeFix),
+        };
+        let mut server_linter = self.server_linter.write().await;
+        *server_linter = ServerLinter::new_with_linter(linter)
+            .with_config_discovery(config_discovery, root_path);
     }
 
     async fn handle_file_update(&self, uri: Url, content: Option<String>, version: Option<i32>) {
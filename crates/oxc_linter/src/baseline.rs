@@ -0,0 +1,242 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use dashmap::DashMap;
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::GetSpan;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    utils::{stable_hash, write_json},
+    Message,
+};
+
+/// Default name of the baseline file.
+pub const BASELINE_FILE_NAME: &str = "oxlint-baseline.json";
+
+/// Number of occurrences of each fingerprint, keyed by rule and fingerprint.
+type FileEntries = BTreeMap<String, BTreeMap<String, usize>>;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BaselineFile {
+    /// Keyed by path relative to the current working directory, with `/` separators.
+    files: BTreeMap<String, FileEntries>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BaselineMode {
+    /// Record all diagnostics of linted files, replacing their existing entries.
+    Write,
+    /// Only report diagnostics that are not in the baseline, and prune entries
+    /// that no longer occur.
+    Check,
+}
+
+/// Known diagnostics to suppress, so that a rule can be enabled without fixing
+/// all its existing violations first.
+///
+/// Diagnostics are keyed by file, rule and a fingerprint of the diagnostic
+/// message and the code it points at. Line numbers are not part of the key, so
+/// entries survive edits elsewhere in the file.
+#[derive(Debug)]
+pub struct Baseline {
+    cwd: Box<Path>,
+    path: PathBuf,
+    mode: BaselineMode,
+    /// Entries read from the baseline file
+    recorded: BTreeMap<String, FileEntries>,
+    /// Entries of files linted during this run
+    seen: DashMap<String, FileEntries>,
+}
+
+impl Baseline {
+    /// Read the baseline at `path`. In [`BaselineMode::Write`] the file does not
+    /// need to exist yet.
+    ///
+    /// Paths in the baseline are relative to `cwd`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the file cannot be read or parsed, or does not exist in
+    /// [`BaselineMode::Check`].
+    pub fn new<T: Into<Box<Path>>>(
+        cwd: T,
+        path: PathBuf,
+        mode: BaselineMode,
+    ) -> Result<Self, OxcDiagnostic> {
+        let cwd = cwd.into();
+        let path = if path.is_relative() { cwd.join(path) } else { path };
+        let recorded = if path.is_file() {
+            let json = fs::read_to_string(&path).map_err(|err| {
+                OxcDiagnostic::error(format!("Failed to read baseline {path:?}: {err}"))
+            })?;
+            serde_json::from_str::<BaselineFile>(&json)
+                .map_err(|err| {
+                    OxcDiagnostic::error(format!("Failed to parse baseline {path:?}: {err}"))
+                })?
+                .files
+        } else if mode == BaselineMode::Check {
+            return Err(OxcDiagnostic::error(format!(
+                "Baseline {path:?} does not exist, create it with `--baseline-write`."
+            )));
+        } else {
+            BTreeMap::default()
+        };
+        Ok(Self { cwd, path, mode, recorded, seen: DashMap::default() })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Remove the diagnostics of `path` that are in the baseline, or record all
+    /// of them in [`BaselineMode::Write`].
+    ///
+    /// Diagnostics without an error code, such as syntax errors, are never
+    /// suppressed.
+    pub(crate) fn apply<'a>(
+        &self,
+        path: &Path,
+        source_text: &str,
+        messages: Vec<Message<'a>>,
+    ) -> Vec<Message<'a>> {
+        let key = self.key(path);
+        let recorded = self.recorded.get(&key);
+        let mut seen = self.seen.entry(key).or_default();
+
+        messages
+            .into_iter()
+            .filter(|message| {
+                if !message.error.code.is_some() {
+                    return true;
+                }
+                let rule = message.error.code.to_string();
+                let fingerprint = fingerprint(message, source_text);
+
+                let suppress = match self.mode {
+                    BaselineMode::Write => true,
+                    BaselineMode::Check => {
+                        let count = |entries: Option<&FileEntries>| {
+                            entries
+                                .and_then(|entries| entries.get(&rule))
+                                .and_then(|fingerprints| fingerprints.get(&fingerprint))
+                                .copied()
+                                .unwrap_or(0)
+                        };
+                        count(Some(&*seen)) < count(recorded)
+                    }
+                };
+                if suppress {
+                    *seen.entry(rule).or_default().entry(fingerprint).or_default() += 1;
+                }
+                !suppress
+            })
+            .collect()
+    }
+
+    /// Write the baseline file if it changed.
+    ///
+    /// Entries of files linted during this run are replaced by the diagnostics
+    /// that were suppressed or recorded, and entries of files that no longer
+    /// exist are removed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the baseline cannot be serialized or the file cannot be written.
+    pub fn save(&self) -> Result<(), OxcDiagnostic> {
+        let mut files = self.recorded.clone();
+        files.retain(|file, _| self.cwd.join(file).is_file());
+        for entry in &self.seen {
+            if entry.value().is_empty() {
+                files.remove(entry.key());
+            } else {
+                files.insert(entry.key().clone(), entry.value().clone());
+            }
+        }
+
+        if files == self.recorded && self.path.is_file() {
+            return Ok(());
+        }
+        write_json(&self.path, &BaselineFile { files }, true, "baseline")
+    }
+
+    fn key(&self, path: &Path) -> String {
+        let path = path.strip_prefix(&self.cwd).unwrap_or(path);
+        path.components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Hash the diagnostic message and the source text it points at, ignoring
+/// whitespace.
+fn fingerprint(message: &Message, source_text: &str) -> String {
+    let span = message.span();
+    let snippet = source_text.get(span.start as usize..span.end as usize).unwrap_or_default();
+    let hash = stable_hash(
+        message
+            .error
+            .message
+            .bytes()
+            .chain([0])
+            .chain(snippet.split_whitespace().flat_map(|word| word.bytes().chain([b' ']))),
+    );
+    format!("{hash:016x}")
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use oxc_diagnostics::OxcDiagnostic;
+    use oxc_span::Span;
+
+    use super::{Baseline, BaselineMode};
+    use crate::Message;
+
+    fn messages(source_text: &str) -> Vec<Message<'static>> {
+        source_text
+            .match_indices("debugger;")
+            .map(|(start, text)| {
+                #[allow(clippy::cast_possible_truncation)]
+                let span = Span::sized(start as u32, text.len() as u32);
+                let diagnostic = OxcDiagnostic::warn("`debugger` statement is not allowed")
+                    .with_error_code("eslint", "no-debugger")
+                    .with_label(span);
+                Message::new(diagnostic, None)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_apply() {
+        let cwd = Path::new("/project");
+        let path = cwd.join("src/index.js");
+
+        let write =
+            Baseline::new(cwd, PathBuf::from("baseline.json"), BaselineMode::Write).unwrap();
+        let source_text = "debugger;\nfoo();\ndebugger;";
+        assert!(write.apply(&path, source_text, messages(source_text)).is_empty());
+        // Syntax errors are never recorded.
+        let syntax_error = Message::new(OxcDiagnostic::error("Unexpected token"), None);
+        assert_eq!(write.apply(&path, source_text, vec![syntax_error]).len(), 1);
+
+        let check = Baseline {
+            mode: BaselineMode::Check,
+            recorded: write.seen.into_iter().collect(),
+            ..Baseline::new(cwd, PathBuf::from("baseline.json"), BaselineMode::Write).unwrap()
+        };
+        assert_eq!(
+            check.recorded["src/index.js"]["eslint(no-debugger)"].values().sum::<usize>(),
+            2
+        );
+
+        // Moved diagnostics are still suppressed, and only new occurrences are reported.
+        let source_text = "\n\nbar();\ndebugger;\ndebugger;\n  debugger;";
+        assert_eq!(check.apply(&path, source_text, messages(source_text)).len(), 1);
+    }
+}
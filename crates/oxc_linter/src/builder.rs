@@ -6,9 +6,9 @@ use std::{
 use rustc_hash::FxHashSet;
 
 use crate::{
-    options::LintPlugins, rules::RULES, AllowWarnDeny, FixKind, FrameworkFlags, LintConfig,
-    LintFilter, LintFilterKind, LintOptions, Linter, Oxlintrc, RuleCategory, RuleEnum,
-    RuleWithSeverity,
+    config::CustomRules, options::LintPlugins, rules::RULES, AllowWarnDeny, FixKind,
+    FrameworkFlags, LintConfig, LintFilter, LintFilterKind, LintOptions, Linter, OxlintOverrides,
+    Oxlintrc, RuleCategory, RuleEnum, RuleWithSeverity,
 };
 
 #[must_use = "You dropped your builder without building a Linter! Did you mean to call .build()?"]
@@ -16,6 +16,7 @@ pub struct LinterBuilder {
     pub(super) rules: FxHashSet<RuleWithSeverity>,
     options: LintOptions,
     config: LintConfig,
+    overrides: OxlintOverrides,
     cache: RulesCache,
 }
 
@@ -33,7 +34,13 @@ impl LinterBuilder {
     pub fn empty() -> Self {
         let options = LintOptions::default();
         let cache = RulesCache::new(options.plugins);
-        Self { rules: FxHashSet::default(), options, config: LintConfig::default(), cache }
+        Self {
+            rules: FxHashSet::default(),
+            options,
+            config: LintConfig::default(),
+            overrides: OxlintOverrides::default(),
+            cache,
+        }
     }
 
     /// Warn on all rules in all plugins and categories, including those in `nursery`.
@@ -50,6 +57,7 @@ impl LinterBuilder {
                 .collect(),
             options,
             config: LintConfig::default(),
+            overrides: OxlintOverrides::default(),
             cache,
         }
     }
@@ -70,14 +78,26 @@ impl LinterBuilder {
     /// ```
     pub fn from_oxlintrc(start_empty: bool, oxlintrc: Oxlintrc) -> Self {
         // TODO: monorepo config merging, plugin-based extends, etc.
-        let Oxlintrc { plugins, settings, env, globals, rules: oxlintrc_rules } = oxlintrc;
-
-        let config = LintConfig { settings, env, globals };
+        // `extends` is resolved when the config file is loaded, and `root` and
+        // `ignorePatterns` only affect which files and config files are used.
+        let Oxlintrc {
+            plugins,
+            settings,
+            env,
+            globals,
+            rules: oxlintrc_rules,
+            custom_rules,
+            overrides,
+            ..
+        } = oxlintrc;
+
+        let custom_rules = CustomRules::new(custom_rules, &oxlintrc_rules);
+        let config = LintConfig { settings, env, globals, custom_rules };
         let options = LintOptions { plugins, ..Default::default() };
         let rules =
             if start_empty { FxHashSet::default() } else { Self::warn_correctness(plugins) };
         let cache = RulesCache::new(options.plugins);
-        let mut builder = Self { rules, options, config, cache };
+        let mut builder = Self { rules, options, config, overrides, cache };
 
         {
             let all_rules = builder.cache.borrow();
@@ -105,6 +125,13 @@ impl LinterBuilder {
         self
     }
 
+    /// Report `eslint-disable` directives that do not suppress any diagnostic.
+    #[inline]
+    pub fn with_report_unused_directives(mut self, severity: Option<AllowWarnDeny>) -> Self {
+        self.options.report_unused_directives = severity;
+        self
+    }
+
     /// Configure what linter plugins are enabled.
     ///
     /// Turning on a plugin will not automatically enable any of its rules. You must do this
@@ -223,7 +250,7 @@ impl LinterBuilder {
             self.rules.into_iter().collect::<Vec<_>>()
         };
         rules.sort_unstable_by_key(|r| r.id());
-        Linter::new(rules, self.options, self.config)
+        Linter::new(rules, self.options, self.config, self.overrides)
     }
 
     /// Warn for all correctness rules in the given set of plugins.
@@ -254,6 +281,7 @@ impl fmt::Debug for LinterBuilder {
             .field("rules", &self.rules)
             .field("options", &self.options)
             .field("config", &self.config)
+            .field("overrides", &self.overrides)
             .finish_non_exhaustive()
     }
 }
@@ -0,0 +1,344 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use dashmap::DashMap;
+use oxc_diagnostics::{LabeledSpan, OxcDiagnostic, Severity};
+use oxc_semantic::ModuleRecord;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    utils::{stable_hash, write_json},
+    Message,
+};
+
+/// Default name of the cache file.
+pub const CACHE_FILE_NAME: &str = ".oxlintcache";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct CacheFile {
+    /// Hash of the linter version and the configuration the cache was written with
+    config: u64,
+    /// Keyed by path relative to the current working directory
+    files: BTreeMap<String, CacheEntry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CacheEntry {
+    /// Hash of the file contents and the configuration discovered for the file
+    hash: u64,
+    /// Content hashes of all modules the file depends on, directly or
+    /// indirectly. Only recorded when cross-module rules are enabled.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    dependencies: BTreeMap<PathBuf, u64>,
+    /// Diagnostics of each source in the file, e.g. each `<script>` of a `.vue` file
+    sources: Vec<Vec<CachedDiagnostic>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CachedDiagnostic {
+    message: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    labels: Vec<CachedLabel>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    help: Option<String>,
+    severity: CachedSeverity,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code_scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    code_number: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    fixable: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedLabel {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    label: Option<String>,
+    offset: usize,
+    len: usize,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum CachedSeverity {
+    Advice,
+    Warning,
+    Error,
+}
+
+impl From<&OxcDiagnostic> for CachedDiagnostic {
+    fn from(diagnostic: &OxcDiagnostic) -> Self {
+        let labels = diagnostic.labels.iter().flatten().map(|label| CachedLabel {
+            label: label.label().map(ToString::to_string),
+            offset: label.offset(),
+            len: label.len(),
+        });
+        Self {
+            message: diagnostic.message.to_string(),
+            labels: labels.collect(),
+            help: diagnostic.help.as_ref().map(ToString::to_string),
+            severity: match diagnostic.severity {
+                Severity::Advice => CachedSeverity::Advice,
+                Severity::Warning => CachedSeverity::Warning,
+                Severity::Error => CachedSeverity::Error,
+            },
+            code_scope: diagnostic.code.scope.as_ref().map(ToString::to_string),
+            code_number: diagnostic.code.number.as_ref().map(ToString::to_string),
+            url: diagnostic.url.as_ref().map(ToString::to_string),
+            fixable: diagnostic.fixable,
+        }
+    }
+}
+
+impl From<&CachedDiagnostic> for OxcDiagnostic {
+    fn from(cached: &CachedDiagnostic) -> Self {
+        let severity = match cached.severity {
+            CachedSeverity::Advice => Severity::Advice,
+            CachedSeverity::Warning => Severity::Warning,
+            CachedSeverity::Error => Severity::Error,
+        };
+        let labels = cached
+            .labels
+            .iter()
+            .map(|label| LabeledSpan::new(label.label.clone(), label.offset, label.len));
+        let mut diagnostic = OxcDiagnostic::error(cached.message.clone())
+            .with_severity(severity)
+            .with_labels(labels)
+            .with_fixable(cached.fixable);
+        if let Some(help) = &cached.help {
+            diagnostic = diagnostic.with_help(help.clone());
+        }
+        if let Some(scope) = &cached.code_scope {
+            diagnostic = diagnostic.with_error_code_scope(scope.clone());
+        }
+        if let Some(number) = &cached.code_number {
+            diagnostic = diagnostic.with_error_code_num(number.clone());
+        }
+        if let Some(url) = &cached.url {
+            diagnostic = diagnostic.with_url(url.clone());
+        }
+        diagnostic
+    }
+}
+
+/// Diagnostics of previous runs, replayed for files that did not change.
+///
+/// A cached result is used if the contents of the file, the configuration
+/// discovered for it, and the contents of all modules it depends on are
+/// unchanged. The whole cache is invalidated when the linter version or the
+/// configuration shared by all files changes.
+#[derive(Debug)]
+pub struct LintCache {
+    cwd: Box<Path>,
+    location: PathBuf,
+    /// Hash of the linter version and the shared configuration
+    config: u64,
+    /// Entries read from the cache file, if it was written with the same configuration
+    entries: BTreeMap<String, CacheEntry>,
+    /// Entries of files linted or replayed during this run
+    updated: DashMap<String, CacheEntry>,
+    /// Content hashes of dependencies, computed at most once per run
+    content_hashes: DashMap<PathBuf, Option<u64>>,
+}
+
+impl LintCache {
+    /// Read the cache file at `location`, if it exists.
+    ///
+    /// `config` must describe everything that affects diagnostics and is shared
+    /// by all files, such as the version of the linter, command-line options
+    /// and the configuration file passed explicitly. A cache file that cannot
+    /// be read or was written with a different configuration is ignored.
+    pub fn new<T: Into<Box<Path>>>(cwd: T, location: PathBuf, config: &str) -> Self {
+        let cwd = cwd.into();
+        let location = if location.is_relative() { cwd.join(location) } else { location };
+        let config =
+            stable_hash(env!("CARGO_PKG_VERSION").bytes().chain([0]).chain(config.bytes()));
+        let entries = fs::read_to_string(&location)
+            .ok()
+            .and_then(|json| serde_json::from_str::<CacheFile>(&json).ok())
+            .filter(|cache| cache.config == config)
+            .map(|cache| cache.files)
+            .unwrap_or_default();
+        Self {
+            cwd,
+            location,
+            config,
+            entries,
+            updated: DashMap::default(),
+            content_hashes: DashMap::default(),
+        }
+    }
+
+    pub fn location(&self) -> &Path {
+        &self.location
+    }
+
+    /// Hash of the contents of a file and the hash of the configuration
+    /// discovered for it, see [`DiscoveredConfig::hash`].
+    ///
+    /// [`DiscoveredConfig::hash`]: crate::DiscoveredConfig::hash
+    pub(crate) fn file_hash(source_text: &str, config_hash: Option<u64>) -> u64 {
+        let config_hash = config_hash.map(u64::to_le_bytes).unwrap_or_default();
+        stable_hash(source_text.bytes().chain(config_hash))
+    }
+
+    /// Diagnostics of each source of `path`, if its hash and the contents of
+    /// its dependencies did not change since they were cached.
+    pub(crate) fn get(&self, path: &Path, hash: u64) -> Option<Vec<Vec<Message<'static>>>> {
+        let key = self.key(path);
+        let entry = self.entries.get(&key).filter(|entry| entry.hash == hash)?;
+        let dependencies_unchanged = entry
+            .dependencies
+            .iter()
+            .all(|(dependency, hash)| self.content_hash(dependency) == Some(*hash));
+        if !dependencies_unchanged {
+            return None;
+        }
+
+        self.updated.insert(key, entry.clone());
+        let sources = entry.sources.iter().map(|diagnostics| {
+            diagnostics
+                .iter()
+                .map(|diagnostic| Message::new(OxcDiagnostic::from(diagnostic), None))
+                .collect()
+        });
+        Some(sources.collect())
+    }
+
+    /// Cache the diagnostics of each source of `path`.
+    ///
+    /// `module_record` is given when cross-module rules are enabled, and the
+    /// contents of all modules it depends on are recorded.
+    pub(crate) fn insert(
+        &self,
+        path: &Path,
+        hash: u64,
+        module_record: Option<&ModuleRecord>,
+        sources: &[Vec<OxcDiagnostic>],
+    ) {
+        let dependencies =
+            module_record.map(|record| self.dependencies(record)).unwrap_or_default();
+        let sources = sources
+            .iter()
+            .map(|diagnostics| diagnostics.iter().map(CachedDiagnostic::from).collect())
+            .collect();
+        self.updated.insert(self.key(path), CacheEntry { hash, dependencies, sources });
+    }
+
+    /// Write the cache file.
+    ///
+    /// Entries of files that were not linted during this run are kept as long
+    /// as the files exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the cache cannot be serialized or the file cannot be written.
+    pub fn save(&self) -> Result<(), OxcDiagnostic> {
+        let mut files = self.entries.clone();
+        files.retain(|file, _| self.cwd.join(file).is_file());
+        for entry in &self.updated {
+            files.insert(entry.key().clone(), entry.value().clone());
+        }
+
+        write_json(&self.location, &CacheFile { config: self.config, files }, false, "cache")
+    }
+
+    fn key(&self, path: &Path) -> String {
+        path.strip_prefix(&self.cwd).unwrap_or(path).to_string_lossy().into_owned()
+    }
+
+    /// Content hashes of all modules `module_record` depends on, directly or indirectly.
+    fn dependencies(&self, module_record: &ModuleRecord) -> BTreeMap<PathBuf, u64> {
+        let loaded_modules = |record: &ModuleRecord| {
+            record.loaded_modules.iter().map(|entry| Arc::clone(entry.value())).collect::<Vec<_>>()
+        };
+
+        let mut dependencies = BTreeMap::new();
+        let mut stack = loaded_modules(module_record);
+        while let Some(record) = stack.pop() {
+            let path = &record.resolved_absolute_path;
+            if *path == module_record.resolved_absolute_path || dependencies.contains_key(path) {
+                continue;
+            }
+            let Some(hash) = self.content_hash(path) else {
+                continue;
+            };
+            dependencies.insert(path.clone(), hash);
+            stack.extend(loaded_modules(&record));
+        }
+        dependencies
+    }
+
+    fn content_hash(&self, path: &Path) -> Option<u64> {
+        if let Some(hash) = self.content_hashes.get(path) {
+            return *hash;
+        }
+        let hash = fs::read(path).ok().map(stable_hash);
+        self.content_hashes.insert(path.to_path_buf(), hash);
+        hash
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::{Path, PathBuf};
+
+    use oxc_diagnostics::{OxcDiagnostic, Severity};
+    use oxc_span::Span;
+
+    use super::{CacheEntry, CachedDiagnostic, LintCache};
+
+    #[test]
+    fn test_diagnostic_round_trip() {
+        let diagnostic = OxcDiagnostic::warn("`debugger` statement is not allowed")
+            .with_help("Delete this code.")
+            .with_error_code("eslint", "no-debugger")
+            .with_url("https://oxc.rs/docs/guide/usage/linter/rules/eslint/no-debugger.html")
+            .with_label(Span::new(2, 11));
+
+        let cached = serde_json::to_string(&CachedDiagnostic::from(&diagnostic)).unwrap();
+        let cached: CachedDiagnostic = serde_json::from_str(&cached).unwrap();
+        let replayed = OxcDiagnostic::from(&cached);
+
+        assert_eq!(replayed.message, diagnostic.message);
+        assert_eq!(replayed.help, diagnostic.help);
+        assert_eq!(replayed.severity, Severity::Warning);
+        assert_eq!(replayed.code.to_string(), "eslint(no-debugger)");
+        assert_eq!(replayed.url, diagnostic.url);
+        assert_eq!(replayed.labels, diagnostic.labels);
+    }
+
+    #[test]
+    fn test_get() {
+        let cwd = Path::new("/project");
+        let path = cwd.join("index.js");
+        let mut cache = LintCache::new(cwd, PathBuf::from("cache"), "");
+        let hash = LintCache::file_hash("debugger;", None);
+        let diagnostic = OxcDiagnostic::warn("`debugger` statement is not allowed");
+        cache.entries.insert(
+            "index.js".to_string(),
+            CacheEntry {
+                hash,
+                dependencies: [(PathBuf::from("/project/does-not-exist.js"), 0)].into(),
+                sources: vec![vec![CachedDiagnostic::from(&diagnostic)]],
+            },
+        );
+
+        // A dependency changed.
+        assert!(cache.get(&path, hash).is_none());
+
+        cache.entries.get_mut("index.js").unwrap().dependencies.clear();
+        assert!(cache.get(&path, LintCache::file_hash("debugger;", Some(1))).is_none());
+        assert!(cache.get(&path, LintCache::file_hash("debugger;;", None)).is_none());
+        let sources = cache.get(&path, hash).unwrap();
+        assert_eq!(sources.len(), 1);
+        assert_eq!(sources[0][0].error.message, diagnostic.message);
+    }
+}
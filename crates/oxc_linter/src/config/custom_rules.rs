@@ -0,0 +1,80 @@
+use std::collections::BTreeMap;
+
+use rustc_hash::FxHashMap;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::rules::OxlintRules;
+use crate::{esquery::Selector, AllowWarnDeny};
+
+/// Plugin name of custom rules in `rules`, e.g. `"custom/no-moment": "error"`.
+pub(crate) const CUSTOM_PLUGIN_NAME: &str = "custom";
+
+/// Rules that report nodes matching an [ESQuery](https://github.com/estools/esquery) selector,
+/// keyed by rule name.
+///
+/// Custom rules are enabled like any other rule, with the `custom` plugin prefix:
+///
+/// ```json
+/// {
+///   "customRules": {
+///     "no-moment": {
+///       "selector": "CallExpression[callee.name='moment']",
+///       "message": "Use `Temporal` instead of `moment`."
+///     }
+///   },
+///   "rules": {
+///     "custom/no-moment": "error"
+///   }
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct OxlintCustomRules(BTreeMap<String, CustomRule>);
+
+/// A rule reporting every node that matches `selector`.
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct CustomRule {
+    /// ESQuery selector of the nodes to report, e.g. `CallExpression[callee.name='moment']`.
+    pub selector: Selector,
+    /// Message of the diagnostic.
+    pub message: String,
+}
+
+/// The custom rules of a configuration, with the severity of those that are enabled.
+#[derive(Debug, Default, Clone)]
+pub(crate) struct CustomRules {
+    declared: OxlintCustomRules,
+    severities: FxHashMap<String, AllowWarnDeny>,
+}
+
+impl CustomRules {
+    pub(crate) fn new(declared: OxlintCustomRules, rules: &OxlintRules) -> Self {
+        let mut custom_rules = Self { declared, severities: FxHashMap::default() };
+        custom_rules.override_rules(rules);
+        custom_rules
+    }
+
+    /// Enable, reconfigure or disable custom rules from `custom/<name>` entries of `rules`.
+    pub(crate) fn override_rules(&mut self, rules: &OxlintRules) {
+        for rule in rules.iter().filter(|rule| rule.plugin_name == CUSTOM_PLUGIN_NAME) {
+            if rule.severity.is_warn_deny() {
+                self.severities.insert(rule.rule_name.clone(), rule.severity);
+            } else {
+                self.severities.remove(&rule.rule_name);
+            }
+        }
+    }
+
+    /// Enabled custom rules, sorted by name.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &CustomRule, AllowWarnDeny)> + '_ {
+        self.declared.0.iter().filter_map(|(name, rule)| {
+            let severity = self.severities.get(name)?;
+            Some((name.as_str(), rule, *severity))
+        })
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.iter().next().is_none()
+    }
+}
@@ -0,0 +1,273 @@
+use std::{borrow::Cow, env, path::Path, sync::Arc};
+
+use dashmap::DashMap;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use oxc_diagnostics::OxcDiagnostic;
+use serde::Deserialize;
+
+use super::{oxlintrc::merge_json, Oxlintrc};
+use crate::utils::stable_hash;
+
+/// Name of the configuration file looked up in each directory.
+pub const CONFIG_FILE_NAME: &str = ".oxlintrc.json";
+
+/// Finds the configuration that applies to each linted file.
+///
+/// Like ESLint's cascading configuration, every `.oxlintrc.json` between a
+/// file and the root of the file system is merged, with files closer to the
+/// linted file taking precedence. The search stops at a configuration file
+/// with `"root": true`.
+///
+/// A configuration passed explicitly (e.g. with `oxlint -c`) takes precedence
+/// over all discovered configuration files.
+///
+/// Globs in `overrides` and `ignorePatterns` are resolved against the directory
+/// of the configuration file declaring them.
+#[derive(Debug, Default)]
+pub struct ConfigDiscovery {
+    /// JSON of the explicitly passed configuration file, without `ignorePatterns`.
+    base: Option<serde_json::Value>,
+    /// Directory of the configuration file declaring each of the `overrides` in `base`.
+    base_override_dirs: Vec<Box<Path>>,
+    /// `ignorePatterns` of the explicitly passed configuration file.
+    base_ignore: Option<Gitignore>,
+    /// Cascaded configuration per directory, or `None` if no configuration
+    /// file was found for it.
+    cache: DashMap<Box<Path>, Option<Arc<CascadedConfig>>>,
+}
+
+#[derive(Debug)]
+struct CascadedConfig {
+    /// Merged JSON of all discovered configuration files, without `base` and
+    /// `ignorePatterns`.
+    json: serde_json::Value,
+    /// Directory of the configuration file declaring each of the `overrides` in `json`.
+    override_dirs: Vec<Box<Path>>,
+    /// `ignorePatterns` of each discovered configuration file.
+    ignores: Vec<Arc<Gitignore>>,
+    config: Arc<DiscoveredConfig>,
+}
+
+/// A configuration resolved by [`ConfigDiscovery`].
+#[derive(Debug)]
+pub struct DiscoveredConfig {
+    dir: Box<Path>,
+    config: Oxlintrc,
+    /// Hash of the merged JSON of `config`
+    hash: u64,
+    ignores: Vec<Arc<Gitignore>>,
+}
+
+impl DiscoveredConfig {
+    /// Directory of the nearest configuration file.
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Whether `path` is ignored by the `ignorePatterns` of the discovered
+    /// configuration files.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.ignores.iter().any(|ignore| ConfigDiscovery::matches_ignore(ignore, path, is_dir))
+    }
+
+    /// The merged configuration.
+    pub fn config(&self) -> &Oxlintrc {
+        &self.config
+    }
+
+    /// Hash of the merged configuration, which is stable across runs.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+}
+
+impl ConfigDiscovery {
+    /// # Errors
+    ///
+    /// Returns `Err` if `config_path` is given and cannot be parsed, or its
+    /// `ignorePatterns` are invalid.
+    pub fn new(config_path: Option<&Path>) -> Result<Self, OxcDiagnostic> {
+        let Some(config_path) = config_path else { return Ok(Self::default()) };
+        let (mut base, base_override_dirs) = Oxlintrc::read_json(config_path)?;
+        let base_ignore = match base.as_object_mut().and_then(|base| base.remove("ignorePatterns"))
+        {
+            Some(patterns) => {
+                // Matched against the absolute paths of linted files, see `is_ignored`.
+                let config_path = env::current_dir()
+                    .map_or_else(|_| config_path.to_path_buf(), |cwd| cwd.join(config_path));
+                let dir = config_path.parent().unwrap_or_else(|| Path::new(""));
+                Some(Self::build_ignore(dir, &config_path, patterns)?)
+            }
+            None => None,
+        };
+        Ok(Self { base: Some(base), base_override_dirs, base_ignore, cache: DashMap::default() })
+    }
+
+    /// Get the configuration for files in `dir`, or `None` if there is no
+    /// configuration file in `dir` or any of its parents.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if a discovered configuration file cannot be parsed.
+    pub fn config_for(&self, dir: &Path) -> Result<Option<Arc<DiscoveredConfig>>, OxcDiagnostic> {
+        Ok(self.cascade(dir)?.map(|cascaded| Arc::clone(&cascaded.config)))
+    }
+
+    /// Hash of the explicitly passed configuration, including the files it
+    /// extends, which is stable across runs.
+    pub fn base_hash(&self) -> Option<u64> {
+        self.base.as_ref().map(|base| stable_hash(base.to_string().into_bytes()))
+    }
+
+    /// Whether the absolute `path` is ignored by the `ignorePatterns` of the
+    /// explicitly passed configuration or of the configuration files
+    /// discovered for it.
+    ///
+    /// Configuration files that cannot be parsed are reported when the files
+    /// next to them are linted.
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.base_ignore.as_ref().is_some_and(|ignore| Self::matches_ignore(ignore, path, is_dir))
+            || path
+                .parent()
+                .and_then(|dir| self.config_for(dir).ok().flatten())
+                .is_some_and(|discovered| discovered.is_ignored(path, is_dir))
+    }
+
+    /// Forget all discovered configuration files, e.g. after one of them changed.
+    pub fn clear(&self) {
+        self.cache.clear();
+    }
+
+    fn cascade(&self, dir: &Path) -> Result<Option<Arc<CascadedConfig>>, OxcDiagnostic> {
+        if let Some(cached) = self.cache.get(dir) {
+            return Ok(cached.value().clone());
+        }
+
+        let config_path = dir.join(CONFIG_FILE_NAME);
+        let cascaded = if config_path.is_file() {
+            Some(Arc::new(self.load(dir, &config_path)?))
+        } else {
+            match dir.parent() {
+                Some(parent) => self.cascade(parent)?,
+                None => None,
+            }
+        };
+
+        self.cache.insert(dir.into(), cascaded.clone());
+        Ok(cascaded)
+    }
+
+    fn load(&self, dir: &Path, config_path: &Path) -> Result<CascadedConfig, OxcDiagnostic> {
+        let (mut json, own_override_dirs) = Oxlintrc::read_json(config_path)?;
+        let Some(config) = json.as_object_mut() else {
+            return Err(OxcDiagnostic::error(format!(
+                "Failed to parse config {config_path:?}: expected an object"
+            )));
+        };
+        let is_root = config.get("root").and_then(serde_json::Value::as_bool).unwrap_or(false);
+        let ignore = config
+            .remove("ignorePatterns")
+            .map(|patterns| Self::build_ignore(dir, config_path, patterns))
+            .transpose()?;
+
+        let parent = match dir.parent() {
+            Some(parent) if !is_root => self.cascade(parent)?,
+            _ => None,
+        };
+        let (json, mut override_dirs, mut ignores) = match parent {
+            Some(parent) => {
+                let mut merged = parent.json.clone();
+                merge_json(&mut merged, json);
+                (merged, parent.override_dirs.clone(), parent.ignores.clone())
+            }
+            None => (json, vec![], vec![]),
+        };
+        override_dirs.extend(own_override_dirs);
+        ignores.extend(ignore.map(Arc::new));
+
+        let merged = match &self.base {
+            Some(base) => {
+                let mut merged = json.clone();
+                merge_json(&mut merged, base.clone());
+                Cow::Owned(merged)
+            }
+            None => Cow::Borrowed(&json),
+        };
+        let mut config = Oxlintrc::from_json(&merged)
+            .map_err(|err| err.with_help(format!("Discovered config: {config_path:?}")))?;
+        // The `overrides` of `base` come last.
+        config.overrides.set_dirs(override_dirs.iter().chain(&self.base_override_dirs).cloned());
+        let hash = stable_hash(merged.to_string().into_bytes());
+
+        let config =
+            Arc::new(DiscoveredConfig { dir: dir.into(), config, hash, ignores: ignores.clone() });
+        Ok(CascadedConfig { json, override_dirs, ignores, config })
+    }
+
+    fn matches_ignore(ignore: &Gitignore, path: &Path, is_dir: bool) -> bool {
+        path.starts_with(ignore.path())
+            && ignore.matched_path_or_any_parents(path, is_dir).is_ignore()
+    }
+
+    fn build_ignore(
+        dir: &Path,
+        config_path: &Path,
+        patterns: serde_json::Value,
+    ) -> Result<Gitignore, OxcDiagnostic> {
+        let error = |err: &dyn std::fmt::Display| {
+            OxcDiagnostic::error(format!(
+                "Failed to parse `ignorePatterns` in config {config_path:?}: {err}"
+            ))
+        };
+        let patterns = Vec::<String>::deserialize(patterns).map_err(|err| error(&err))?;
+        let mut builder = GitignoreBuilder::new(dir);
+        for pattern in &patterns {
+            builder.add_line(None, pattern).map_err(|err| error(&err))?;
+        }
+        builder.build().map_err(|err| error(&err))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::env;
+
+    use super::ConfigDiscovery;
+
+    #[test]
+    fn test_discovery() {
+        let fixtures = env::current_dir().unwrap().join("fixtures/config_discovery");
+        let discovery = ConfigDiscovery::new(None).unwrap();
+
+        // `nested/.oxlintrc.json` extends `base.json` and is merged on top of `.oxlintrc.json`.
+        let nested = discovery.config_for(&fixtures.join("nested/src")).unwrap().unwrap();
+        assert_eq!(nested.dir(), fixtures.join("nested"));
+        let config = nested.config();
+        assert!(config.env.contains("browser"));
+        assert!(config.env.contains("node"));
+        assert!(config.globals.is_enabled("fromBase"));
+        let rule_names = config.rules.iter().map(|r| r.rule_name.as_str()).collect::<Vec<_>>();
+        assert!(rule_names.contains(&"no-console"));
+        assert!(rule_names.contains(&"no-debugger"));
+        assert!(rule_names.contains(&"eqeqeq"));
+
+        // `root: true` stops the search.
+        let root = discovery.config_for(&fixtures.join("root")).unwrap().unwrap();
+        assert!(!root.config().env.contains("node"));
+        let rule_names =
+            root.config().rules.iter().map(|r| r.rule_name.as_str()).collect::<Vec<_>>();
+        assert_eq!(rule_names, vec!["no-empty"]);
+    }
+
+    #[test]
+    fn test_discovery_with_base() {
+        let fixtures = env::current_dir().unwrap().join("fixtures/config_discovery");
+        let discovery = ConfigDiscovery::new(Some(&fixtures.join("cli_config.json"))).unwrap();
+
+        // The explicitly passed config takes precedence.
+        let config = discovery.config_for(&fixtures.join("nested")).unwrap().unwrap();
+        let no_console =
+            config.config().rules.iter().find(|r| r.rule_name == "no-console").unwrap();
+        assert!(no_console.severity.is_allow());
+    }
+}
@@ -26,6 +26,13 @@ impl OxlintEnv {
         // Filter out false values
         self.0.iter().filter_map(|(k, v)| (*v).then_some(k.as_str()))
     }
+
+    /// Enable or disable environments from `other`, leaving unrelated ones alone.
+    pub(crate) fn override_envs(&mut self, other: &Self) {
+        for (env, enabled) in &other.0 {
+            self.0.insert(env.clone(), *enabled);
+        }
+    }
 }
 
 impl FromIterator<String> for OxlintEnv {
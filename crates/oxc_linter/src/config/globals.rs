@@ -29,7 +29,7 @@ use serde::{de::Visitor, Deserialize, Serialize};
 /// You may also use `"readable"` or `false` to represent `"readonly"`, and
 /// `"writeable"` or `true` to represent `"writable"`.
 // <https://eslint.org/docs/v8.x/use/configure/language-options#using-configuration-files-1>
-#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema)]
 pub struct OxlintGlobals(FxHashMap<String, GlobalValue>);
 impl OxlintGlobals {
     pub fn is_enabled<Q>(&self, name: &Q) -> bool
@@ -39,6 +39,13 @@ impl OxlintGlobals {
     {
         self.0.get(name).is_some_and(|value| *value != GlobalValue::Off)
     }
+
+    /// Add, change or remove globals from `other`, leaving unrelated ones alone.
+    pub(crate) fn override_globals(&mut self, other: &Self) {
+        for (name, value) in &other.0 {
+            self.0.insert(name.clone(), *value);
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
@@ -0,0 +1,459 @@
+use oxc_diagnostics::OxcDiagnostic;
+use serde_json::{Map, Value};
+
+use super::rules::{parse_rule_key, transform_rule_and_plugin_name};
+use crate::{options::LintPlugins, rules::RULES, AllowWarnDeny};
+
+/// An ESLint v8 configuration (`.eslintrc.*` or `package.json#eslintConfig`)
+/// translated into the `.oxlintrc.json` format.
+///
+/// Rules from well-known shareable presets in `extends` (e.g. `eslint:recommended`)
+/// are expanded, since oxlint cannot load ESLint presets.
+#[derive(Debug, Default)]
+#[non_exhaustive]
+pub struct EslintMigration {
+    /// JSON of the migrated `.oxlintrc.json`.
+    pub config: Value,
+    /// Enabled ESLint rules that oxlint does not implement, e.g. `import/no-unresolved`.
+    pub unsupported_rules: Vec<String>,
+    /// Parts of the ESLint configuration that could not be migrated.
+    pub skipped: Vec<String>,
+}
+
+impl EslintMigration {
+    /// # Errors
+    ///
+    /// Returns `Err` if `eslintrc` is not an object.
+    pub fn from_eslintrc(eslintrc: &Value) -> Result<Self, OxcDiagnostic> {
+        let Some(eslintrc) = eslintrc.as_object() else {
+            return Err(OxcDiagnostic::error(
+                "Failed to migrate ESLint config: expected an object",
+            ));
+        };
+
+        let mut migration = Self::default();
+        let config = migration.migrate(eslintrc, false);
+        migration.config = Value::Object(config);
+        migration.unsupported_rules.sort_unstable();
+        Ok(migration)
+    }
+
+    fn migrate(&mut self, eslintrc: &Map<String, Value>, in_override: bool) -> Map<String, Value> {
+        let mut config = Map::new();
+        let mut rules = Map::new();
+        let mut plugins = LintPlugins::empty();
+
+        for preset in string_or_array(eslintrc.get("extends")) {
+            match preset_rules(preset) {
+                Some(preset_rules) => {
+                    for (name, severity) in preset_rules {
+                        rules.insert((*name).to_string(), Value::from(*severity));
+                    }
+                }
+                None => self.skip(format!(
+                    "`extends`: {preset:?} is not a known preset, its rules were not migrated"
+                )),
+            }
+        }
+        if let Some(Value::Object(own_rules)) = eslintrc.get("rules") {
+            rules.extend(own_rules.clone());
+        }
+
+        for plugin in string_or_array(eslintrc.get("plugins")) {
+            match eslint_plugin(plugin) {
+                Some(plugin) => plugins |= plugin,
+                None => self.skip(format!("`plugins`: {plugin:?} is not supported")),
+            }
+        }
+
+        let mut migrated_rules = Map::new();
+        for (name, value) in rules {
+            let severity = match value.as_array() {
+                Some(array) => array.first().map(AllowWarnDeny::try_from),
+                None => Some(AllowWarnDeny::try_from(&value)),
+            };
+            let Some(Ok(severity)) = severity else {
+                self.skip(format!("`rules`: {name:?} has an invalid severity"));
+                continue;
+            };
+            match find_rule_plugin(&name) {
+                Some(plugin) => {
+                    if severity.is_warn_deny() {
+                        plugins |= plugin;
+                    }
+                    migrated_rules.insert(name, value);
+                }
+                None if severity.is_warn_deny() => {
+                    if !self.unsupported_rules.contains(&name) {
+                        self.unsupported_rules.push(name);
+                    }
+                }
+                None => {}
+            }
+        }
+
+        for (key, value) in eslintrc {
+            match key.as_str() {
+                "extends" | "plugins" | "rules" => {}
+                "root" if !in_override => {
+                    config.insert(key.clone(), value.clone());
+                }
+                "env" | "globals" => {
+                    config.insert(key.clone(), value.clone());
+                }
+                "files" | "excludedFiles" if in_override => {
+                    config.insert(key.clone(), value.clone());
+                }
+                "ignorePatterns" if !in_override => {
+                    let patterns = string_or_array(Some(value)).collect::<Vec<_>>();
+                    config.insert("ignorePatterns".to_string(), Value::from(patterns));
+                }
+                "settings" => {
+                    if let Some(settings) = self.migrate_settings(value) {
+                        config.insert(key.clone(), settings);
+                    }
+                }
+                "overrides" if !in_override => {
+                    let overrides = value
+                        .as_array()
+                        .into_iter()
+                        .flatten()
+                        .filter_map(Value::as_object)
+                        .map(|config| Value::Object(self.migrate(config, true)))
+                        .collect::<Vec<_>>();
+                    config.insert(key.clone(), Value::from(overrides));
+                }
+                _ => self.skip(format!("`{key}` is not supported")),
+            }
+        }
+
+        if !plugins.is_empty() {
+            let plugins = plugins.iter().map(<&str>::from).collect::<Vec<_>>();
+            config.insert("plugins".to_string(), Value::from(plugins));
+        }
+        if !migrated_rules.is_empty() || in_override {
+            config.insert("rules".to_string(), Value::Object(migrated_rules));
+        }
+        config
+    }
+
+    /// Keep settings of plugins oxlint implements.
+    fn migrate_settings(&mut self, settings: &Value) -> Option<Value> {
+        let settings = settings.as_object()?;
+        let mut migrated = Map::new();
+        for (key, value) in settings {
+            if matches!(key.as_str(), "jsx-a11y" | "next" | "react" | "jsdoc") {
+                migrated.insert(key.clone(), value.clone());
+            } else {
+                self.skip(format!("`settings`: {key:?} is not supported"));
+            }
+        }
+        (!migrated.is_empty()).then_some(Value::Object(migrated))
+    }
+
+    fn skip(&mut self, message: String) {
+        if !self.skipped.contains(&message) {
+            self.skipped.push(message);
+        }
+    }
+}
+
+fn string_or_array(value: Option<&Value>) -> impl Iterator<Item = &str> {
+    let values = match value {
+        Some(Value::Array(values)) => values.as_slice(),
+        Some(value) => std::slice::from_ref(value),
+        None => &[],
+    };
+    values.iter().filter_map(Value::as_str)
+}
+
+/// The oxlint plugin implementing an ESLint rule, or `None` if oxlint does not
+/// implement the rule. Core ESLint rules belong to [`LintPlugins::ESLINT`].
+fn find_rule_plugin(key: &str) -> Option<LintPlugins> {
+    let (plugin_name, rule_name) = parse_rule_key(key);
+    let (rule_name, plugin_name) = transform_rule_and_plugin_name(&rule_name, &plugin_name);
+    RULES
+        .iter()
+        .any(|rule| rule.name() == rule_name && rule.plugin_name() == plugin_name)
+        .then(|| LintPlugins::from(plugin_name))
+}
+
+/// Map the name of an ESLint plugin in `plugins` to the oxlint plugin.
+fn eslint_plugin(name: &str) -> Option<LintPlugins> {
+    let name = name.strip_prefix("eslint-plugin-").unwrap_or(name);
+    let plugin = match name {
+        "@next/next" | "@next/eslint-plugin-next" | "next" => LintPlugins::NEXTJS,
+        "@typescript-eslint/eslint-plugin" => LintPlugins::TYPESCRIPT,
+        "n" => LintPlugins::NODE,
+        name => LintPlugins::from(name),
+    };
+    (!plugin.is_empty()).then_some(plugin)
+}
+
+type PresetRules = &'static [(&'static str, &'static str)];
+
+/// Rules enabled by a shareable preset in `extends`, or `None` for unknown presets.
+fn preset_rules(name: &str) -> Option<PresetRules> {
+    let rules = match name {
+        "eslint:recommended" => ESLINT_RECOMMENDED,
+        "plugin:@typescript-eslint/recommended" => TYPESCRIPT_RECOMMENDED,
+        "plugin:@typescript-eslint/eslint-recommended" => TYPESCRIPT_ESLINT_RECOMMENDED,
+        "plugin:react/recommended" => REACT_RECOMMENDED,
+        "plugin:react/jsx-runtime" => REACT_JSX_RUNTIME,
+        "plugin:react-hooks/recommended" => REACT_HOOKS_RECOMMENDED,
+        // Only turn off formatting rules, which oxlint does not implement.
+        "prettier" | "plugin:prettier/recommended" => &[],
+        _ => return None,
+    };
+    Some(rules)
+}
+
+// <https://github.com/eslint/eslint/blob/v8.57.0/packages/js/src/configs/eslint-recommended.js>
+const ESLINT_RECOMMENDED: PresetRules = &[
+    ("constructor-super", "error"),
+    ("for-direction", "error"),
+    ("getter-return", "error"),
+    ("no-async-promise-executor", "error"),
+    ("no-case-declarations", "error"),
+    ("no-class-assign", "error"),
+    ("no-compare-neg-zero", "error"),
+    ("no-cond-assign", "error"),
+    ("no-const-assign", "error"),
+    ("no-constant-condition", "error"),
+    ("no-control-regex", "error"),
+    ("no-debugger", "error"),
+    ("no-delete-var", "error"),
+    ("no-dupe-args", "error"),
+    ("no-dupe-class-members", "error"),
+    ("no-dupe-else-if", "error"),
+    ("no-dupe-keys", "error"),
+    ("no-duplicate-case", "error"),
+    ("no-empty", "error"),
+    ("no-empty-character-class", "error"),
+    ("no-empty-pattern", "error"),
+    ("no-ex-assign", "error"),
+    ("no-extra-boolean-cast", "error"),
+    ("no-extra-semi", "error"),
+    ("no-fallthrough", "error"),
+    ("no-func-assign", "error"),
+    ("no-global-assign", "error"),
+    ("no-import-assign", "error"),
+    ("no-inner-declarations", "error"),
+    ("no-invalid-regexp", "error"),
+    ("no-irregular-whitespace", "error"),
+    ("no-loss-of-precision", "error"),
+    ("no-misleading-character-class", "error"),
+    ("no-mixed-spaces-and-tabs", "error"),
+    ("no-new-symbol", "error"),
+    ("no-nonoctal-decimal-escape", "error"),
+    ("no-obj-calls", "error"),
+    ("no-octal", "error"),
+    ("no-prototype-builtins", "error"),
+    ("no-redeclare", "error"),
+    ("no-regex-spaces", "error"),
+    ("no-self-assign", "error"),
+    ("no-setter-return", "error"),
+    ("no-shadow-restricted-names", "error"),
+    ("no-sparse-arrays", "error"),
+    ("no-this-before-super", "error"),
+    ("no-undef", "error"),
+    ("no-unexpected-multiline", "error"),
+    ("no-unreachable", "error"),
+    ("no-unsafe-finally", "error"),
+    ("no-unsafe-negation", "error"),
+    ("no-unsafe-optional-chaining", "error"),
+    ("no-unused-labels", "error"),
+    ("no-unused-vars", "error"),
+    ("no-useless-backreference", "error"),
+    ("no-useless-catch", "error"),
+    ("no-useless-escape", "error"),
+    ("no-with", "error"),
+    ("require-yield", "error"),
+    ("use-isnan", "error"),
+    ("valid-typeof", "error"),
+];
+
+// <https://github.com/typescript-eslint/typescript-eslint/blob/v7.18.0/packages/eslint-plugin/src/configs/eslint-recommended-raw.ts>
+const TYPESCRIPT_ESLINT_RECOMMENDED: PresetRules = &[
+    ("constructor-super", "off"),
+    ("getter-return", "off"),
+    ("no-const-assign", "off"),
+    ("no-dupe-args", "off"),
+    ("no-dupe-class-members", "off"),
+    ("no-dupe-keys", "off"),
+    ("no-func-assign", "off"),
+    ("no-import-assign", "off"),
+    ("no-new-symbol", "off"),
+    ("no-obj-calls", "off"),
+    ("no-redeclare", "off"),
+    ("no-setter-return", "off"),
+    ("no-this-before-super", "off"),
+    ("no-undef", "off"),
+    ("no-unreachable", "off"),
+    ("no-unsafe-negation", "off"),
+    ("no-var", "error"),
+    ("prefer-const", "error"),
+    ("prefer-rest-params", "error"),
+    ("prefer-spread", "error"),
+];
+
+// <https://github.com/typescript-eslint/typescript-eslint/blob/v7.18.0/packages/eslint-plugin/src/configs/recommended.ts>
+const TYPESCRIPT_RECOMMENDED: PresetRules = &[
+    ("constructor-super", "off"),
+    ("getter-return", "off"),
+    ("no-const-assign", "off"),
+    ("no-dupe-args", "off"),
+    ("no-dupe-class-members", "off"),
+    ("no-dupe-keys", "off"),
+    ("no-func-assign", "off"),
+    ("no-import-assign", "off"),
+    ("no-new-symbol", "off"),
+    ("no-obj-calls", "off"),
+    ("no-redeclare", "off"),
+    ("no-setter-return", "off"),
+    ("no-this-before-super", "off"),
+    ("no-undef", "off"),
+    ("no-unreachable", "off"),
+    ("no-unsafe-negation", "off"),
+    ("no-var", "error"),
+    ("prefer-const", "error"),
+    ("prefer-rest-params", "error"),
+    ("prefer-spread", "error"),
+    ("@typescript-eslint/ban-ts-comment", "error"),
+    ("@typescript-eslint/ban-types", "error"),
+    ("no-array-constructor", "off"),
+    ("@typescript-eslint/no-array-constructor", "error"),
+    ("@typescript-eslint/no-duplicate-enum-values", "error"),
+    ("@typescript-eslint/no-explicit-any", "error"),
+    ("@typescript-eslint/no-extra-non-null-assertion", "error"),
+    ("no-loss-of-precision", "off"),
+    ("@typescript-eslint/no-loss-of-precision", "error"),
+    ("@typescript-eslint/no-misused-new", "error"),
+    ("@typescript-eslint/no-namespace", "error"),
+    ("@typescript-eslint/no-non-null-asserted-optional-chain", "error"),
+    ("@typescript-eslint/no-this-alias", "error"),
+    ("@typescript-eslint/no-unnecessary-type-constraint", "error"),
+    ("@typescript-eslint/no-unsafe-declaration-merging", "error"),
+    ("no-unused-vars", "off"),
+    ("@typescript-eslint/no-unused-vars", "error"),
+    ("@typescript-eslint/no-var-requires", "error"),
+    ("@typescript-eslint/prefer-as-const", "error"),
+    ("@typescript-eslint/triple-slash-reference", "error"),
+];
+
+// <https://github.com/jsx-eslint/eslint-plugin-react/blob/v7.35.0/index.js>
+const REACT_RECOMMENDED: PresetRules = &[
+    ("react/display-name", "error"),
+    ("react/jsx-key", "error"),
+    ("react/jsx-no-comment-textnodes", "error"),
+    ("react/jsx-no-duplicate-props", "error"),
+    ("react/jsx-no-target-blank", "error"),
+    ("react/jsx-no-undef", "error"),
+    ("react/jsx-uses-react", "error"),
+    ("react/jsx-uses-vars", "error"),
+    ("react/no-children-prop", "error"),
+    ("react/no-danger-with-children", "error"),
+    ("react/no-deprecated", "error"),
+    ("react/no-direct-mutation-state", "error"),
+    ("react/no-find-dom-node", "error"),
+    ("react/no-is-mounted", "error"),
+    ("react/no-render-return-value", "error"),
+    ("react/no-string-refs", "error"),
+    ("react/no-unescaped-entities", "error"),
+    ("react/no-unknown-property", "error"),
+    ("react/no-unsafe", "off"),
+    ("react/prop-types", "error"),
+    ("react/react-in-jsx-scope", "error"),
+    ("react/require-render-return", "error"),
+];
+
+const REACT_JSX_RUNTIME: PresetRules =
+    &[("react/react-in-jsx-scope", "off"), ("react/jsx-uses-react", "off")];
+
+// <https://github.com/facebook/react/blob/v18.3.1/packages/eslint-plugin-react-hooks/src/index.js>
+const REACT_HOOKS_RECOMMENDED: PresetRules =
+    &[("react-hooks/rules-of-hooks", "error"), ("react-hooks/exhaustive-deps", "warn")];
+
+#[cfg(test)]
+mod test {
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::EslintMigration;
+    use crate::config::Oxlintrc;
+
+    #[test]
+    fn test_migrate() {
+        let migration = EslintMigration::from_eslintrc(&json!({
+            "root": true,
+            "extends": ["eslint:recommended", "plugin:react/recommended", "airbnb"],
+            "parser": "@typescript-eslint/parser",
+            "plugins": ["jest", "eslint-plugin-unknown"],
+            "env": { "browser": true },
+            "globals": { "foo": "readonly" },
+            "settings": { "react": { "linkComponents": ["Link"] }, "import/resolver": "node" },
+            "ignorePatterns": "dist/",
+            "rules": {
+                "eqeqeq": ["warn", "smart"],
+                "no-debugger": "off",
+                "import/no-unresolved": "error",
+                "some-plugin/off-rule": "off",
+                "@typescript-eslint/no-explicit-any": 1
+            },
+            "overrides": [
+                {
+                    "files": ["*.test.js"],
+                    "extends": ["plugin:react-hooks/recommended"],
+                    "env": { "jest": true },
+                    "rules": { "jest/no-disabled-tests": "error" }
+                }
+            ]
+        }))
+        .unwrap();
+
+        let config = &migration.config;
+        assert_eq!(config["root"], json!(true));
+        assert_eq!(config["env"], json!({ "browser": true }));
+        assert_eq!(config["globals"], json!({ "foo": "readonly" }));
+        assert_eq!(config["settings"], json!({ "react": { "linkComponents": ["Link"] } }));
+        assert_eq!(config["ignorePatterns"], json!(["dist/"]));
+        assert_eq!(config["plugins"], json!(["react", "typescript", "jest"]));
+        assert_eq!(config["rules"]["eqeqeq"], json!(["warn", "smart"]));
+        assert_eq!(config["rules"]["no-debugger"], json!("off"));
+        assert_eq!(config["rules"]["no-undef"], json!("error"));
+        assert_eq!(config["rules"]["react/jsx-key"], json!("error"));
+        assert_eq!(config["rules"]["@typescript-eslint/no-explicit-any"], json!(1));
+        assert!(config["rules"].get("import/no-unresolved").is_none());
+        assert!(config["rules"].get("some-plugin/off-rule").is_none());
+        assert!(config.get("parser").is_none());
+
+        let overrides = &config["overrides"][0];
+        assert_eq!(overrides["files"], json!(["*.test.js"]));
+        assert_eq!(overrides["env"], json!({ "jest": true }));
+        assert_eq!(overrides["rules"]["react-hooks/rules-of-hooks"], json!("error"));
+        assert_eq!(overrides["rules"]["jest/no-disabled-tests"], json!("error"));
+
+        assert!(migration.unsupported_rules.contains(&"import/no-unresolved".to_string()));
+        assert!(migration.unsupported_rules.contains(&"react-hooks/exhaustive-deps".to_string()));
+        assert!(!migration.unsupported_rules.contains(&"some-plugin/off-rule".to_string()));
+        assert!(migration.skipped.iter().any(|skipped| skipped.contains("airbnb")));
+        assert!(migration.skipped.iter().any(|skipped| skipped.contains("`parser`")));
+        assert!(migration.skipped.iter().any(|skipped| skipped.contains("import/resolver")));
+        assert!(migration.skipped.iter().any(|skipped| skipped.contains("eslint-plugin-unknown")));
+
+        // The migrated config is a valid `.oxlintrc.json`.
+        let oxlintrc = Oxlintrc::deserialize(&migration.config).unwrap();
+        assert_eq!(oxlintrc.ignore_patterns, vec!["dist/"]);
+        assert_eq!(oxlintrc.overrides.len(), 1);
+    }
+
+    #[test]
+    fn test_migrate_invalid() {
+        assert!(EslintMigration::from_eslintrc(&json!([])).is_err());
+
+        let migration =
+            EslintMigration::from_eslintrc(&json!({ "rules": { "no-debugger": "on" } })).unwrap();
+        assert!(migration.config.get("rules").is_none());
+        assert_eq!(migration.skipped.len(), 1);
+    }
+}
@@ -1,28 +1,53 @@
+mod custom_rules;
+mod discovery;
 mod env;
 mod globals;
+mod migrate;
+mod overrides;
 mod oxlintrc;
 mod rules;
 mod settings;
 
+use std::sync::Arc;
+
+use crate::{options::LintPlugins, RuleWithSeverity};
+
+pub(crate) use self::custom_rules::{CustomRules, CUSTOM_PLUGIN_NAME};
 pub use self::{
+    discovery::{ConfigDiscovery, DiscoveredConfig, CONFIG_FILE_NAME},
     env::OxlintEnv,
     globals::OxlintGlobals,
+    migrate::EslintMigration,
+    overrides::{GlobSet, OxlintOverride, OxlintOverrides},
     oxlintrc::Oxlintrc,
     settings::{jsdoc::JSDocPluginSettings, OxlintSettings},
 };
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct LintConfig {
     pub(crate) settings: OxlintSettings,
     /// Environments enable and disable collections of global variables.
     pub(crate) env: OxlintEnv,
     /// Enabled or disabled specific global variables.
     pub(crate) globals: OxlintGlobals,
+    /// Custom rules declared in the configuration file, and which of them are enabled.
+    pub(crate) custom_rules: CustomRules,
+}
+
+/// The rules and configuration that apply to a single file, after its
+/// [`DiscoveredConfig`] and all matching [`OxlintOverrides`] have been applied
+/// on top of the base configuration.
+#[derive(Debug)]
+pub(crate) struct ResolvedLinterState {
+    pub(crate) rules: Vec<RuleWithSeverity>,
+    pub(crate) config: Arc<LintConfig>,
+    pub(crate) plugins: LintPlugins,
 }
 
 impl From<Oxlintrc> for LintConfig {
     fn from(config: Oxlintrc) -> Self {
-        Self { settings: config.settings, env: config.env, globals: config.globals }
+        let custom_rules = CustomRules::new(config.custom_rules, &config.rules);
+        Self { settings: config.settings, env: config.env, globals: config.globals, custom_rules }
     }
 }
 
@@ -34,8 +59,8 @@ mod test {
     use rustc_hash::FxHashSet;
     use serde::Deserialize;
 
-    use super::Oxlintrc;
-    use crate::rules::RULES;
+    use super::{CustomRules, Oxlintrc};
+    use crate::{rules::RULES, AllowWarnDeny};
 
     #[test]
     fn test_from_file() {
@@ -86,6 +111,48 @@ mod test {
         assert!(globals.is_enabled("foo"));
     }
 
+    #[test]
+    fn test_custom_rules() {
+        let config = Oxlintrc::deserialize(&serde_json::json!({
+            "customRules": {
+                "no-moment": {
+                    "selector": "CallExpression[callee.name='moment']",
+                    "message": "Use `Temporal` instead."
+                },
+                "no-with": { "selector": "WithStatement", "message": "No `with`." }
+            },
+            "rules": { "custom/no-moment": "error", "custom/no-with": "off" },
+            "overrides": [{ "files": ["*.test.js"], "rules": { "custom/no-with": "warn" } }]
+        }))
+        .unwrap();
+        let mut custom_rules = CustomRules::new(config.custom_rules, &config.rules);
+        let enabled = |custom_rules: &CustomRules| {
+            custom_rules
+                .iter()
+                .map(|(name, _, severity)| (name.to_string(), severity))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(enabled(&custom_rules), [("no-moment".to_string(), AllowWarnDeny::Deny)]);
+
+        custom_rules.override_rules(&config.overrides[0].rules);
+        assert_eq!(
+            enabled(&custom_rules),
+            [
+                ("no-moment".to_string(), AllowWarnDeny::Deny),
+                ("no-with".to_string(), AllowWarnDeny::Warn)
+            ]
+        );
+
+        for custom_rules in [
+            serde_json::json!({ "foo": { "selector": "Identifier[", "message": "" } }),
+            serde_json::json!({ "foo": { "selector": "Identifier" } }),
+            serde_json::json!({ "foo": { "selector": "Identifier", "message": "", "fix": "" } }),
+        ] {
+            let config = Oxlintrc::deserialize(&serde_json::json!({ "customRules": custom_rules }));
+            assert!(config.is_err());
+        }
+    }
+
     #[test]
     fn test_vitest_rule_replace() {
         let fixture_path: std::path::PathBuf =
@@ -0,0 +1,223 @@
+use std::{borrow::Cow, ops::Deref, path::Path};
+
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{env::OxlintEnv, globals::OxlintGlobals, rules::OxlintRules};
+use crate::options::LintPlugins;
+
+/// Configure rules, environments and globals for a subset of files.
+///
+/// Overrides are applied in order, on top of the top-level configuration. When
+/// several overrides match the same file, later ones take precedence.
+///
+/// ```json
+/// {
+///   "overrides": [
+///     {
+///       "files": ["**/*.test.ts", "**/*.spec.ts"],
+///       "plugins": ["jest"],
+///       "rules": { "jest/no-disabled-tests": "error" }
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema)]
+pub struct OxlintOverrides(Vec<OxlintOverride>);
+
+impl Deref for OxlintOverrides {
+    type Target = Vec<OxlintOverride>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl OxlintOverrides {
+    /// Indices of all overrides that apply to `path`, in the order they were
+    /// declared.
+    ///
+    /// `path` should be relative to `dir` (the current working directory).
+    /// Globs of overrides read from a configuration file are resolved against
+    /// the directory of that file, and globs of other overrides against `dir`.
+    pub(crate) fn matching(&self, path: &Path, dir: &Path) -> Vec<usize> {
+        self.0
+            .iter()
+            .enumerate()
+            .filter(|(_, config)| match &config.dir {
+                Some(config_dir) => dir
+                    .join(path)
+                    .strip_prefix(config_dir)
+                    .is_ok_and(|relative_path| config.is_match(relative_path)),
+                None => config.is_match(path),
+            })
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Resolve the globs of the first overrides against `dirs`, the directories
+    /// of the configuration files they were declared in.
+    pub(crate) fn set_dirs<I: IntoIterator<Item = Box<Path>>>(&mut self, dirs: I) {
+        for (config, dir) in self.0.iter_mut().zip(dirs) {
+            config.dir = Some(dir);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+pub struct OxlintOverride {
+    /// A list of glob patterns to apply this override to.
+    pub files: GlobSet,
+    /// A list of glob patterns to exclude from `files`.
+    #[serde(default)]
+    pub excluded_files: GlobSet,
+    /// Additional plugins to enable for matching files.
+    pub plugins: Option<LintPlugins>,
+    /// Environments to enable or disable for matching files.
+    pub env: Option<OxlintEnv>,
+    /// Globals to add or remove for matching files.
+    pub globals: Option<OxlintGlobals>,
+    /// See [Oxlint Rules](https://oxc.rs/docs/guide/usage/linter/rules.html).
+    #[serde(default)]
+    pub rules: OxlintRules,
+    /// Directory of the configuration file declaring this override.
+    #[serde(skip)]
+    pub(crate) dir: Option<Box<Path>>,
+}
+
+impl OxlintOverride {
+    pub fn is_match(&self, path: &Path) -> bool {
+        self.files.is_match(path) && !self.excluded_files.is_match(path)
+    }
+}
+
+/// A set of glob patterns, e.g. `["**/*.test.ts", "scripts/**"]`.
+///
+/// A single pattern may also be given as a string.
+#[derive(Debug, Clone, Default)]
+pub struct GlobSet {
+    patterns: Vec<String>,
+    set: globset::GlobSet,
+}
+
+impl GlobSet {
+    /// # Errors
+    ///
+    /// Returns `Err` if any of the patterns is not a valid glob.
+    pub fn new<S: AsRef<str>>(patterns: &[S]) -> Result<Self, globset::Error> {
+        let mut builder = globset::GlobSetBuilder::new();
+        for pattern in patterns {
+            builder.add(globset::Glob::new(pattern.as_ref())?);
+        }
+        Ok(Self {
+            patterns: patterns.iter().map(|p| p.as_ref().to_string()).collect(),
+            set: builder.build()?,
+        })
+    }
+
+    pub fn is_match(&self, path: &Path) -> bool {
+        self.set.is_match(path)
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+}
+
+impl<'de> Deserialize<'de> for GlobSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum OneOrMany {
+            One(String),
+            Many(Vec<String>),
+        }
+
+        let patterns = match OneOrMany::deserialize(deserializer)? {
+            OneOrMany::One(pattern) => vec![pattern],
+            OneOrMany::Many(patterns) => patterns,
+        };
+        Self::new(&patterns).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for GlobSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.patterns.serialize(serializer)
+    }
+}
+
+impl JsonSchema for GlobSet {
+    fn schema_name() -> String {
+        "GlobSet".to_string()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        Cow::Borrowed("GlobSet")
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        gen.subschema_for::<Vec<String>>()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::OxlintOverrides;
+    use crate::options::LintPlugins;
+
+    #[test]
+    fn test_deserialize() {
+        let overrides = OxlintOverrides::deserialize(&json!([
+            {
+                "files": ["**/*.test.ts"],
+                "plugins": ["jest"],
+                "env": { "jest": true },
+                "rules": { "jest/no-disabled-tests": "error" }
+            },
+            {
+                "files": "scripts/**",
+                "excludedFiles": ["scripts/vendor/**"],
+                "globals": { "foo": "readonly" },
+                "rules": { "no-console": "off" }
+            }
+        ]))
+        .unwrap();
+
+        assert_eq!(overrides.len(), 2);
+        assert_eq!(overrides[0].files.patterns(), ["**/*.test.ts"]);
+        assert!(overrides[0].plugins.is_some_and(LintPlugins::has_jest));
+        assert!(overrides[0].env.as_ref().is_some_and(|env| env.contains("jest")));
+        assert_eq!(overrides[1].files.patterns(), ["scripts/**"]);
+        assert!(overrides[1].globals.as_ref().is_some_and(|g| g.is_enabled("foo")));
+        assert!(overrides[1].plugins.is_none());
+    }
+
+    #[test]
+    fn test_invalid_glob() {
+        assert!(OxlintOverrides::deserialize(&json!([{ "files": ["a/{b"] }])).is_err());
+        assert!(OxlintOverrides::deserialize(&json!([{ "rules": {} }])).is_err());
+    }
+
+    #[test]
+    fn test_matching() {
+        let overrides = OxlintOverrides::deserialize(&json!([
+            { "files": ["*.test.ts"] },
+            { "files": ["scripts/**"], "excludedFiles": "scripts/vendor/**" },
+            { "files": ["**/*.ts"] }
+        ]))
+        .unwrap();
+
+        assert_eq!(overrides.matching(Path::new("src/foo.test.ts"), Path::new("")), vec![0, 2]);
+        assert_eq!(overrides.matching(Path::new("scripts/build.js"), Path::new("")), vec![1]);
+        assert!(overrides.matching(Path::new("scripts/vendor/lib.js"), Path::new("")).is_empty());
+        assert!(overrides.matching(Path::new("src/index.js"), Path::new("")).is_empty());
+    }
+}
@@ -1,10 +1,17 @@
-use std::path::Path;
+use std::{
+    iter,
+    path::{Path, PathBuf},
+};
 
 use oxc_diagnostics::OxcDiagnostic;
+use oxc_resolver::{ResolveOptions, Resolver};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::{env::OxlintEnv, globals::OxlintGlobals, rules::OxlintRules, settings::OxlintSettings};
+use super::{
+    custom_rules::OxlintCustomRules, env::OxlintEnv, globals::OxlintGlobals,
+    overrides::OxlintOverrides, rules::OxlintRules, settings::OxlintSettings,
+};
 
 use crate::{options::LintPlugins, utils::read_to_string};
 
@@ -37,13 +44,27 @@ use crate::{options::LintPlugins, utils::read_to_string};
 ///   "rules": {
 ///     "eqeqeq": "warn",
 ///     "import/no-cycle": "error"
-///   }
+///   },
+///   "overrides": [
+///     {
+///       "files": ["*.test.ts", "*.spec.ts"],
+///       "rules": {
+///         "@typescript-eslint/no-explicit-any": "off"
+///       }
+///     }
+///   ]
 ///  }
 /// ```
-#[derive(Debug, Default, Deserialize, Serialize, JsonSchema)]
-#[serde(default)]
+#[derive(Debug, Default, Clone, Deserialize, Serialize, JsonSchema)]
+#[serde(default, rename_all = "camelCase")]
 #[non_exhaustive]
 pub struct Oxlintrc {
+    /// Paths of configuration files to extend, relative to this file, or names of
+    /// packages in `node_modules` that provide one. Later entries take precedence,
+    /// and this file takes precedence over all of them.
+    pub extends: Vec<String>,
+    /// Stop looking for configuration files in parent directories.
+    pub root: bool,
     pub plugins: LintPlugins,
     /// See [Oxlint Rules](https://oxc.rs/docs/guide/usage/linter/rules.html).
     pub rules: OxlintRules,
@@ -52,13 +73,103 @@ pub struct Oxlintrc {
     pub env: OxlintEnv,
     /// Enabled or disabled specific global variables.
     pub globals: OxlintGlobals,
+    /// Rules that report nodes matching an ESQuery selector. Enable them in `rules`
+    /// with the `custom` prefix, e.g. `"custom/no-moment": "error"`.
+    pub custom_rules: OxlintCustomRules,
+    /// Add, remove, or otherwise reconfigure rules for specific files or groups of files.
+    pub overrides: OxlintOverrides,
+    /// Patterns of files to not lint, in addition to those in `.eslintignore`.
+    ///
+    /// Uses the same syntax as `.gitignore` files and the `--ignore-pattern` option.
+    pub ignore_patterns: Vec<String>,
 }
 
 impl Oxlintrc {
+    /// Load a configuration file, resolving everything it `extends`.
+    ///
     /// # Errors
     ///
     /// * Parse Failure
+    /// * A config in `extends` cannot be found, or configs extend each other in a cycle
     pub fn from_file(path: &Path) -> Result<Self, OxcDiagnostic> {
+        let (json, override_dirs) = Self::read_json(path)?;
+        let mut config = Self::from_json(&json)?;
+        config.overrides.set_dirs(override_dirs);
+        Ok(config)
+    }
+
+    pub(crate) fn from_json(json: &serde_json::Value) -> Result<Self, OxcDiagnostic> {
+        Self::deserialize(json).map_err(|err| {
+            OxcDiagnostic::error(format!("Failed to parse config with error {err:?}"))
+        })
+    }
+
+    /// Read a configuration file as JSON, with all configs in its `extends` merged in.
+    ///
+    /// Also returns the directory of the configuration file declaring each of
+    /// the `overrides`, which their globs are resolved against.
+    pub(crate) fn read_json(
+        path: &Path,
+    ) -> Result<(serde_json::Value, Vec<Box<Path>>), OxcDiagnostic> {
+        Self::read_json_impl(path, &mut vec![])
+    }
+
+    fn read_json_impl(
+        path: &Path,
+        visited: &mut Vec<PathBuf>,
+    ) -> Result<(serde_json::Value, Vec<Box<Path>>), OxcDiagnostic> {
+        let canonical_path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        if visited.contains(&canonical_path) {
+            return Err(OxcDiagnostic::error(format!(
+                "Failed to parse config {path:?}: circular `extends`"
+            )));
+        }
+
+        let mut json = Self::parse_json_file(path)?;
+        let override_count =
+            json.get("overrides").and_then(serde_json::Value::as_array).map_or(0, Vec::len);
+        let config_dir: Box<Path> = canonical_path.parent().unwrap_or_else(|| Path::new("")).into();
+        let own_override_dirs = iter::repeat(config_dir).take(override_count);
+        let Some(extends) = json.as_object_mut().and_then(|config| config.remove("extends")) else {
+            return Ok((json, own_override_dirs.collect()));
+        };
+        let extends = match extends {
+            serde_json::Value::String(specifier) => vec![specifier],
+            extends => Vec::<String>::deserialize(extends).map_err(|err| {
+                OxcDiagnostic::error(format!("Failed to parse `extends` in config {path:?}: {err}"))
+            })?,
+        };
+
+        visited.push(canonical_path);
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        let mut merged = serde_json::Value::Object(serde_json::Map::new());
+        let mut override_dirs = vec![];
+        for specifier in &extends {
+            let extended_path = resolve_extends(dir, specifier).ok_or_else(|| {
+                OxcDiagnostic::error(format!(
+                    "Failed to resolve {specifier:?} in `extends` of config {path:?}"
+                ))
+            })?;
+            let (mut extended, extended_override_dirs) =
+                Self::read_json_impl(&extended_path, visited)?;
+            let Some(extended_config) = extended.as_object_mut() else {
+                return Err(OxcDiagnostic::error(format!(
+                    "Failed to parse config {extended_path:?}: expected an object"
+                )));
+            };
+            // `root` only applies to the config file it is written in.
+            extended_config.remove("root");
+            merge_json(&mut merged, extended);
+            override_dirs.extend(extended_override_dirs);
+        }
+        visited.pop();
+
+        merge_json(&mut merged, json);
+        override_dirs.extend(own_override_dirs);
+        Ok((merged, override_dirs))
+    }
+
+    fn parse_json_file(path: &Path) -> Result<serde_json::Value, OxcDiagnostic> {
         let mut string = read_to_string(path).map_err(|e| {
             OxcDiagnostic::error(format!("Failed to parse config {path:?} with error {e:?}"))
         })?;
@@ -68,7 +179,7 @@ impl Oxlintrc {
             OxcDiagnostic::error(format!("Failed to parse jsonc file {path:?}: {err:?}"))
         })?;
 
-        let json = serde_json::from_str::<serde_json::Value>(&string).map_err(|err| {
+        serde_json::from_str::<serde_json::Value>(&string).map_err(|err| {
             let guess = mime_guess::from_path(path);
             let err = match guess.first() {
                 // syntax error
@@ -81,12 +192,72 @@ impl Oxlintrc {
                 }
             };
             OxcDiagnostic::error(format!("Failed to parse eslint config {path:?}.\n{err}"))
-        })?;
+        })
+    }
+}
 
-        let config = Self::deserialize(&json).map_err(|err| {
-            OxcDiagnostic::error(format!("Failed to parse config with error {err:?}"))
-        })?;
+/// Resolve an entry of `extends`, which is either a path relative to the
+/// extending config or the name of a package in `node_modules`.
+fn resolve_extends(dir: &Path, specifier: &str) -> Option<PathBuf> {
+    if specifier.starts_with('.') || Path::new(specifier).is_absolute() {
+        let path = dir.join(specifier);
+        return path.is_file().then_some(path);
+    }
 
-        Ok(config)
+    let resolver = Resolver::new(ResolveOptions {
+        extensions: vec![".json".into()],
+        main_files: vec![".oxlintrc".into(), "oxlintrc".into(), "index".into()],
+        ..ResolveOptions::default()
+    });
+    resolver.resolve(dir, specifier).ok().map(|resolution| resolution.path().to_path_buf())
+}
+
+/// Merge the JSON of a config file into `base`, with `config` taking precedence.
+///
+/// Rules are replaced one by one, `plugins` are combined, `overrides` are
+/// appended, and everything else is merged recursively.
+pub(crate) fn merge_json(base: &mut serde_json::Value, config: serde_json::Value) {
+    use serde_json::Value;
+
+    let (Value::Object(base), Value::Object(config)) = (base, config) else {
+        // Configs that are not objects are rejected when they are deserialized.
+        return;
+    };
+
+    for (key, value) in config {
+        let Some(existing) = base.get_mut(&key) else {
+            base.insert(key, value);
+            continue;
+        };
+        match (key.as_str(), existing, value) {
+            ("rules", Value::Object(existing), Value::Object(rules)) => existing.extend(rules),
+            ("plugins", Value::Array(existing), Value::Array(plugins)) => {
+                for plugin in plugins {
+                    if !existing.contains(&plugin) {
+                        existing.push(plugin);
+                    }
+                }
+            }
+            ("overrides", Value::Array(existing), Value::Array(overrides)) => {
+                existing.extend(overrides);
+            }
+            (_, existing, value) => merge_value(existing, value),
+        }
+    }
+}
+
+fn merge_value(base: &mut serde_json::Value, value: serde_json::Value) {
+    match (base, value) {
+        (serde_json::Value::Object(base), serde_json::Value::Object(value)) => {
+            for (key, value) in value {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_value(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, value) => *base = value,
     }
 }
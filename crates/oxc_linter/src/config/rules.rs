@@ -122,7 +122,7 @@ impl OxlintRules {
     }
 }
 
-fn transform_rule_and_plugin_name<'a>(
+pub(super) fn transform_rule_and_plugin_name<'a>(
     rule_name: &'a str,
     plugin_name: &'a str,
 ) -> (&'a str, &'a str) {
@@ -225,7 +225,7 @@ impl<'de> Deserialize<'de> for OxlintRules {
     }
 }
 
-fn parse_rule_key(name: &str) -> (String, String) {
+pub(super) fn parse_rule_key(name: &str) -> (String, String) {
     let Some((plugin_name, rule_name)) = name.split_once('/') else {
         return (
             RULES
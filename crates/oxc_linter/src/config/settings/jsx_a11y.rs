@@ -4,7 +4,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 // <https://github.com/jsx-eslint/eslint-plugin-jsx-a11y#configurations>
-#[derive(Debug, Deserialize, Default, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Default, Serialize, JsonSchema)]
 pub struct JSXA11yPluginSettings {
     #[serde(rename = "polymorphicPropName")]
     pub polymorphic_prop_name: Option<CompactStr>,
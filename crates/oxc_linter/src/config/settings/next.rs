@@ -3,7 +3,7 @@ use std::borrow::Cow;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize, Serializer};
 
-#[derive(Debug, Deserialize, Default, Serialize, JsonSchema)]
+#[derive(Debug, Clone, Deserialize, Default, Serialize, JsonSchema)]
 pub struct NextPluginSettings {
     #[serde(default)]
     #[serde(rename = "rootDir")]
@@ -1,13 +1,15 @@
+use oxc_diagnostics::OxcDiagnostic;
 use oxc_semantic::Semantic;
-use oxc_span::SourceType;
+use oxc_span::{GetSpan, SourceType};
 use std::{cell::RefCell, path::Path, rc::Rc, sync::Arc};
 
 use crate::{
-    config::LintConfig,
+    config::{LintConfig, CUSTOM_PLUGIN_NAME},
     disable_directives::{DisableDirectives, DisableDirectivesBuilder},
-    fixer::{FixKind, Message},
+    esquery::SelectorMatcher,
+    fixer::{Fix, FixKind, Message},
     frameworks,
-    options::{LintOptions, LintPlugins},
+    options::{AllowWarnDeny, LintOptions, LintPlugins},
     utils, FrameworkFlags, RuleWithSeverity,
 };
 
@@ -120,6 +122,46 @@ impl<'a> ContextHost<'a> {
         self.diagnostics.borrow_mut().push(diagnostic);
     }
 
+    /// Report nodes matching the selectors of enabled custom rules.
+    ///
+    /// Must be called before [`ContextHost::report_unused_directives`], since
+    /// custom rules can be disabled with directives too.
+    pub fn run_custom_rules(&self) {
+        let custom_rules = &self.config.custom_rules;
+        if custom_rules.is_empty() {
+            return;
+        }
+        let matcher = SelectorMatcher::new(self.semantic.nodes());
+        for node in self.semantic.nodes().iter() {
+            let span = node.kind().span();
+            for (name, rule, severity) in custom_rules.iter() {
+                if !matcher.matches(&rule.selector, node)
+                    || self.disable_directives.contains(name, span)
+                {
+                    continue;
+                }
+                let diagnostic = OxcDiagnostic::warn(rule.message.clone())
+                    .with_label(span)
+                    .with_error_code(CUSTOM_PLUGIN_NAME, name.to_string())
+                    .with_severity(severity.into());
+                self.push_diagnostic(Message::new(diagnostic, None));
+            }
+        }
+    }
+
+    /// Report `eslint-disable` directives that did not suppress any
+    /// diagnostic, with a fix that removes them.
+    ///
+    /// Must be called after all rules have been run.
+    pub fn report_unused_directives(&self, severity: AllowWarnDeny) {
+        let unused = self.disable_directives.unused_directives(self.semantic.source_text());
+        for (diagnostic, fix_span) in unused {
+            let fix = self.fix.can_apply(FixKind::SafeFix).then(|| Fix::delete(fix_span));
+            let diagnostic = diagnostic.with_severity(severity.into()).with_fixable(true);
+            self.push_diagnostic(Message::new(diagnostic, fix));
+        }
+    }
+
     /// Take all diagnostics collected during linting.
     pub fn take_diagnostics(&self) -> Vec<Message<'a>> {
         // NOTE: diagnostics are only ever borrowed here and in push_diagnostic.
@@ -1,14 +1,27 @@
-use oxc_ast::Trivias;
+use std::cell::RefCell;
+
+use oxc_ast::{Comment, Trivias};
+use oxc_diagnostics::OxcDiagnostic;
 use oxc_span::Span;
 use rust_lapper::{Interval, Lapper};
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::rules::RULES;
 
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum DisabledRule<'a> {
     All,
     Single(&'a str),
 }
 
+/// A rule disabled by a directive comment
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct DirectiveRule<'a> {
+    rule: DisabledRule<'a>,
+    /// Span of the comment that disabled the rule
+    comment: Span,
+}
+
 /// A comment which disables one or more specific rules
 #[derive(Debug)]
 pub struct DisableRuleComment<'a> {
@@ -18,24 +31,52 @@ pub struct DisableRuleComment<'a> {
     pub rules: Vec<&'a str>,
 }
 
+/// A disable directive comment, used to find directives that are not needed.
+#[derive(Debug)]
+struct DirectiveComment<'a> {
+    /// Span of the comment, without `//` or `/* */`
+    span: Span,
+    /// Span of the comment, including `//` or `/* */`
+    real_span: Span,
+    /// e.g. `eslint-disable-next-line`
+    directive: &'a str,
+    /// Rules disabled by the comment, or empty if it disables all rules
+    rules: Vec<(&'a str, Span)>,
+}
+
 pub struct DisableDirectives<'a> {
     /// All the disabled rules with their corresponding covering spans
-    intervals: Lapper<u32, DisabledRule<'a>>,
+    intervals: Lapper<u32, DirectiveRule<'a>>,
     /// Spans of comments that disable all rules
     disable_all_comments: Box<[Span]>,
     /// All comments that disable one or more specific rules
     disable_rule_comments: Box<[DisableRuleComment<'a>]>,
+    /// All `eslint-disable` comments, including those that disable all rules
+    directive_comments: Box<[DirectiveComment<'a>]>,
+    /// Disabled rules that suppressed at least one diagnostic
+    used: RefCell<FxHashSet<DirectiveRule<'a>>>,
 }
 
 impl<'a> DisableDirectives<'a> {
-    pub fn contains(&self, rule_name: &'static str, span: Span) -> bool {
-        self.intervals.find(span.start, span.end).any(|interval| {
-            interval.val == DisabledRule::All
+    /// Returns `true` if diagnostics of `rule_name` are disabled at `span`,
+    /// and marks the directives that disable it as used.
+    pub fn contains(&self, rule_name: &str, span: Span) -> bool {
+        let mut used = self.used.borrow_mut();
+        let mut contains = false;
+        for interval in self.intervals.find(span.start, span.end) {
+            let disabled = match interval.val.rule {
+                DisabledRule::All => true,
                 // Our rule name currently does not contain the prefix.
                 // For example, this will match `@typescript-eslint/no-var-requires` given
                 // our rule_name is `no-var-requires`.
-                || matches!(interval.val, DisabledRule::Single(name) if name.contains(rule_name))
-        })
+                DisabledRule::Single(name) => name.contains(rule_name),
+            };
+            if disabled {
+                used.insert(interval.val);
+                contains = true;
+            }
+        }
+        contains
     }
 
     pub fn disable_all_comments(&self) -> &[Span] {
@@ -45,21 +86,133 @@ impl<'a> DisableDirectives<'a> {
     pub fn disable_rule_comments(&self) -> &[DisableRuleComment<'a>] {
         &self.disable_rule_comments
     }
+
+    /// Diagnostics for directives, or rules named in directives, that did not
+    /// suppress any diagnostic, each with the span to delete to remove it.
+    ///
+    /// Should only be called after all rules have been run.
+    pub(crate) fn unused_directives(&self, source_text: &str) -> Vec<(OxcDiagnostic, Span)> {
+        let used = self.used.borrow();
+        let is_used = |comment: &DirectiveComment, rule| {
+            used.contains(&DirectiveRule { rule, comment: comment.span })
+        };
+
+        let mut unused = vec![];
+        for comment in &*self.directive_comments {
+            if comment.rules.is_empty() {
+                if !is_used(comment, DisabledRule::All) {
+                    unused.push((
+                        unused_directive_diagnostic(comment.directive, comment.real_span),
+                        comment_removal_span(source_text, comment.real_span),
+                    ));
+                }
+                continue;
+            }
+
+            let rules = &comment.rules;
+            if rules.iter().all(|(name, _)| !is_used(comment, DisabledRule::Single(name))) {
+                let names = rules.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+                unused.push((
+                    unused_rules_diagnostic(comment.directive, &names, comment.real_span),
+                    comment_removal_span(source_text, comment.real_span),
+                ));
+                continue;
+            }
+
+            // Remove each run of consecutive unused rule names along with the
+            // separating comma, so that fixes for the same comment never overlap.
+            let mut index = 0;
+            while index < rules.len() {
+                if is_used(comment, DisabledRule::Single(rules[index].0)) {
+                    index += 1;
+                    continue;
+                }
+                let first = index;
+                while index < rules.len() && !is_used(comment, DisabledRule::Single(rules[index].0))
+                {
+                    index += 1;
+                }
+                let run = &rules[first..index];
+                let span = Span::new(run[0].1.start, run[run.len() - 1].1.end);
+                let fix_span = match rules.get(index) {
+                    Some((_, next)) => Span::new(span.start, next.start),
+                    // `first > 0` because at least one rule is used
+                    None => Span::new(rules[first - 1].1.end, span.end),
+                };
+                let names = run.iter().map(|(name, _)| *name).collect::<Vec<_>>();
+                unused.push((unused_rules_diagnostic(comment.directive, &names, span), fix_span));
+            }
+        }
+        unused
+    }
+}
+
+fn unused_directive_diagnostic(directive: &str, span: Span) -> OxcDiagnostic {
+    OxcDiagnostic::warn(format!("Unused `{directive}` directive (no problems were reported)."))
+        .with_help("Remove this comment.")
+        .with_label(span)
+}
+
+fn unused_rules_diagnostic(directive: &str, rules: &[&str], span: Span) -> OxcDiagnostic {
+    let names = rules.iter().map(|name| format!("`{name}`")).collect::<Vec<_>>().join(", ");
+    let diagnostic = OxcDiagnostic::warn(format!(
+        "Unused `{directive}` directive (no problems were reported from {names})."
+    ))
+    .with_label(span);
+
+    let unknown = rules
+        .iter()
+        .filter(|name| !is_known_rule(name))
+        .map(|name| format!("`{name}`"))
+        .collect::<Vec<_>>();
+    match unknown.len() {
+        0 => diagnostic,
+        1 => diagnostic.with_help(format!("{} is not a known rule.", unknown[0])),
+        _ => diagnostic.with_help(format!("{} are not known rules.", unknown.join(", "))),
+    }
+}
+
+/// Like [`DisableDirectives::contains`], rules are matched by name without their plugin prefix.
+fn is_known_rule(name: &str) -> bool {
+    let rule_name = name.rsplit('/').next().unwrap_or(name);
+    RULES.iter().any(|rule| rule.name() == rule_name)
+}
+
+/// The span to delete to remove a comment. If nothing else is on its line, the
+/// whole line is removed, and a trailing comment is removed along with the
+/// whitespace before it.
+#[allow(clippy::cast_possible_truncation)] // for `as u32`
+fn comment_removal_span(source_text: &str, span: Span) -> Span {
+    let (start, end) = (span.start as usize, span.end as usize);
+    let line_start = source_text[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source_text[end..].find('\n').map_or(source_text.len(), |i| end + i);
+    let before = &source_text[line_start..start];
+
+    if !source_text[end..line_end].trim().is_empty() {
+        span
+    } else if before.trim().is_empty() {
+        let line_end = if line_end < source_text.len() { line_end + 1 } else { line_end };
+        Span::new(line_start as u32, line_end as u32)
+    } else {
+        Span::new((line_start + before.trim_end().len()) as u32, span.end)
+    }
 }
 
 pub struct DisableDirectivesBuilder<'a> {
     source_text: &'a str,
     trivias: Trivias,
     /// All the disabled rules with their corresponding covering spans
-    intervals: Lapper<u32, DisabledRule<'a>>,
-    /// Start of `eslint-disable` or `oxlint-disable`
-    disable_all_start: Option<u32>,
-    /// Start of `eslint-disable` or `oxlint-disable` rule_name`
-    disable_start_map: FxHashMap<&'a str, u32>,
+    intervals: Lapper<u32, DirectiveRule<'a>>,
+    /// Start of `eslint-disable` or `oxlint-disable`, and the span of the comment
+    disable_all_start: Option<(u32, Span)>,
+    /// Start of `eslint-disable` or `oxlint-disable` rule_name`, and the span of the comment
+    disable_start_map: FxHashMap<&'a str, (u32, Span)>,
     /// Spans of comments that disable all rules
     disable_all_comments: Vec<Span>,
     /// All comments that disable one or more specific rules
     disable_rule_comments: Vec<DisableRuleComment<'a>>,
+    /// All `eslint-disable` comments, including those that disable all rules
+    directive_comments: Vec<DirectiveComment<'a>>,
 }
 
 impl<'a> DisableDirectivesBuilder<'a> {
@@ -72,6 +225,7 @@ impl<'a> DisableDirectivesBuilder<'a> {
             disable_start_map: FxHashMap::default(),
             disable_all_comments: vec![],
             disable_rule_comments: vec![],
+            directive_comments: vec![],
         }
     }
 
@@ -81,11 +235,37 @@ impl<'a> DisableDirectivesBuilder<'a> {
             intervals: self.intervals,
             disable_all_comments: self.disable_all_comments.into_boxed_slice(),
             disable_rule_comments: self.disable_rule_comments.into_boxed_slice(),
+            directive_comments: self.directive_comments.into_boxed_slice(),
+            used: RefCell::default(),
         }
     }
 
-    fn add_interval(&mut self, start: u32, stop: u32, val: DisabledRule<'a>) {
-        self.intervals.insert(Interval { start, stop, val });
+    fn add_interval(&mut self, start: u32, stop: u32, rule: DisabledRule<'a>, comment: Span) {
+        self.intervals.insert(Interval { start, stop, val: DirectiveRule { rule, comment } });
+    }
+
+    /// Record a directive comment. `text` is the comment text after the directive.
+    fn add_directive_comment(&mut self, comment: &Comment, text: &'a str) {
+        let comment_text = comment.span.source_text(self.source_text).trim_start();
+        let directive = comment_text.split(char::is_whitespace).next().unwrap_or_default();
+        let mut rules = vec![];
+        Self::get_rule_names_with_spans(text, Self::offset_of(comment, text), |name, span| {
+            if !name.is_empty() {
+                rules.push((name, span));
+            }
+        });
+        self.directive_comments.push(DirectiveComment {
+            span: comment.span,
+            real_span: comment.real_span(),
+            directive,
+            rules,
+        });
+    }
+
+    /// Offset of `text`, a suffix of the text of `comment`, in the source text.
+    #[allow(clippy::cast_possible_truncation)] // for `as u32`
+    fn offset_of(comment: &Comment, text: &str) -> u32 {
+        comment.span.end - text.len() as u32
     }
 
     #[allow(clippy::cast_possible_truncation)] // for `as u32`
@@ -104,9 +284,10 @@ impl<'a> DisableDirectivesBuilder<'a> {
                 // `eslint-disable`
                 if text.trim().is_empty() {
                     if self.disable_all_start.is_none() {
-                        self.disable_all_start = Some(comment.span.end);
+                        self.disable_all_start = Some((comment.span.end, comment.span));
                     }
                     self.disable_all_comments.push(comment.span);
+                    self.add_directive_comment(comment, text);
                     continue;
                 }
                 // `eslint-disable-next-line`
@@ -117,7 +298,7 @@ impl<'a> DisableDirectivesBuilder<'a> {
                         .take(2)
                         .fold(comment.span.end, |acc, line| acc + line.len() as u32);
                     if text.trim().is_empty() {
-                        self.add_interval(comment.span.end, stop, DisabledRule::All);
+                        self.add_interval(comment.span.end, stop, DisabledRule::All, comment.span);
                         self.disable_all_comments.push(comment.span);
                     } else {
                         // `eslint-disable-next-line rule_name1, rule_name2`
@@ -127,12 +308,14 @@ impl<'a> DisableDirectivesBuilder<'a> {
                                 comment.span.end,
                                 stop,
                                 DisabledRule::Single(rule_name),
+                                comment.span,
                             );
                             rules.push(rule_name);
                         });
                         self.disable_rule_comments
                             .push(DisableRuleComment { span: comment.span, rules });
                     }
+                    self.add_directive_comment(comment, text);
                     continue;
                 }
                 // `eslint-disable-line`
@@ -146,18 +329,24 @@ impl<'a> DisableDirectivesBuilder<'a> {
 
                     // `eslint-disable-line`
                     if text.trim().is_empty() {
-                        self.add_interval(start, stop, DisabledRule::All);
+                        self.add_interval(start, stop, DisabledRule::All, comment.span);
                         self.disable_all_comments.push(comment.span);
                     } else {
                         // `eslint-disable-line rule-name1, rule-name2`
                         let mut rules = vec![];
                         Self::get_rule_names(text, |rule_name| {
-                            self.add_interval(start, stop, DisabledRule::Single(rule_name));
+                            self.add_interval(
+                                start,
+                                stop,
+                                DisabledRule::Single(rule_name),
+                                comment.span,
+                            );
                             rules.push(rule_name);
                         });
                         self.disable_rule_comments
                             .push(DisableRuleComment { span: comment.span, rules });
                     }
+                    self.add_directive_comment(comment, text);
                     continue;
                 }
                 // Remaining text should start with a space, else it's probably a typo of the correct syntax.
@@ -166,11 +355,14 @@ impl<'a> DisableDirectivesBuilder<'a> {
                     // `eslint-disable rule-name1, rule-name2`
                     let mut rules = vec![];
                     Self::get_rule_names(text, |rule_name| {
-                        self.disable_start_map.entry(rule_name).or_insert(comment.span.end);
+                        self.disable_start_map
+                            .entry(rule_name)
+                            .or_insert((comment.span.end, comment.span));
                         rules.push(rule_name);
                     });
                     self.disable_rule_comments
                         .push(DisableRuleComment { span: comment.span, rules });
+                    self.add_directive_comment(comment, text);
                     continue;
                 }
             }
@@ -180,17 +372,25 @@ impl<'a> DisableDirectivesBuilder<'a> {
             {
                 // `eslint-enable`
                 if text.trim().is_empty() {
-                    if let Some(start) = self.disable_all_start.take() {
-                        self.add_interval(start, comment.span.start, DisabledRule::All);
+                    if let Some((start, disable_comment)) = self.disable_all_start.take() {
+                        self.add_interval(
+                            start,
+                            comment.span.start,
+                            DisabledRule::All,
+                            disable_comment,
+                        );
                     }
                 } else {
                     // `eslint-enable rule-name1, rule-name2`
                     Self::get_rule_names(text, |rule_name| {
-                        if let Some(start) = self.disable_start_map.remove(rule_name) {
+                        if let Some((start, disable_comment)) =
+                            self.disable_start_map.remove(rule_name)
+                        {
                             self.add_interval(
                                 start,
                                 comment.span.start,
                                 DisabledRule::Single(rule_name),
+                                disable_comment,
                             );
                         }
                     });
@@ -200,20 +400,35 @@ impl<'a> DisableDirectivesBuilder<'a> {
         }
 
         // Lone `eslint-disable`
-        if let Some(start) = self.disable_all_start {
-            self.add_interval(start, source_len, DisabledRule::All);
+        if let Some((start, comment)) = self.disable_all_start {
+            self.add_interval(start, source_len, DisabledRule::All, comment);
         }
 
         // Lone `eslint-disable rule_name`
         let disable_start_map = self.disable_start_map.drain().collect::<Vec<_>>();
-        for (rule_name, start) in disable_start_map {
-            self.add_interval(start, source_len, DisabledRule::Single(rule_name));
+        for (rule_name, (start, comment)) in disable_start_map {
+            self.add_interval(start, source_len, DisabledRule::Single(rule_name), comment);
         }
     }
 
-    fn get_rule_names<F: FnMut(&'a str)>(text: &'a str, cb: F) {
-        if let Some(text) = text.split_terminator("--").next() {
-            text.split(',').map(str::trim).for_each(cb);
+    fn get_rule_names<F: FnMut(&'a str)>(text: &'a str, mut cb: F) {
+        Self::get_rule_names_with_spans(text, 0, |rule_name, _| cb(rule_name));
+    }
+
+    /// Like [`Self::get_rule_names`], but also passes the span of each rule
+    /// name, given the `offset` of `text` in the source text.
+    #[allow(clippy::cast_possible_truncation)] // for `as u32`
+    fn get_rule_names_with_spans<F: FnMut(&'a str, Span)>(text: &'a str, offset: u32, mut cb: F) {
+        let Some(text) = text.split_terminator("--").next() else {
+            return;
+        };
+        let mut start = offset;
+        for part in text.split(',') {
+            let rule_name = part.trim();
+            let rule_start = start + (part.len() - part.trim_start().len()) as u32;
+            cb(rule_name, Span::new(rule_start, rule_start + rule_name.len() as u32));
+            // Skip past this part and the `,`
+            start += part.len() as u32 + 1;
         }
     }
 }
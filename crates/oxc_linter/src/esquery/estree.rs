@@ -0,0 +1,389 @@
+//! Maps the oxc AST to the [ESTree](https://github.com/estree/estree) shape selectors are
+//! written against.
+//!
+//! Node types come from [`AstKind`]s, with kinds that have no ESTree counterpart (e.g.
+//! [`AstKind::Argument`]) being transparent. Attributes are read from the serialized AST,
+//! normalized where oxc deviates from ESTree.
+
+use cow_utils::CowUtils;
+use oxc_ast::{
+    ast::{ClassType, FunctionType, JSXAttributeItem},
+    AstKind,
+};
+use oxc_semantic::{AstNode, AstNodes};
+use oxc_span::Span;
+use serde_json::{Map, Value};
+
+macro_rules! estree_kinds {
+    ($($kind:ident),* $(,)?) => {
+        fn variant_name(kind: AstKind) -> Option<&'static str> {
+            match kind {
+                $(AstKind::$kind(_) => Some(stringify!($kind)),)*
+                _ => None,
+            }
+        }
+
+        fn serialize(kind: AstKind) -> Option<Value> {
+            match kind {
+                $(AstKind::$kind(it) => serde_json::to_value(it).ok(),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+estree_kinds!(
+    BooleanLiteral,
+    NullLiteral,
+    NumericLiteral,
+    BigIntLiteral,
+    RegExpLiteral,
+    StringLiteral,
+    Program,
+    IdentifierName,
+    IdentifierReference,
+    BindingIdentifier,
+    LabelIdentifier,
+    ThisExpression,
+    ArrayExpression,
+    ObjectExpression,
+    ObjectProperty,
+    TemplateLiteral,
+    TaggedTemplateExpression,
+    MemberExpression,
+    CallExpression,
+    NewExpression,
+    MetaProperty,
+    SpreadElement,
+    UpdateExpression,
+    UnaryExpression,
+    BinaryExpression,
+    PrivateInExpression,
+    LogicalExpression,
+    ConditionalExpression,
+    AssignmentExpression,
+    ArrayAssignmentTarget,
+    ObjectAssignmentTarget,
+    AssignmentTargetWithDefault,
+    SequenceExpression,
+    Super,
+    AwaitExpression,
+    ChainExpression,
+    Directive,
+    BlockStatement,
+    VariableDeclaration,
+    VariableDeclarator,
+    EmptyStatement,
+    ExpressionStatement,
+    IfStatement,
+    DoWhileStatement,
+    WhileStatement,
+    ForStatement,
+    ForInStatement,
+    ForOfStatement,
+    ContinueStatement,
+    BreakStatement,
+    ReturnStatement,
+    WithStatement,
+    SwitchStatement,
+    SwitchCase,
+    LabeledStatement,
+    ThrowStatement,
+    TryStatement,
+    FinallyClause,
+    CatchClause,
+    DebuggerStatement,
+    AssignmentPattern,
+    ObjectPattern,
+    ArrayPattern,
+    BindingRestElement,
+    Function,
+    FunctionBody,
+    ArrowFunctionExpression,
+    YieldExpression,
+    Class,
+    ClassBody,
+    MethodDefinition,
+    PropertyDefinition,
+    PrivateIdentifier,
+    StaticBlock,
+    ImportExpression,
+    ImportDeclaration,
+    ImportSpecifier,
+    ImportDefaultSpecifier,
+    ImportNamespaceSpecifier,
+    ExportNamedDeclaration,
+    ExportDefaultDeclaration,
+    ExportAllDeclaration,
+    ExportSpecifier,
+    TSThisParameter,
+    TSEnumDeclaration,
+    TSEnumMember,
+    TSTypeAnnotation,
+    TSLiteralType,
+    TSConditionalType,
+    TSUnionType,
+    TSIntersectionType,
+    TSParenthesizedType,
+    TSIndexedAccessType,
+    TSNamedTupleMember,
+    TSAnyKeyword,
+    TSStringKeyword,
+    TSBooleanKeyword,
+    TSNumberKeyword,
+    TSNeverKeyword,
+    TSIntrinsicKeyword,
+    TSUnknownKeyword,
+    TSNullKeyword,
+    TSUndefinedKeyword,
+    TSVoidKeyword,
+    TSSymbolKeyword,
+    TSThisType,
+    TSObjectKeyword,
+    TSBigIntKeyword,
+    TSTypeReference,
+    TSQualifiedName,
+    TSTypeParameterInstantiation,
+    TSTypeParameter,
+    TSTypeParameterDeclaration,
+    TSTypeAliasDeclaration,
+    TSClassImplements,
+    TSInterfaceDeclaration,
+    TSPropertySignature,
+    TSMethodSignature,
+    TSConstructSignatureDeclaration,
+    TSInterfaceHeritage,
+    TSModuleDeclaration,
+    TSModuleBlock,
+    TSTypeLiteral,
+    TSInferType,
+    TSTypeQuery,
+    TSImportType,
+    TSMappedType,
+    TSTemplateLiteralType,
+    TSAsExpression,
+    TSSatisfiesExpression,
+    TSTypeAssertion,
+    TSImportEqualsDeclaration,
+    TSExternalModuleReference,
+    TSNonNullExpression,
+    Decorator,
+    TSExportAssignment,
+    TSInstantiationExpression,
+    JSXElement,
+    JSXOpeningElement,
+    JSXClosingElement,
+    JSXFragment,
+    JSXNamespacedName,
+    JSXMemberExpression,
+    JSXExpressionContainer,
+    JSXAttributeItem,
+    JSXSpreadAttribute,
+    JSXIdentifier,
+    JSXText,
+);
+
+/// The ESTree type of `node`, or `None` if it has no ESTree counterpart.
+pub(super) fn node_type(nodes: &AstNodes, node: &AstNode) -> Option<&'static str> {
+    let node_type = match node.kind() {
+        AstKind::BooleanLiteral(_)
+        | AstKind::NullLiteral(_)
+        | AstKind::NumericLiteral(_)
+        | AstKind::BigIntLiteral(_)
+        | AstKind::RegExpLiteral(_)
+        | AstKind::StringLiteral(_) => "Literal",
+        AstKind::IdentifierReference(_)
+            if matches!(
+                nodes.parent_kind(node.id()),
+                Some(AstKind::JSXElementName(_) | AstKind::JSXMemberExpressionObject(_))
+            ) =>
+        {
+            "JSXIdentifier"
+        }
+        AstKind::IdentifierName(_)
+        | AstKind::IdentifierReference(_)
+        | AstKind::BindingIdentifier(_)
+        | AstKind::LabelIdentifier(_) => "Identifier",
+        AstKind::ObjectProperty(_) => "Property",
+        AstKind::ArrayAssignmentTarget(_) => "ArrayPattern",
+        AstKind::ObjectAssignmentTarget(_) => "ObjectPattern",
+        AstKind::AssignmentTargetWithDefault(_) => "AssignmentPattern",
+        AstKind::BindingRestElement(_) => "RestElement",
+        AstKind::PrivateInExpression(_) => "BinaryExpression",
+        AstKind::Directive(_) => "ExpressionStatement",
+        AstKind::FunctionBody(_) if is_expression_body(nodes, node) => return None,
+        AstKind::FinallyClause(_) | AstKind::FunctionBody(_) => "BlockStatement",
+        AstKind::ExpressionStatement(_)
+            if nodes.parent_node(node.id()).is_some_and(|body| is_expression_body(nodes, body)) =>
+        {
+            return None
+        }
+        AstKind::Function(function) => match function.r#type {
+            FunctionType::FunctionDeclaration => "FunctionDeclaration",
+            FunctionType::FunctionExpression => "FunctionExpression",
+            FunctionType::TSDeclareFunction => "TSDeclareFunction",
+            FunctionType::TSEmptyBodyFunctionExpression => "TSEmptyBodyFunctionExpression",
+        },
+        AstKind::Class(class) => match class.r#type {
+            ClassType::ClassDeclaration => "ClassDeclaration",
+            ClassType::ClassExpression => "ClassExpression",
+        },
+        AstKind::JSXAttributeItem(JSXAttributeItem::Attribute(_)) => "JSXAttribute",
+        AstKind::JSXAttributeItem(JSXAttributeItem::SpreadAttribute(_)) => return None,
+        kind => return variant_name(kind),
+    };
+    Some(node_type)
+}
+
+/// Whether `node` is the body of an arrow function with an expression body, like `() => x`.
+fn is_expression_body(nodes: &AstNodes, node: &AstNode) -> bool {
+    matches!(node.kind(), AstKind::FunctionBody(_))
+        && matches!(
+            nodes.parent_kind(node.id()),
+            Some(AstKind::ArrowFunctionExpression(arrow)) if arrow.expression
+        )
+}
+
+/// The ESTree JSON of `kind`, or `Null` if it has no ESTree counterpart.
+pub(super) fn to_json(kind: AstKind) -> Value {
+    serialize(kind).map_or(Value::Null, normalize)
+}
+
+/// Whether `value` is the JSON of a node of type `node_type` at `span`.
+pub(super) fn is_node(value: &Value, node_type: &str, span: Span) -> bool {
+    value.get("type").and_then(Value::as_str) == Some(node_type)
+        && value.get("start").and_then(Value::as_u64) == Some(u64::from(span.start))
+        && value.get("end").and_then(Value::as_u64) == Some(u64::from(span.end))
+}
+
+/// Converts the value to a string like JavaScript's `String(value)`, or `None` for `undefined`.
+pub(super) fn js_string(value: Option<&Value>) -> Option<String> {
+    let string = match value? {
+        Value::Null => "null".to_string(),
+        Value::Bool(value) => value.to_string(),
+        Value::Number(number) => match number.as_f64() {
+            Some(number) if number.fract() == 0.0 && number.abs() < 1e21 => {
+                format!("{number:.0}")
+            }
+            _ => number.to_string(),
+        },
+        Value::String(value) => value.clone(),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| if item.is_null() { String::new() } else { js_string(Some(item)).unwrap() })
+            .collect::<Vec<_>>()
+            .join(","),
+        Value::Object(_) => "[object Object]".to_string(),
+    };
+    Some(string)
+}
+
+/// JavaScript's `typeof` of the value.
+pub(super) fn js_typeof(value: Option<&Value>) -> &'static str {
+    match value {
+        None => "undefined",
+        Some(Value::Bool(_)) => "boolean",
+        Some(Value::Number(_)) => "number",
+        Some(Value::String(_)) => "string",
+        Some(Value::Null | Value::Array(_) | Value::Object(_)) => "object",
+    }
+}
+
+fn normalize(value: Value) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(items.into_iter().map(normalize).collect()),
+        Value::Object(object) => {
+            normalize_node(object.into_iter().map(|(key, value)| (key, normalize(value))).collect())
+        }
+        value => value,
+    }
+}
+
+fn normalize_node(mut node: Map<String, Value>) -> Value {
+    let Some(node_type) = node.get("type").and_then(Value::as_str).map(ToString::to_string) else {
+        return Value::Object(node);
+    };
+    let node_type = match node_type.as_str() {
+        "ParenthesizedExpression" => return node.remove("expression").unwrap_or_default(),
+        "CatchParameter" | "FormalParameter" => return node.remove("pattern").unwrap_or_default(),
+        "FormalParameters" => return node.remove("items").unwrap_or_default(),
+        "BooleanLiteral" | "NumericLiteral" | "StringLiteral" | "RegExpLiteral" => "Literal",
+        "NullLiteral" => {
+            node.insert("value".to_string(), Value::Null);
+            "Literal"
+        }
+        "BigIntLiteral" => {
+            let bigint = node
+                .get("raw")
+                .and_then(Value::as_str)
+                .map(|raw| raw.trim_end_matches('n').cow_replace('_', "").into_owned());
+            node.insert("value".to_string(), Value::Null);
+            node.insert("bigint".to_string(), bigint.map_or(Value::Null, Value::String));
+            "Literal"
+        }
+        "StaticMemberExpression" => {
+            node.insert("computed".to_string(), Value::Bool(false));
+            "MemberExpression"
+        }
+        "ComputedMemberExpression" => {
+            rename(&mut node, "expression", "property");
+            node.insert("computed".to_string(), Value::Bool(true));
+            "MemberExpression"
+        }
+        "PrivateFieldExpression" => {
+            rename(&mut node, "field", "property");
+            node.insert("computed".to_string(), Value::Bool(false));
+            "MemberExpression"
+        }
+        "ObjectProperty" | "BindingProperty" => "Property",
+        "ArrayAssignmentTarget" => "ArrayPattern",
+        "ObjectAssignmentTarget" => "ObjectPattern",
+        "AssignmentTargetWithDefault" => {
+            rename(&mut node, "binding", "left");
+            rename(&mut node, "init", "right");
+            "AssignmentPattern"
+        }
+        "PrivateInExpression" => "BinaryExpression",
+        "Directive" => "ExpressionStatement",
+        "FunctionBody" => {
+            let mut body = take_array(&mut node, "directives");
+            body.extend(take_array(&mut node, "statements"));
+            node.insert("body".to_string(), Value::Array(body));
+            "BlockStatement"
+        }
+        "Program" => {
+            let mut body = take_array(&mut node, "directives");
+            body.extend(take_array(&mut node, "body"));
+            node.insert("body".to_string(), Value::Array(body));
+            "Program"
+        }
+        "ArrowFunctionExpression" => {
+            if node.get("expression") == Some(&Value::Bool(true)) {
+                let expression = node
+                    .get_mut("body")
+                    .and_then(|body| body.pointer_mut("/body/0/expression"))
+                    .map(Value::take);
+                if let Some(expression) = expression {
+                    node.insert("body".to_string(), expression);
+                }
+            }
+            "ArrowFunctionExpression"
+        }
+        _ => return Value::Object(node),
+    };
+    node.insert("type".to_string(), Value::String(node_type.to_string()));
+    Value::Object(node)
+}
+
+fn rename(node: &mut Map<String, Value>, from: &str, to: &str) {
+    if let Some(value) = node.remove(from) {
+        node.insert(to.to_string(), value);
+    }
+}
+
+fn take_array(node: &mut Map<String, Value>, key: &str) -> Vec<Value> {
+    match node.remove(key) {
+        Some(Value::Array(items)) => items,
+        _ => vec![],
+    }
+}
@@ -0,0 +1,265 @@
+use std::{borrow::Cow, cell::RefCell, rc::Rc};
+
+use oxc_index::Idx;
+use oxc_semantic::{AstNode, AstNodes, NodeId};
+use oxc_span::GetSpan;
+use rustc_hash::FxHashMap;
+use serde_json::Value;
+
+use super::{
+    estree::{is_node, js_string, js_typeof, node_type, to_json},
+    parser::{Attribute, AttributeOperator, AttributeValue, Class, Combinator, SelectorNode},
+    Selector,
+};
+
+/// Matches [`Selector`]s against the nodes of a file.
+///
+/// Nodes without an ESTree counterpart are skipped when walking the tree, so e.g. the parent of
+/// the `Identifier` in `foo(bar)` is the `CallExpression`, not the `Argument`.
+pub struct SelectorMatcher<'s, 'a> {
+    nodes: &'s AstNodes<'a>,
+    /// The ESTree JSON of nodes whose attributes were looked at.
+    json: RefCell<FxHashMap<NodeId, Rc<Value>>>,
+}
+
+impl<'s, 'a> SelectorMatcher<'s, 'a> {
+    pub fn new(nodes: &'s AstNodes<'a>) -> Self {
+        Self { nodes, json: RefCell::default() }
+    }
+
+    /// Whether `node` matches `selector`. Nodes without an ESTree counterpart never match.
+    pub fn matches(&self, selector: &Selector, node: &AstNode<'a>) -> bool {
+        self.node_type(node.id()).is_some() && self.matches_node(&selector.node, node.id(), None)
+    }
+
+    fn matches_node(&self, selector: &SelectorNode, id: NodeId, scope: Option<NodeId>) -> bool {
+        match selector {
+            SelectorNode::Wildcard => true,
+            SelectorNode::Type(name) => {
+                self.node_type(id).is_some_and(|node_type| node_type.eq_ignore_ascii_case(name))
+            }
+            SelectorNode::Attribute(attribute) => self.matches_attribute(attribute, id),
+            SelectorNode::Field(path) => self.matches_field(path, id),
+            SelectorNode::Class(class) => self.matches_class(*class, id),
+            SelectorNode::NthChild { position, from_end } => {
+                self.position(id).is_some_and(|(_, index, len)| {
+                    if *from_end {
+                        len - index == *position
+                    } else {
+                        index + 1 == *position
+                    }
+                })
+            }
+            SelectorNode::Compound(selectors) => {
+                selectors.iter().all(|selector| self.matches_node(selector, id, scope))
+            }
+            SelectorNode::Matches(selectors) => {
+                selectors.iter().any(|selector| self.matches_node(selector, id, scope))
+            }
+            SelectorNode::Not(selectors) => {
+                !selectors.iter().any(|selector| self.matches_node(selector, id, scope))
+            }
+            SelectorNode::Has(selectors) => self.descendants(id).any(|descendant| {
+                selectors.iter().any(|selector| self.matches_node(selector, descendant, Some(id)))
+            }),
+            SelectorNode::Scope => scope == Some(id),
+            SelectorNode::Relation(left, combinator, right) => {
+                self.matches_node(right, id, scope)
+                    && match combinator {
+                        Combinator::Descendant => {
+                            std::iter::successors(self.parent(id), |&ancestor| {
+                                self.parent(ancestor)
+                            })
+                            .any(|ancestor| self.matches_node(left, ancestor, scope))
+                        }
+                        Combinator::Child => self
+                            .parent(id)
+                            .is_some_and(|parent| self.matches_node(left, parent, scope)),
+                        Combinator::Sibling => self
+                            .previous_siblings(id)
+                            .into_iter()
+                            .any(|sibling| self.matches_node(left, sibling, scope)),
+                        Combinator::Adjacent => self
+                            .previous_siblings(id)
+                            .first()
+                            .is_some_and(|&sibling| self.matches_node(left, sibling, scope)),
+                    }
+            }
+        }
+    }
+
+    fn matches_attribute(&self, attribute: &Attribute, id: NodeId) -> bool {
+        let json = self.json(id);
+        let value = get_path(&json, &attribute.path);
+        let value = value.as_deref();
+        let Some((operator, expected)) = &attribute.test else {
+            return value.is_some_and(|value| !value.is_null());
+        };
+        match (operator, expected) {
+            (AttributeOperator::Equal | AttributeOperator::NotEqual, expected) => {
+                let equal = match expected {
+                    AttributeValue::String(expected) => {
+                        js_string(value).is_some_and(|value| value == *expected)
+                    }
+                    AttributeValue::Number(expected) => {
+                        js_string(value) == js_string(Some(&Value::from(*expected)))
+                    }
+                    AttributeValue::Regex(regex) => {
+                        value.and_then(Value::as_str).is_some_and(|value| regex.is_match(value))
+                    }
+                    AttributeValue::Type(expected) => js_typeof(value) == expected,
+                };
+                equal == (*operator == AttributeOperator::Equal)
+            }
+            (operator, AttributeValue::String(expected)) if value.is_some_and(Value::is_string) => {
+                compare(*operator, value.and_then(Value::as_str).unwrap(), expected.as_str())
+            }
+            (operator, expected) => {
+                let value = value.and_then(|value| match value {
+                    Value::Number(number) => number.as_f64(),
+                    Value::String(string) => string.trim().parse().ok(),
+                    _ => None,
+                });
+                let expected = match expected {
+                    AttributeValue::Number(number) => Some(*number),
+                    AttributeValue::String(string) => string.trim().parse().ok(),
+                    _ => None,
+                };
+                value
+                    .zip(expected)
+                    .is_some_and(|(value, expected)| compare(*operator, &value, &expected))
+            }
+        }
+    }
+
+    /// `.a.b` matches nodes at `a.b` of their grandparent.
+    fn matches_field(&self, path: &[String], id: NodeId) -> bool {
+        let Some(node_type) = self.node_type(id) else { return false };
+        let span = self.nodes.kind(id).span();
+        let mut ancestor = Some(id);
+        for _ in 0..path.len() {
+            ancestor = ancestor.and_then(|id| self.parent(id));
+        }
+        let Some(ancestor) = ancestor else { return false };
+        in_path(&self.json(ancestor), path, &|value| is_node(value, node_type, span))
+    }
+
+    fn matches_class(&self, class: Class, id: NodeId) -> bool {
+        let Some(node_type) = self.node_type(id) else { return false };
+        let is_expression = || {
+            node_type.ends_with("Expression")
+                || node_type.ends_with("Literal")
+                || node_type == "MetaProperty"
+                || (node_type == "Identifier"
+                    && self.parent(id).and_then(|parent| self.node_type(parent))
+                        != Some("MetaProperty"))
+        };
+        match class {
+            Class::Statement => {
+                node_type.ends_with("Statement") || node_type.ends_with("Declaration")
+            }
+            Class::Declaration => node_type.ends_with("Declaration"),
+            Class::Pattern => node_type.ends_with("Pattern") || is_expression(),
+            Class::Expression => is_expression(),
+            Class::Function => matches!(
+                node_type,
+                "FunctionDeclaration" | "FunctionExpression" | "ArrowFunctionExpression"
+            ),
+        }
+    }
+
+    fn node_type(&self, id: NodeId) -> Option<&'static str> {
+        node_type(self.nodes, self.nodes.get_node(id))
+    }
+
+    /// The closest ancestor with an ESTree counterpart.
+    fn parent(&self, id: NodeId) -> Option<NodeId> {
+        self.nodes.ancestors(id).skip(1).find(|&id| self.node_type(id).is_some())
+    }
+
+    /// Descendants with an ESTree counterpart, in source order.
+    fn descendants(&self, id: NodeId) -> impl Iterator<Item = NodeId> + '_ {
+        self.nodes
+            .iter()
+            .skip(id.index() + 1)
+            .map(AstNode::id)
+            .take_while(move |&descendant| self.nodes.ancestors(descendant).any(|it| it == id))
+            .filter(move |&descendant| self.node_type(descendant).is_some())
+    }
+
+    /// The array field of its parent the node is in, with its index and the length of the array.
+    fn position(&self, id: NodeId) -> Option<(String, usize, usize)> {
+        let node_type = self.node_type(id)?;
+        let span = self.nodes.kind(id).span();
+        let json = self.json(self.parent(id)?);
+        json.as_object()?.iter().find_map(|(key, value)| {
+            let items = value.as_array()?;
+            let index = items.iter().position(|item| is_node(item, node_type, span))?;
+            Some((key.clone(), index, items.len()))
+        })
+    }
+
+    /// Nodes before this one in the same array field of their parent, closest first.
+    fn previous_siblings(&self, id: NodeId) -> Vec<NodeId> {
+        let (Some(parent), Some((key, index, _))) = (self.parent(id), self.position(id)) else {
+            return vec![];
+        };
+        let mut siblings = self
+            .descendants(parent)
+            .filter(|&sibling| self.parent(sibling) == Some(parent))
+            .filter_map(|sibling| {
+                let (sibling_key, sibling_index, _) = self.position(sibling)?;
+                (sibling_key == key && sibling_index < index).then_some((sibling_index, sibling))
+            })
+            .collect::<Vec<_>>();
+        siblings.sort_unstable_by(|a, b| b.0.cmp(&a.0));
+        siblings.into_iter().map(|(_, sibling)| sibling).collect()
+    }
+
+    fn json(&self, id: NodeId) -> Rc<Value> {
+        if let Some(json) = self.json.borrow().get(&id) {
+            return Rc::clone(json);
+        }
+        let json = Rc::new(to_json(self.nodes.kind(id)));
+        self.json.borrow_mut().insert(id, Rc::clone(&json));
+        json
+    }
+}
+
+/// The value at `path`, with `length` of arrays and strings like in JavaScript.
+fn get_path<'v>(value: &'v Value, path: &[String]) -> Option<Cow<'v, Value>> {
+    let Some((key, rest)) = path.split_first() else { return Some(Cow::Borrowed(value)) };
+    match value {
+        Value::Object(object) => get_path(object.get(key)?, rest),
+        Value::Array(items) if key == "length" => {
+            rest.is_empty().then(|| Cow::Owned(Value::from(items.len())))
+        }
+        Value::Array(items) => get_path(items.get(key.parse::<usize>().ok()?)?, rest),
+        Value::String(string) if key == "length" => {
+            rest.is_empty().then(|| Cow::Owned(Value::from(string.encode_utf16().count())))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a value at `path` of `value` is `is_target`, looking into every item of arrays
+/// along the way.
+fn in_path(value: &Value, path: &[String], is_target: &dyn Fn(&Value) -> bool) -> bool {
+    let Some((key, rest)) = path.split_first() else { return is_target(value) };
+    match value.get(key) {
+        Some(Value::Array(items)) => items.iter().any(|item| in_path(item, rest, is_target)),
+        Some(value) => in_path(value, rest, is_target),
+        None => false,
+    }
+}
+
+fn compare<T: PartialOrd + ?Sized>(operator: AttributeOperator, value: &T, expected: &T) -> bool {
+    match operator {
+        AttributeOperator::Less => value < expected,
+        AttributeOperator::LessEqual => value <= expected,
+        AttributeOperator::Greater => value > expected,
+        AttributeOperator::GreaterEqual => value >= expected,
+        AttributeOperator::Equal => value == expected,
+        AttributeOperator::NotEqual => value != expected,
+    }
+}
@@ -0,0 +1,178 @@
+//! [ESQuery](https://github.com/estools/esquery) selectors, as used by `no-restricted-syntax`
+//! and custom rules.
+
+mod estree;
+mod matcher;
+mod parser;
+
+use std::{fmt, str::FromStr};
+
+use oxc_diagnostics::OxcDiagnostic;
+use schemars::{gen::SchemaGenerator, schema::Schema, JsonSchema};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+pub(crate) use self::matcher::SelectorMatcher;
+use self::parser::SelectorNode;
+
+/// A parsed ESQuery selector, e.g. `CallExpression[callee.name="moment"]`.
+///
+/// Supports type, wildcard, attribute (`=`, `!=`, `<`, `<=`, `>`, `>=`, regular expressions and
+/// `type()`) and field selectors, the descendant, child, sibling and adjacent combinators, and
+/// the `:has`, `:matches`/`:is`, `:not`, `:nth-child`, `:nth-last-child`, `:first-child`,
+/// `:last-child`, `:statement`, `:expression`, `:declaration`, `:function` and `:pattern`
+/// pseudo-classes. Selectors are matched against the ESTree shape of the AST.
+#[derive(Debug, Clone)]
+pub struct Selector {
+    source: String,
+    node: SelectorNode,
+}
+
+impl Selector {
+    /// # Errors
+    ///
+    /// Returns an error if `source` is not a valid selector.
+    pub fn parse(source: &str) -> Result<Self, OxcDiagnostic> {
+        let node = parser::parse(source)?;
+        Ok(Self { source: source.to_string(), node })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+impl FromStr for Selector {
+    type Err = OxcDiagnostic;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for Selector {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.source)
+    }
+}
+
+impl<'de> Deserialize<'de> for Selector {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let source = String::deserialize(deserializer)?;
+        Self::parse(&source).map_err(de::Error::custom)
+    }
+}
+
+impl JsonSchema for Selector {
+    fn schema_name() -> String {
+        "Selector".to_string()
+    }
+
+    fn is_referenceable() -> bool {
+        false
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        String::json_schema(gen)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::{GetSpan, SourceType};
+
+    use super::{Selector, SelectorMatcher};
+
+    /// The source text of every node matching `selector`.
+    fn matches(source_text: &str, selector: &str) -> Vec<String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::default().with_jsx(true);
+        let program = Parser::new(&allocator, source_text, source_type).parse().program;
+        let program = allocator.alloc(program);
+        let semantic = SemanticBuilder::new(source_text).build(program).semantic;
+        let selector = Selector::parse(selector).unwrap();
+        let matcher = SelectorMatcher::new(semantic.nodes());
+        semantic
+            .nodes()
+            .iter()
+            .filter(|node| matcher.matches(&selector, node))
+            .map(|node| node.kind().span().source_text(source_text).to_string())
+            .collect()
+    }
+
+    #[test]
+    fn parse_errors() {
+        for selector in [
+            "",
+            "Identifier[",
+            "[name=\"foo]",
+            "Identifier >",
+            ":unknown",
+            ":nth-child(0)",
+            "[name=/(/]",
+            "[name > /foo/]",
+            "Identifier)",
+        ] {
+            assert!(Selector::parse(selector).is_err(), "{selector}");
+        }
+    }
+
+    #[test]
+    fn types_and_attributes() {
+        assert_eq!(matches("foo(1); bar('a');", "CallExpression"), ["foo(1)", "bar('a')"]);
+        assert_eq!(matches("foo(1); bar('a');", "callexpression[callee.name='bar']"), ["bar('a')"]);
+        assert_eq!(matches("a = 1; b = 'x'; c = null;", "Literal"), ["1", "'x'", "null"]);
+        assert_eq!(matches("a = 1; b = 2.5; c = 10;", "Literal[value>=2]"), ["2.5", "10"]);
+        assert_eq!(matches("a = 1; b = 2;", "Literal[value=1]"), ["1"]);
+        assert_eq!(matches("a = 1; b = '1';", "Literal[value=type(string)]"), ["'1'"]);
+        assert_eq!(matches("fooBar; Foo;", "Identifier[name=/^foo/i]"), ["fooBar", "Foo"]);
+        assert_eq!(matches("let a, b = 1;", "VariableDeclarator[init]"), ["b = 1"]);
+        assert_eq!(matches("a.b; a[b];", "MemberExpression[computed=true]"), ["a[b]"]);
+        assert_eq!(matches("(a);", "ExpressionStatement > Identifier"), ["a"]);
+    }
+
+    #[test]
+    fn combinators() {
+        let source_text = "function f(a) { return a; } g(a, b, c);";
+        assert_eq!(matches(source_text, "FunctionDeclaration Identifier"), ["f", "a", "a"]);
+        assert_eq!(matches(source_text, "ReturnStatement > Identifier"), ["a"]);
+        assert_eq!(matches(source_text, "CallExpression > Identifier ~ Identifier"), ["b", "c"]);
+        assert_eq!(matches(source_text, "Identifier[name='a'] + Identifier"), ["b"]);
+        assert_eq!(matches(source_text, "CallExpression > .arguments"), ["a", "b", "c"]);
+        assert_eq!(matches(source_text, ".callee"), ["g"]);
+    }
+
+    #[test]
+    fn pseudo_classes() {
+        let source_text = "function f() { if (a) { b(); } } const g = () => c;";
+        assert_eq!(
+            matches(source_text, ":function:has(IfStatement)"),
+            ["function f() { if (a) { b(); } }"]
+        );
+        assert_eq!(
+            matches(source_text, ":function:has(> Identifier)"),
+            ["function f() { if (a) { b(); } }", "() => c"]
+        );
+        assert_eq!(
+            matches(source_text, ":statement:not(BlockStatement, :declaration)"),
+            ["if (a) { b(); }", "b();"]
+        );
+        assert_eq!(
+            matches(source_text, ":matches(IfStatement, ArrowFunctionExpression)"),
+            ["if (a) { b(); }", "() => c"]
+        );
+        assert_eq!(
+            matches("[1, 2, 3]", "Literal:first-child, Literal:nth-last-child(1)"),
+            ["1", "3"]
+        );
+        assert_eq!(matches("[1, 2, 3]", "Literal:nth-child(2)"), ["2"]);
+    }
+}
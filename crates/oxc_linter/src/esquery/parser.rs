@@ -0,0 +1,410 @@
+use std::{iter::Peekable, str::CharIndices};
+
+use cow_utils::CowUtils;
+use oxc_diagnostics::OxcDiagnostic;
+use regex::Regex;
+
+/// A parsed selector, see <https://github.com/estools/esquery>.
+#[derive(Debug, Clone)]
+pub(super) enum SelectorNode {
+    /// `*`
+    Wildcard,
+    /// `CallExpression`, compared case-insensitively.
+    Type(String),
+    /// `[callee.name="moment"]`
+    Attribute(Attribute),
+    /// `.callee.object`, matches nodes at this path from one of their ancestors.
+    Field(Vec<String>),
+    /// `:statement`, `:expression`, `:declaration`, `:function` or `:pattern`
+    Class(Class),
+    /// `:nth-child(2)`, `:first-child`, or from the end with `:nth-last-child`
+    /// and `:last-child`. Positions start at 1.
+    NthChild { position: usize, from_end: bool },
+    /// Several of the above without whitespace in between, e.g. `Identifier[name="foo"]`.
+    Compound(Vec<SelectorNode>),
+    /// `:matches(a, b)` or `:is(a, b)`, and comma separated lists of selectors.
+    Matches(Vec<SelectorNode>),
+    /// `:not(a, b)`
+    Not(Vec<SelectorNode>),
+    /// `:has(a, > b)`
+    Has(Vec<SelectorNode>),
+    /// The node a `:has` selector is matched against, implied at the start of
+    /// its selectors.
+    Scope,
+    /// `a > b`, `a b`, `a ~ b` or `a + b`
+    Relation(Box<SelectorNode>, Combinator, Box<SelectorNode>),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Combinator {
+    /// `a b`
+    Descendant,
+    /// `a > b`
+    Child,
+    /// `a ~ b`
+    Sibling,
+    /// `a + b`
+    Adjacent,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Class {
+    Statement,
+    Expression,
+    Declaration,
+    Function,
+    Pattern,
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Attribute {
+    pub path: Vec<String>,
+    /// `None` if the attribute only needs to exist, as in `[init]`.
+    pub test: Option<(AttributeOperator, AttributeValue)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum AttributeOperator {
+    Equal,
+    NotEqual,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+}
+
+#[derive(Debug, Clone)]
+pub(super) enum AttributeValue {
+    /// A quoted string or an unquoted name, e.g. `"foo"` or `foo`.
+    String(String),
+    Number(f64),
+    /// `/^foo/i`
+    Regex(Regex),
+    /// `type(string)`, compared with the JavaScript `typeof` of the attribute.
+    Type(String),
+}
+
+pub(super) fn parse(source: &str) -> Result<SelectorNode, OxcDiagnostic> {
+    let mut parser = Parser { source, chars: source.char_indices().peekable() };
+    parser.skip_whitespace();
+    let selector = parser.parse_selectors(false)?;
+    parser.skip_whitespace();
+    match parser.chars.peek() {
+        None => Ok(selector),
+        Some(&(position, c)) => Err(parser.error(position, &format!("unexpected `{c}`"))),
+    }
+}
+
+struct Parser<'s> {
+    source: &'s str,
+    chars: Peekable<CharIndices<'s>>,
+}
+
+impl<'s> Parser<'s> {
+    /// A comma separated list of selectors. Selectors of `:has` are relative to
+    /// the node it is matched against.
+    fn parse_selectors(&mut self, relative: bool) -> Result<SelectorNode, OxcDiagnostic> {
+        let mut selectors = vec![];
+        loop {
+            self.skip_whitespace();
+            selectors.push(self.parse_complex(relative)?);
+            self.skip_whitespace();
+            if !self.eat(',') {
+                break;
+            }
+        }
+        Ok(if selectors.len() == 1 {
+            selectors.pop().unwrap()
+        } else {
+            SelectorNode::Matches(selectors)
+        })
+    }
+
+    /// Compound selectors separated by combinators, e.g. `a > b c`.
+    fn parse_complex(&mut self, relative: bool) -> Result<SelectorNode, OxcDiagnostic> {
+        let mut selector = if relative {
+            let combinator = self.parse_combinator().unwrap_or(Combinator::Descendant);
+            let right = self.parse_compound()?;
+            SelectorNode::Relation(Box::new(SelectorNode::Scope), combinator, Box::new(right))
+        } else {
+            self.parse_compound()?
+        };
+        loop {
+            let had_whitespace = self.skip_whitespace();
+            let combinator = match self.parse_combinator() {
+                Some(combinator) => combinator,
+                None if had_whitespace
+                    && self.chars.peek().is_some_and(|&(_, c)| c != ',' && c != ')') =>
+                {
+                    Combinator::Descendant
+                }
+                None => return Ok(selector),
+            };
+            let right = self.parse_compound()?;
+            selector = SelectorNode::Relation(Box::new(selector), combinator, Box::new(right));
+        }
+    }
+
+    fn parse_combinator(&mut self) -> Option<Combinator> {
+        let combinator = match self.chars.peek()?.1 {
+            '>' => Combinator::Child,
+            '~' => Combinator::Sibling,
+            '+' => Combinator::Adjacent,
+            _ => return None,
+        };
+        self.chars.next();
+        self.skip_whitespace();
+        Some(combinator)
+    }
+
+    /// Selectors without whitespace in between, e.g. `Identifier[name="foo"]:first-child`.
+    fn parse_compound(&mut self) -> Result<SelectorNode, OxcDiagnostic> {
+        let mut selectors = vec![];
+        while let Some(&(position, c)) = self.chars.peek() {
+            let selector = match c {
+                '*' => {
+                    self.chars.next();
+                    SelectorNode::Wildcard
+                }
+                '[' => {
+                    self.chars.next();
+                    SelectorNode::Attribute(self.parse_attribute()?)
+                }
+                '.' => {
+                    self.chars.next();
+                    SelectorNode::Field(self.parse_path()?)
+                }
+                ':' => {
+                    self.chars.next();
+                    self.parse_pseudo(position)?
+                }
+                c if is_name_char(c) => SelectorNode::Type(self.parse_name()?),
+                _ => break,
+            };
+            selectors.push(selector);
+        }
+        match selectors.len() {
+            0 => Err(self.error_at_next("expected a selector")),
+            1 => Ok(selectors.pop().unwrap()),
+            _ => Ok(SelectorNode::Compound(selectors)),
+        }
+    }
+
+    fn parse_attribute(&mut self) -> Result<Attribute, OxcDiagnostic> {
+        self.skip_whitespace();
+        let path = self.parse_path()?;
+        self.skip_whitespace();
+        let operator = match self.chars.peek().map(|&(_, c)| c) {
+            Some(']') => {
+                self.chars.next();
+                return Ok(Attribute { path, test: None });
+            }
+            Some('=') => AttributeOperator::Equal,
+            Some('!') => AttributeOperator::NotEqual,
+            Some('<') => AttributeOperator::Less,
+            Some('>') => AttributeOperator::Greater,
+            _ => return Err(self.error_at_next("expected `]` or an operator")),
+        };
+        self.chars.next();
+        let operator = match operator {
+            AttributeOperator::NotEqual => {
+                self.expect('=')?;
+                operator
+            }
+            AttributeOperator::Less if self.eat('=') => AttributeOperator::LessEqual,
+            AttributeOperator::Greater if self.eat('=') => AttributeOperator::GreaterEqual,
+            _ => operator,
+        };
+        self.skip_whitespace();
+        let value = self.parse_attribute_value()?;
+        if matches!(value, AttributeValue::Regex(_) | AttributeValue::Type(_))
+            && !matches!(operator, AttributeOperator::Equal | AttributeOperator::NotEqual)
+        {
+            return Err(self.error_at_next(
+                "regular expressions and types can only be compared with `=` and `!=`",
+            ));
+        }
+        self.skip_whitespace();
+        self.expect(']')?;
+        Ok(Attribute { path, test: Some((operator, value)) })
+    }
+
+    fn parse_attribute_value(&mut self) -> Result<AttributeValue, OxcDiagnostic> {
+        let Some(&(position, c)) = self.chars.peek() else {
+            return Err(self.error_at_next("expected a value"));
+        };
+        match c {
+            '"' | '\'' => {
+                self.chars.next();
+                let mut value = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some((_, '\\')) => match self.chars.next() {
+                            Some((_, 'n')) => value.push('\n'),
+                            Some((_, 'r')) => value.push('\r'),
+                            Some((_, 't')) => value.push('\t'),
+                            Some((_, c)) => value.push(c),
+                            None => break,
+                        },
+                        Some((_, quote)) if quote == c => return Ok(AttributeValue::String(value)),
+                        Some((_, c)) => value.push(c),
+                        None => break,
+                    }
+                }
+                Err(self.error(position, "unterminated string"))
+            }
+            '/' => {
+                self.chars.next();
+                let mut pattern = String::new();
+                loop {
+                    match self.chars.next() {
+                        Some((_, '\\')) if self.eat('/') => pattern.push('/'),
+                        Some((_, '/')) => break,
+                        Some((_, c)) => pattern.push(c),
+                        None => return Err(self.error(position, "unterminated regular expression")),
+                    }
+                }
+                let mut flags = String::new();
+                while let Some(&(_, flag @ ('i' | 'm' | 's' | 'u'))) = self.chars.peek() {
+                    if flag != 'u' {
+                        flags.push(flag);
+                    }
+                    self.chars.next();
+                }
+                let pattern =
+                    if flags.is_empty() { pattern } else { format!("(?{flags}){pattern}") };
+                Regex::new(&pattern).map(AttributeValue::Regex).map_err(|err| {
+                    self.error(position, &format!("invalid regular expression: {err}"))
+                })
+            }
+            '0'..='9' => {
+                let mut number = String::new();
+                while let Some(&(_, c @ ('0'..='9' | '.'))) = self.chars.peek() {
+                    number.push(c);
+                    self.chars.next();
+                }
+                number
+                    .parse()
+                    .map(AttributeValue::Number)
+                    .map_err(|_| self.error(position, &format!("invalid number `{number}`")))
+            }
+            _ => {
+                let name = self.parse_path()?.join(".");
+                if name == "type" && self.eat('(') {
+                    self.skip_whitespace();
+                    let name = self.parse_name()?;
+                    self.skip_whitespace();
+                    self.expect(')')?;
+                    return Ok(AttributeValue::Type(name));
+                }
+                Ok(AttributeValue::String(name))
+            }
+        }
+    }
+
+    fn parse_pseudo(&mut self, position: usize) -> Result<SelectorNode, OxcDiagnostic> {
+        let name = self.parse_name()?;
+        let name = name.cow_to_ascii_lowercase();
+        let selector = match name.as_ref() {
+            "not" | "matches" | "is" | "has" => {
+                self.expect('(')?;
+                let selector = self.parse_selectors(name == "has")?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                let selectors = match selector {
+                    SelectorNode::Matches(selectors) => selectors,
+                    selector => vec![selector],
+                };
+                match name.as_ref() {
+                    "not" => SelectorNode::Not(selectors),
+                    "has" => SelectorNode::Has(selectors),
+                    _ => SelectorNode::Matches(selectors),
+                }
+            }
+            "nth-child" | "nth-last-child" => {
+                self.expect('(')?;
+                self.skip_whitespace();
+                let start = self.chars.peek().map_or(self.source.len(), |&(i, _)| i);
+                let mut digits = String::new();
+                while let Some(&(_, c @ '0'..='9')) = self.chars.peek() {
+                    digits.push(c);
+                    self.chars.next();
+                }
+                let position = digits
+                    .parse()
+                    .ok()
+                    .filter(|&position| position > 0)
+                    .ok_or_else(|| self.error(start, "expected a positive integer"))?;
+                self.skip_whitespace();
+                self.expect(')')?;
+                SelectorNode::NthChild { position, from_end: name == "nth-last-child" }
+            }
+            "first-child" => SelectorNode::NthChild { position: 1, from_end: false },
+            "last-child" => SelectorNode::NthChild { position: 1, from_end: true },
+            "statement" => SelectorNode::Class(Class::Statement),
+            "expression" => SelectorNode::Class(Class::Expression),
+            "declaration" => SelectorNode::Class(Class::Declaration),
+            "function" => SelectorNode::Class(Class::Function),
+            "pattern" => SelectorNode::Class(Class::Pattern),
+            _ => return Err(self.error(position, &format!("unknown pseudo-class `:{name}`"))),
+        };
+        Ok(selector)
+    }
+
+    /// Names separated by dots, e.g. `callee.object.name`.
+    fn parse_path(&mut self) -> Result<Vec<String>, OxcDiagnostic> {
+        let mut path = vec![self.parse_name()?];
+        while self.eat('.') {
+            path.push(self.parse_name()?);
+        }
+        Ok(path)
+    }
+
+    fn parse_name(&mut self) -> Result<String, OxcDiagnostic> {
+        let mut name = String::new();
+        while let Some(&(_, c)) = self.chars.peek().filter(|&&(_, c)| is_name_char(c)) {
+            name.push(c);
+            self.chars.next();
+        }
+        if name.is_empty() {
+            return Err(self.error_at_next("expected a name"));
+        }
+        Ok(name)
+    }
+
+    fn skip_whitespace(&mut self) -> bool {
+        let mut skipped = false;
+        while self.chars.next_if(|&(_, c)| c.is_whitespace()).is_some() {
+            skipped = true;
+        }
+        skipped
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.chars.next_if(|&(_, c)| c == expected).is_some()
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), OxcDiagnostic> {
+        if self.eat(expected) {
+            Ok(())
+        } else {
+            Err(self.error_at_next(&format!("expected `{expected}`")))
+        }
+    }
+
+    fn error_at_next(&mut self, message: &str) -> OxcDiagnostic {
+        let position = self.chars.peek().map_or(self.source.len(), |&(i, _)| i);
+        self.error(position, message)
+    }
+
+    fn error(&self, position: usize, message: &str) -> OxcDiagnostic {
+        OxcDiagnostic::error(format!(
+            "Invalid selector `{}`: {message} at position {position}.",
+            self.source
+        ))
+    }
+}
+
+fn is_name_char(c: char) -> bool {
+    !c.is_whitespace() && !"[],():#!=<>~+.*\"'/".contains(c)
+}
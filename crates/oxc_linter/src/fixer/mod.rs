@@ -207,7 +207,10 @@ pub struct FixResult<'a> {
     #[allow(unused)]
     pub fixed: bool,
     pub fixed_code: Cow<'a, str>,
+    /// Messages that were not fixed
     pub messages: Vec<Message<'a>>,
+    /// Messages whose fixes were applied
+    pub fixed_messages: Vec<Message<'a>>,
 }
 
 #[derive(Clone)]
@@ -272,6 +275,7 @@ impl<'a> Fixer<'a> {
                 fixed: false,
                 fixed_code: Cow::Borrowed(source_text),
                 messages: self.messages,
+                fixed_messages: vec![],
             };
         }
 
@@ -282,6 +286,7 @@ impl<'a> Fixer<'a> {
 
         // only keep messages that were not fixed
         let mut filtered_messages = Vec::with_capacity(self.messages.len());
+        let mut fixed_messages = vec![];
 
         for mut m in self.messages {
             let Some(Fix { content, span }) = m.fix.as_ref() else {
@@ -306,13 +311,19 @@ impl<'a> Fixer<'a> {
             output.push_str(&source_text[offset..start as usize]);
             output.push_str(content);
             last_pos = i64::from(end);
+            fixed_messages.push(m);
         }
 
         let offset = usize::try_from(last_pos.max(0)).ok().unwrap();
         output.push_str(&source_text[offset..]);
 
         filtered_messages.sort_unstable_by_key(GetSpan::span);
-        FixResult { fixed, fixed_code: Cow::Owned(output), messages: filtered_messages }
+        FixResult {
+            fixed,
+            fixed_code: Cow::Owned(output),
+            messages: filtered_messages,
+            fixed_messages,
+        }
     }
 }
 
@@ -4,10 +4,13 @@
 mod tester;
 
 mod ast_util;
+mod baseline;
 mod builder;
+mod cache;
 mod config;
 mod context;
 mod disable_directives;
+mod esquery;
 mod fixer;
 mod frameworks;
 mod globals;
@@ -23,26 +26,35 @@ pub mod table;
 
 use std::{io::Write, path::Path, rc::Rc, sync::Arc};
 
-use config::LintConfig;
+use config::{CustomRules, LintConfig, ResolvedLinterState};
 use context::ContextHost;
+use dashmap::DashMap;
 use options::LintOptions;
 use oxc_diagnostics::Error;
 use oxc_semantic::{AstNode, Semantic};
+use rustc_hash::FxHashSet;
 
 pub use crate::{
+    baseline::{Baseline, BaselineMode, BASELINE_FILE_NAME},
     builder::LinterBuilder,
-    config::Oxlintrc,
+    cache::{LintCache, CACHE_FILE_NAME},
+    config::{
+        ConfigDiscovery, DiscoveredConfig, EslintMigration, GlobSet, OxlintOverride,
+        OxlintOverrides, Oxlintrc, CONFIG_FILE_NAME,
+    },
     context::LintContext,
+    esquery::Selector,
     fixer::FixKind,
     frameworks::FrameworkFlags,
     options::{AllowWarnDeny, InvalidFilterKind, LintFilter, LintFilterKind, OxlintOptions},
     rule::{RuleCategory, RuleFixMeta, RuleMeta, RuleWithSeverity},
-    service::{LintService, LintServiceOptions},
+    service::{AppliedFix, FixedFile, LintService, LintServiceOptions},
 };
 use crate::{
     config::{OxlintEnv, OxlintGlobals, OxlintSettings},
     fixer::{Fixer, Message},
-    rules::RuleEnum,
+    options::LintPlugins,
+    rules::{RuleEnum, RULES},
     table::RuleTable,
 };
 
@@ -55,11 +67,18 @@ fn size_asserts() {
     assert!(std::mem::size_of::<RuleEnum>() == 16);
 }
 
+/// Rules and configuration resolved for a discovered configuration file and
+/// the `overrides` that apply to a file, keyed by the directory of the
+/// configuration file and the indices of the overrides.
+type ResolvedStates = DashMap<(Option<Box<Path>>, Vec<usize>), Arc<ResolvedLinterState>>;
+
 #[derive(Debug)]
 pub struct Linter {
     rules: Vec<RuleWithSeverity>,
     options: LintOptions,
     config: Arc<LintConfig>,
+    overrides: OxlintOverrides,
+    resolved_states: ResolvedStates,
 }
 
 impl Default for Linter {
@@ -73,16 +92,23 @@ impl Linter {
         rules: Vec<RuleWithSeverity>,
         options: LintOptions,
         config: LintConfig,
+        overrides: OxlintOverrides,
     ) -> Self {
-        Self { rules, options, config: Arc::new(config) }
+        Self {
+            rules,
+            options,
+            config: Arc::new(config),
+            overrides,
+            resolved_states: ResolvedStates::default(),
+        }
     }
 
     /// # Errors
     ///
     /// Returns `Err` if there are any errors parsing the configuration file.
     pub fn from_options(options: OxlintOptions) -> Result<Self, Error> {
-        let (rules, config) = options.derive_rules_and_config()?;
-        Ok(Self { rules, options: options.into(), config: Arc::new(config) })
+        let (rules, config, overrides) = options.derive_rules_and_config()?;
+        Ok(Self::new(rules, options.into(), config, overrides))
     }
 
     #[cfg(test)]
@@ -121,12 +147,148 @@ impl Linter {
         &self.rules
     }
 
+    /// Apply a discovered configuration, then the overrides at `indices` (in
+    /// order), on top of this linter's rules and configuration.
+    ///
+    /// When `discovered` is given, `indices` refer to its `overrides` rather
+    /// than this linter's.
+    pub(crate) fn resolve(
+        &self,
+        discovered: Option<&Oxlintrc>,
+        indices: &[usize],
+    ) -> ResolvedLinterState {
+        let mut rules = self.rules.iter().cloned().collect::<FxHashSet<_>>();
+        let mut plugins = self.options.plugins;
+
+        let (mut config, overrides) = match discovered {
+            Some(discovered) => {
+                plugins |= discovered.plugins;
+                discovered.rules.override_rules(&mut rules, &Self::rules_for_plugins(plugins));
+                let config = LintConfig {
+                    settings: discovered.settings.clone(),
+                    env: discovered.env.clone(),
+                    globals: discovered.globals.clone(),
+                    custom_rules: CustomRules::new(
+                        discovered.custom_rules.clone(),
+                        &discovered.rules,
+                    ),
+                };
+                (config, &discovered.overrides)
+            }
+            None => (LintConfig::clone(&self.config), &self.overrides),
+        };
+
+        for override_config in indices.iter().map(|&i| &overrides[i]) {
+            if let Some(override_plugins) = override_config.plugins {
+                plugins |= override_plugins;
+            }
+            if let Some(env) = &override_config.env {
+                config.env.override_envs(env);
+            }
+            if let Some(globals) = &override_config.globals {
+                config.globals.override_globals(globals);
+            }
+            override_config.rules.override_rules(&mut rules, &Self::rules_for_plugins(plugins));
+            config.custom_rules.override_rules(&override_config.rules);
+        }
+
+        let mut rules = rules.into_iter().collect::<Vec<_>>();
+        rules.sort_unstable_by_key(|rule| rule.id());
+        ResolvedLinterState { rules, config: Arc::new(config), plugins }
+    }
+
+    fn rules_for_plugins(plugins: LintPlugins) -> Vec<RuleEnum> {
+        RULES
+            .iter()
+            .filter(|rule| plugins.contains(LintPlugins::from(rule.plugin_name())))
+            .cloned()
+            .collect()
+    }
+
     pub fn run<'a>(&self, path: &Path, semantic: Rc<Semantic<'a>>) -> Vec<Message<'a>> {
-        let ctx_host =
-            Rc::new(ContextHost::new(path, semantic, self.options).with_config(&self.config));
+        Self::run_with(path, semantic, &self.rules, &self.config, self.options)
+    }
+
+    /// Lint a file like [`Linter::run`], with the `overrides` of the configuration whose globs
+    /// match `path` applied.
+    ///
+    /// Globs are matched against `path` as is, so it should be relative to the directory of the
+    /// configuration file. The rules and configuration resolved for each combination of matching
+    /// overrides are cached.
+    pub fn run_with_overrides<'a>(
+        &self,
+        path: &Path,
+        semantic: Rc<Semantic<'a>>,
+    ) -> Vec<Message<'a>> {
+        self.run_with_discovered(path, semantic, None, Path::new(""))
+    }
+
+    /// Lint a file with a configuration discovered for it, and the `overrides`
+    /// whose globs match it applied.
+    ///
+    /// Globs of the `overrides` of this linter's configuration are matched
+    /// against `path` relative to `cwd`.
+    pub fn run_with_discovered<'a>(
+        &self,
+        path: &Path,
+        semantic: Rc<Semantic<'a>>,
+        discovered: Option<&DiscoveredConfig>,
+        cwd: &Path,
+    ) -> Vec<Message<'a>> {
+        match self.resolved_state(path, discovered, cwd) {
+            Some(state) => self.run_resolved(path, semantic, &state),
+            None => self.run(path, semantic),
+        }
+    }
 
-        let rules = self
-            .rules
+    /// Get the rules and configuration for `path` if a discovered configuration
+    /// file or any `overrides` apply to it.
+    fn resolved_state(
+        &self,
+        path: &Path,
+        discovered: Option<&DiscoveredConfig>,
+        cwd: &Path,
+    ) -> Option<Arc<ResolvedLinterState>> {
+        let overrides =
+            discovered.map_or(&self.overrides, |discovered| &discovered.config().overrides);
+        let relative_path =
+            path.strip_prefix(cwd).or_else(|_| path.strip_prefix(".")).unwrap_or(path);
+        let indices =
+            if overrides.is_empty() { vec![] } else { overrides.matching(relative_path, cwd) };
+        if discovered.is_none() && indices.is_empty() {
+            return None;
+        }
+
+        let key = (discovered.map(|discovered| discovered.dir().into()), indices);
+        if let Some(state) = self.resolved_states.get(&key) {
+            return Some(Arc::clone(state.value()));
+        }
+        let state = Arc::new(self.resolve(discovered.map(DiscoveredConfig::config), &key.1));
+        self.resolved_states.insert(key, Arc::clone(&state));
+        Some(state)
+    }
+
+    /// Lint a file with the rules and configuration resolved for it by [`Linter::resolve`].
+    pub(crate) fn run_resolved<'a>(
+        &self,
+        path: &Path,
+        semantic: Rc<Semantic<'a>>,
+        state: &ResolvedLinterState,
+    ) -> Vec<Message<'a>> {
+        let options = LintOptions { plugins: state.plugins, ..self.options };
+        Self::run_with(path, semantic, &state.rules, &state.config, options)
+    }
+
+    fn run_with<'a>(
+        path: &Path,
+        semantic: Rc<Semantic<'a>>,
+        rules: &[RuleWithSeverity],
+        config: &Arc<LintConfig>,
+        options: LintOptions,
+    ) -> Vec<Message<'a>> {
+        let ctx_host = Rc::new(ContextHost::new(path, semantic, options).with_config(config));
+
+        let rules = rules
             .iter()
             .filter(|rule| rule.should_run(&ctx_host))
             .map(|rule| (rule, Rc::clone(&ctx_host).spawn(rule)))
@@ -149,6 +311,14 @@ impl Linter {
             }
         }
 
+        ctx_host.run_custom_rules();
+
+        if let Some(severity) =
+            options.report_unused_directives.filter(|severity| severity.is_warn_deny())
+        {
+            ctx_host.report_unused_directives(severity);
+        }
+
         ctx_host.take_diagnostics()
     }
 
@@ -165,7 +335,14 @@ impl Linter {
 
 #[cfg(test)]
 mod test {
-    use super::{Linter, Oxlintrc};
+    use std::{path::Path, rc::Rc};
+
+    use oxc_allocator::Allocator;
+    use oxc_parser::Parser;
+    use oxc_semantic::SemanticBuilder;
+    use oxc_span::SourceType;
+
+    use super::{Linter, LinterBuilder, Oxlintrc};
 
     #[test]
     fn print_rules() {
@@ -174,6 +351,29 @@ mod test {
         assert!(!writer.is_empty());
     }
 
+    #[test]
+    fn run_with_overrides_caches_resolved_states() {
+        let config: Oxlintrc = serde_json::from_value(serde_json::json!({
+            "rules": { "no-debugger": "off" },
+            "overrides": [{ "files": ["*.test.js"], "rules": { "no-debugger": "error" } }]
+        }))
+        .unwrap();
+        let linter = LinterBuilder::from_oxlintrc(false, config).build();
+        let lint = |path: &str| {
+            let allocator = Allocator::default();
+            let source_type = SourceType::from_path(path).unwrap();
+            let program = Parser::new(&allocator, "debugger;", source_type).parse().program;
+            let semantic =
+                SemanticBuilder::new("debugger;").with_cfg(true).build(&program).semantic;
+            linter.run_with_overrides(Path::new(path), Rc::new(semantic)).len()
+        };
+
+        assert_eq!(lint("a.test.js"), 1);
+        assert_eq!(lint("b.test.js"), 1);
+        assert_eq!(lint("index.js"), 0);
+        assert_eq!(linter.resolved_states.len(), 1);
+    }
+
     #[test]
     fn test_schema_json() {
         use std::fs;
@@ -12,7 +12,7 @@ pub use filter::{InvalidFilterKind, LintFilter, LintFilterKind};
 pub use plugins::{LintPluginOptions, LintPlugins};
 
 use crate::{
-    config::{LintConfig, Oxlintrc},
+    config::{LintConfig, OxlintOverrides, Oxlintrc},
     fixer::FixKind,
     rules::RULES,
     utils::is_jest_rule_adapted_to_vitest,
@@ -30,6 +30,7 @@ pub(crate) struct LintOptions {
     pub fix: FixKind,
     pub framework_hints: FrameworkFlags,
     pub plugins: LintPlugins,
+    pub report_unused_directives: Option<AllowWarnDeny>,
 }
 
 impl From<OxlintOptions> for LintOptions {
@@ -38,6 +39,7 @@ impl From<OxlintOptions> for LintOptions {
             fix: options.fix,
             framework_hints: options.framework_hints,
             plugins: options.plugins.into(),
+            report_unused_directives: options.report_unused_directives,
         }
     }
 }
@@ -56,6 +58,10 @@ pub struct OxlintOptions {
     pub plugins: LintPluginOptions,
 
     pub framework_hints: FrameworkFlags,
+
+    /// Report `eslint-disable` directives that do not suppress any
+    /// diagnostic. Set to [`None`] to not report them.
+    pub report_unused_directives: Option<AllowWarnDeny>,
 }
 
 impl Default for OxlintOptions {
@@ -66,6 +72,7 @@ impl Default for OxlintOptions {
             fix: FixKind::None,
             plugins: LintPluginOptions::default(),
             framework_hints: FrameworkFlags::default(),
+            report_unused_directives: None,
         }
     }
 }
@@ -101,6 +108,13 @@ impl OxlintOptions {
         self
     }
 
+    /// Report unused `eslint-disable` directives with the given severity.
+    #[must_use]
+    pub fn with_report_unused_directives(mut self, severity: Option<AllowWarnDeny>) -> Self {
+        self.report_unused_directives = severity;
+        self
+    }
+
     #[must_use]
     pub fn with_react_plugin(mut self, yes: bool) -> Self {
         self.plugins.react = yes;
@@ -192,7 +206,7 @@ impl OxlintOptions {
     /// * Returns `Err` if there are any errors parsing the configuration file.
     pub(crate) fn derive_rules_and_config(
         &self,
-    ) -> Result<(Vec<RuleWithSeverity>, LintConfig), Error> {
+    ) -> Result<(Vec<RuleWithSeverity>, LintConfig, OxlintOverrides), Error> {
         let config = self.config_path.as_ref().map(|path| Oxlintrc::from_file(path)).transpose()?;
 
         let mut rules: FxHashSet<RuleWithSeverity> = FxHashSet::default();
@@ -262,7 +276,13 @@ impl OxlintOptions {
         // for stable diagnostics output ordering
         rules.sort_unstable_by_key(|rule| rule.id());
 
-        Ok((rules, config.map(Into::into).unwrap_or_default()))
+        let (config, overrides): (LintConfig, _) =
+            config.map_or_else(Default::default, |mut config| {
+                let overrides = std::mem::take(&mut config.overrides);
+                (config.into(), overrides)
+            });
+
+        Ok((rules, config, overrides))
     }
 
     /// Get final filtered rules by reading `self.xxx_plugin`
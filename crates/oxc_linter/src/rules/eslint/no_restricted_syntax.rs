@@ -0,0 +1,189 @@
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_macros::declare_oxc_lint;
+use oxc_span::{GetSpan, Span};
+use serde_json::Value;
+
+use crate::{
+    context::LintContext,
+    esquery::{Selector, SelectorMatcher},
+    rule::Rule,
+};
+
+fn no_restricted_syntax_diagnostic(restriction: &Restriction, span: Span) -> OxcDiagnostic {
+    let message = match &restriction.message {
+        Some(message) => message.clone(),
+        None => format!("Using '{}' is not allowed.", restriction.selector),
+    };
+    OxcDiagnostic::warn(message).with_label(span)
+}
+
+#[derive(Debug, Default, Clone)]
+pub struct NoRestrictedSyntax(Box<NoRestrictedSyntaxConfig>);
+
+#[derive(Debug, Default, Clone)]
+pub struct NoRestrictedSyntaxConfig {
+    restrictions: Vec<Restriction>,
+}
+
+impl std::ops::Deref for NoRestrictedSyntax {
+    type Target = NoRestrictedSyntaxConfig;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Restriction {
+    selector: Selector,
+    message: Option<String>,
+}
+
+declare_oxc_lint!(
+    /// ### What it does
+    ///
+    /// Disallows syntax matching the given [ESQuery](https://github.com/estools/esquery)
+    /// selectors.
+    ///
+    /// ### Why is this bad?
+    ///
+    /// JavaScript has a lot of language features, and not everyone likes all of them. Some
+    /// projects want to disallow specific constructs, such as `with` statements, `for...in`
+    /// loops or calls to a deprecated helper.
+    ///
+    /// Selectors are matched against the [ESTree](https://github.com/estree/estree) shape of
+    /// the AST, so selectors written for ESLint work unchanged. Invalid selectors are ignored.
+    ///
+    /// ### Example
+    ///
+    /// With the options:
+    ///
+    /// ```json
+    /// "no-restricted-syntax": [
+    ///     "error",
+    ///     "WithStatement",
+    ///     { "selector": "CallExpression[callee.name='setTimeout'][arguments.length!=2]", "message": "setTimeout must always be invoked with two arguments." }
+    /// ]
+    /// ```
+    ///
+    /// Examples of **incorrect** code for this rule:
+    /// ```js
+    /// with (me) {
+    ///     dontMess();
+    /// }
+    /// setTimeout(callback);
+    /// ```
+    ///
+    /// Examples of **correct** code for this rule:
+    /// ```js
+    /// me.dontMess();
+    /// setTimeout(callback, 0);
+    /// ```
+    NoRestrictedSyntax,
+    restriction,
+);
+
+impl Rule for NoRestrictedSyntax {
+    fn from_configuration(value: Value) -> Self {
+        let restrictions = value
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|restriction| match restriction {
+                Value::String(selector) => {
+                    Some(Restriction { selector: Selector::parse(selector).ok()?, message: None })
+                }
+                Value::Object(restriction) => {
+                    let selector = restriction.get("selector").and_then(Value::as_str)?;
+                    Some(Restriction {
+                        selector: Selector::parse(selector).ok()?,
+                        message: restriction
+                            .get("message")
+                            .and_then(Value::as_str)
+                            .map(ToString::to_string),
+                    })
+                }
+                _ => None,
+            })
+            .collect();
+        Self(Box::new(NoRestrictedSyntaxConfig { restrictions }))
+    }
+
+    fn run_once(&self, ctx: &LintContext) {
+        if self.restrictions.is_empty() {
+            return;
+        }
+        let matcher = SelectorMatcher::new(ctx.nodes());
+        for node in ctx.nodes().iter() {
+            for restriction in &self.restrictions {
+                if matcher.matches(&restriction.selector, node) {
+                    ctx.diagnostic(no_restricted_syntax_diagnostic(
+                        restriction,
+                        node.kind().span(),
+                    ));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test() {
+    use crate::tester::Tester;
+
+    let pass = vec![
+        ("doSomething();", None),
+        ("var foo = 42;", Some(serde_json::json!(["ConditionalExpression"]))),
+        ("foo += 42;", Some(serde_json::json!(["VariableDeclaration", "FunctionExpression"]))),
+        ("foo;", Some(serde_json::json!(["Identifier[name=\"bar\"]"]))),
+        ("() => 5", Some(serde_json::json!(["ArrowFunctionExpression > BlockStatement"]))),
+        ("({ foo: 1, bar: 2 })", Some(serde_json::json!(["Property > Literal.key"]))),
+        ("A: for (;;) break;", Some(serde_json::json!(["BreakStatement[label]"]))),
+        (
+            "function foo(bar, baz) {}",
+            Some(serde_json::json!(["FunctionDeclaration[params.length>2]"])),
+        ),
+        ("foo", Some(serde_json::json!([{ "selector": "Identifier[name=\"bar\"]" }]))),
+        ("foo", Some(serde_json::json!(["Identifier["]))),
+        ("var foo = 42;", Some(serde_json::json!([{ "message": "no selector" }]))),
+    ];
+
+    let fail = vec![
+        ("var foo = 41;", Some(serde_json::json!(["VariableDeclaration"]))),
+        (";function lol(a) { return 42; }", Some(serde_json::json!(["EmptyStatement"]))),
+        (
+            "try { voila(); } catch (e) { oops(); }",
+            Some(serde_json::json!([
+                "TryStatement",
+                "CallExpression[callee.name='voila']",
+                "Identifier[name='oops']"
+            ])),
+        ),
+        ("bar;", Some(serde_json::json!(["Identifier[name=\"bar\"]"]))),
+        ("bar;", Some(serde_json::json!(["Identifier", "Identifier[name=\"bar\"]"]))),
+        ("() => {}", Some(serde_json::json!(["ArrowFunctionExpression > BlockStatement"]))),
+        ("({ foo: 1, 'bar': 2 })", Some(serde_json::json!(["Property > Literal.key"]))),
+        ("A: for (;;) break A;", Some(serde_json::json!(["BreakStatement[label]"]))),
+        (
+            "function foo(bar, baz, qux) {}",
+            Some(serde_json::json!(["FunctionDeclaration[params.length>2]"])),
+        ),
+        (
+            "var foo = 42;",
+            Some(
+                serde_json::json!([{ "selector": "VariableDeclaration[kind='var']", "message": "Use `let` or `const` instead of `var`." }]),
+            ),
+        ),
+        ("with (me) { dontMess(); }", Some(serde_json::json!([{ "selector": "WithStatement" }]))),
+        (
+            "setTimeout(callback);",
+            Some(
+                serde_json::json!([{ "selector": "CallExpression[callee.name='setTimeout'][arguments.length!=2]", "message": "setTimeout must always be invoked with two arguments." }]),
+            ),
+        ),
+        ("if (a) { b(); }", Some(serde_json::json!([":statement:has(CallExpression)"]))),
+        ("foo(1, 2); bar(3);", Some(serde_json::json!(["CallExpression > Literal:last-child"]))),
+    ];
+
+    Tester::new(NoRestrictedSyntax::NAME, pass, fail).test_and_snapshot();
+}
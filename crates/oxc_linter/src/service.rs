@@ -13,16 +13,23 @@ use oxc_diagnostics::{DiagnosticSender, DiagnosticService, Error, OxcDiagnostic}
 use oxc_parser::{ParseOptions, Parser};
 use oxc_resolver::Resolver;
 use oxc_semantic::{ModuleRecord, SemanticBuilder};
-use oxc_span::{SourceType, VALID_EXTENSIONS};
+use oxc_span::{SourceType, Span, VALID_EXTENSIONS};
 use rayon::{iter::ParallelBridge, prelude::ParallelIterator};
 use rustc_hash::{FxHashMap, FxHashSet};
 
 use crate::{
+    baseline::Baseline,
+    cache::LintCache,
+    config::{ConfigDiscovery, DiscoveredConfig},
     loader::{JavaScriptSource, PartialLoader, LINT_PARTIAL_LOADER_EXT},
     utils::read_to_string,
     Fixer, Linter, Message,
 };
 
+/// Maximum number of times fixes are applied to a file. Each pass re-lints the
+/// code fixed by the previous pass.
+const MAX_FIX_PASSES: usize = 10;
+
 pub struct LintServiceOptions {
     /// Current working directory
     cwd: Box<Path>,
@@ -34,6 +41,18 @@ pub struct LintServiceOptions {
     tsconfig: Option<PathBuf>,
 
     cross_module: bool,
+
+    /// Look up `.oxlintrc.json` files for each linted file
+    config_discovery: Option<Arc<ConfigDiscovery>>,
+
+    /// Suppress or record known diagnostics
+    baseline: Option<Baseline>,
+
+    /// Replay diagnostics of unchanged files
+    cache: Option<LintCache>,
+
+    /// Keep fixed files in memory instead of writing them to disk
+    fix_dry_run: bool,
 }
 
 impl LintServiceOptions {
@@ -42,7 +61,16 @@ impl LintServiceOptions {
     where
         T: Into<Box<Path>>,
     {
-        Self { cwd: cwd.into(), paths, tsconfig: None, cross_module: false }
+        Self {
+            cwd: cwd.into(),
+            paths,
+            tsconfig: None,
+            cross_module: false,
+            config_discovery: None,
+            baseline: None,
+            cache: None,
+            fix_dry_run: false,
+        }
     }
 
     #[inline]
@@ -67,12 +95,88 @@ impl LintServiceOptions {
         self
     }
 
+    /// Resolve configuration per file from `.oxlintrc.json` files found next to
+    /// linted files and in their parent directories.
+    #[inline]
+    #[must_use]
+    pub fn with_config_discovery(mut self, config_discovery: Arc<ConfigDiscovery>) -> Self {
+        self.config_discovery = Some(config_discovery);
+        self
+    }
+
+    /// Only report diagnostics that are not in `baseline`, or record them all
+    /// into it. Call [`Baseline::save`] after linting to write it.
+    #[inline]
+    #[must_use]
+    pub fn with_baseline(mut self, baseline: Baseline) -> Self {
+        self.baseline = Some(baseline);
+        self
+    }
+
+    /// Replay the diagnostics of files that did not change since the last run,
+    /// and cache the diagnostics of all other files. Call [`LintCache::save`]
+    /// after linting to write it.
+    ///
+    /// The cache is not used when fixes are applied.
+    #[inline]
+    #[must_use]
+    pub fn with_cache(mut self, cache: LintCache) -> Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    /// Do not write fixed files to disk, collect them with
+    /// [`LintService::take_fixed_files`] instead.
+    #[inline]
+    #[must_use]
+    pub fn with_fix_dry_run(mut self, yes: bool) -> Self {
+        self.fix_dry_run = yes;
+        self
+    }
+
     #[inline]
     pub fn cwd(&self) -> &Path {
         &self.cwd
     }
 }
 
+/// A file fixed by [`LintService`] with [`LintServiceOptions::with_fix_dry_run`].
+#[derive(Debug)]
+pub struct FixedFile {
+    /// Path relative to the current working directory
+    pub path: PathBuf,
+    pub source_text: String,
+    pub fixed_source_text: String,
+    pub fixes: Vec<AppliedFix>,
+}
+
+/// A fix applied to a [`FixedFile`].
+#[derive(Debug)]
+pub struct AppliedFix {
+    /// The pass that applied the fix, starting at 1.
+    pub pass: usize,
+    /// Error code of the fixed diagnostic, e.g. `eslint(no-debugger)`
+    pub code: String,
+    pub message: String,
+    /// The replaced span, relative to the contents of the file before the
+    /// pass that applied the fix.
+    pub span: Span,
+    pub content: String,
+}
+
+impl AppliedFix {
+    fn new(pass: usize, offset: u32, message: &Message) -> Option<Self> {
+        let fix = message.fix.as_ref()?;
+        Some(Self {
+            pass,
+            code: message.error.code.to_string(),
+            message: message.error.message.to_string(),
+            span: Span::new(offset + fix.span.start, offset + fix.span.end),
+            content: fix.content.to_string(),
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct LintService {
     runtime: Arc<Runtime>,
@@ -94,6 +198,28 @@ impl LintService {
         &self.runtime.linter
     }
 
+    pub fn baseline(&self) -> Option<&Baseline> {
+        self.runtime.baseline.as_ref()
+    }
+
+    pub fn cache(&self) -> Option<&LintCache> {
+        self.runtime.cache.as_ref()
+    }
+
+    /// Take the files fixed during a dry run, sorted by path.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a linting thread panicked while holding the lock.
+    pub fn take_fixed_files(&self) -> Vec<FixedFile> {
+        let Some(fixed_files) = &self.runtime.fixed_files else {
+            return vec![];
+        };
+        let mut fixed_files = std::mem::take(&mut *fixed_files.lock().unwrap());
+        fixed_files.sort_unstable_by(|a, b| a.path.cmp(&b.path));
+        fixed_files
+    }
+
     pub fn number_of_dependencies(&self) -> usize {
         self.runtime.module_map.len() - self.runtime.paths.len()
     }
@@ -129,6 +255,7 @@ impl LintService {
                     source_text,
                     source_type,
                     check_syntax_errors,
+                    true,
                     tx_error,
                 )
             })
@@ -170,6 +297,13 @@ pub struct Runtime {
     resolver: Option<Resolver>,
     module_map: ModuleMap,
     cache_state: CacheState,
+    /// Discovers `.oxlintrc.json` files for linted files, if enabled.
+    config_discovery: Option<Arc<ConfigDiscovery>>,
+    baseline: Option<Baseline>,
+    /// Diagnostics of previous runs. Not used when fixes are applied.
+    cache: Option<LintCache>,
+    /// Files fixed during a dry run
+    fixed_files: Option<Mutex<Vec<FixedFile>>>,
 }
 
 impl Runtime {
@@ -180,10 +314,14 @@ impl Runtime {
         Self {
             cwd: options.cwd,
             paths: options.paths.iter().cloned().collect(),
-            linter,
             resolver,
             module_map: ModuleMap::default(),
             cache_state: CacheState::default(),
+            config_discovery: options.config_discovery,
+            baseline: options.baseline,
+            cache: options.cache.filter(|_| linter.options().fix.is_none()),
+            fixed_files: options.fix_dry_run.then(Mutex::default),
+            linter,
         }
     }
 
@@ -262,6 +400,23 @@ impl Runtime {
             return;
         }
 
+        // If results are cached, hash the file and its configuration and look up
+        // the diagnostics of the previous run. Modules that are only resolved for
+        // cross-module rules are not cached.
+        let cache_hash = self.cache.as_ref().filter(|_| self.paths.contains(path)).map(|_| {
+            let discovered = self.discovered_config(path).ok().flatten();
+            LintCache::file_hash(&source_text, discovered.as_deref().map(DiscoveredConfig::hash))
+        });
+        let mut cached = self
+            .cache
+            .as_ref()
+            .zip(cache_hash)
+            .and_then(|(cache, hash)| cache.get(path, hash))
+            .filter(|cached| cached.len() == sources.len())
+            .map(Vec::into_iter);
+        let mut uncached_diagnostics = vec![];
+        let mut module_record = None;
+
         // If there are fixes, we will accumulate all of them and write to the file at the end.
         // This means we do not write multiple times to the same file if there are multiple sources
         // in the same file (for example, multiple scripts in an `.astro` file).
@@ -270,47 +425,134 @@ impl Runtime {
         // Otherwise, spans for fixes will be incorrect due to varying size of the
         // source code after each fix.
         let mut fix_offset: i32 = 0;
+        // Fixes applied to this file, only recorded for dry runs.
+        let mut applied_fixes = vec![];
 
         for source in sources {
-            let allocator = Allocator::default();
-            let mut messages = self.process_source(
-                path,
-                &allocator,
-                source.source_text,
-                source_type,
-                true,
-                tx_error,
-            );
+            let start = source.start.saturating_add_signed(fix_offset);
+            // Fixes may overlap or enable other fixes, so keep re-linting the
+            // fixed code and applying fixes until no fix applies.
+            let mut fixed_source_text = Cow::Borrowed(source.source_text);
+            let mut pass = 0;
+            loop {
+                // Everything that borrows `fixed_source_text` is dropped at the end
+                // of this block, before it is replaced with the fixed code.
+                let fixed_code = {
+                    let allocator = Allocator::default();
+                    let mut messages = if let Some(cached) = cached.as_mut() {
+                        if self.resolver.is_some() {
+                            // Other modules may depend on the module record of this file.
+                            self.process_source(
+                                path,
+                                &allocator,
+                                source.source_text,
+                                source_type,
+                                true,
+                                false,
+                                tx_error,
+                            );
+                        }
+                        cached.next().unwrap_or_default()
+                    } else {
+                        let messages = self.process_source(
+                            path,
+                            &allocator,
+                            &fixed_source_text,
+                            source_type,
+                            true,
+                            true,
+                            tx_error,
+                        );
+                        if cache_hash.is_some() {
+                            uncached_diagnostics.push(
+                                messages.iter().map(|message| message.error.clone()).collect(),
+                            );
+                            // Read it now, `ignore_path` below replaces it in the module map.
+                            module_record = self.module_record(path);
+                        }
+                        messages
+                    };
+
+                    let mut fixed_code = None;
+                    if self.linter.options().fix.is_some() && pass < MAX_FIX_PASSES {
+                        let fix_result = Fixer::new(&fixed_source_text, messages).fix();
+                        messages = fix_result.messages;
+                        if fix_result.fixed {
+                            pass += 1;
+                            if self.fixed_files.is_some() {
+                                applied_fixes.extend(
+                                    fix_result.fixed_messages.iter().filter_map(|message| {
+                                        AppliedFix::new(pass, start, message)
+                                    }),
+                                );
+                            }
+                            fixed_code = Some(fix_result.fixed_code.into_owned());
+                        }
+                    }
+                    // The remaining diagnostics of a fixed source are reported after
+                    // re-linting the fixed code.
+                    if fixed_code.is_none() {
+                        self.report(path, &fixed_source_text, messages, tx_error);
+                    }
+                    fixed_code
+                };
 
-            if self.linter.options().fix.is_some() {
-                let fix_result = Fixer::new(source.source_text, messages).fix();
-                if fix_result.fixed {
-                    // write to file, replacing only the changed part
-                    let start = source.start.saturating_add_signed(fix_offset) as usize;
-                    let end = start + source.source_text.len();
-                    new_source_text.to_mut().replace_range(start..end, &fix_result.fixed_code);
-                    let old_code_len = source.source_text.len() as u32;
-                    let new_code_len = fix_result.fixed_code.len() as u32;
-                    fix_offset += new_code_len as i32;
-                    fix_offset -= old_code_len as i32;
+                match fixed_code {
+                    Some(fixed_code) => fixed_source_text = Cow::Owned(fixed_code),
+                    None => break,
                 }
-                messages = fix_result.messages;
             }
 
-            if !messages.is_empty() {
-                self.ignore_path(path);
-                let errors = messages.into_iter().map(Into::into).collect();
-                let path = path.strip_prefix(&self.cwd).unwrap_or(path);
-                let diagnostics =
-                    DiagnosticService::wrap_diagnostics(path, source.source_text, errors);
-                tx_error.send(Some(diagnostics)).unwrap();
+            if let Cow::Owned(fixed_source_text) = fixed_source_text {
+                // replace only the changed part
+                let start = start as usize;
+                let end = start + source.source_text.len();
+                new_source_text.to_mut().replace_range(start..end, &fixed_source_text);
+                fix_offset += fixed_source_text.len() as i32;
+                fix_offset -= source.source_text.len() as i32;
             }
         }
 
+        if let (Some(cache), Some(hash), None) = (&self.cache, cache_hash, cached) {
+            cache.insert(path, hash, module_record.as_deref(), &uncached_diagnostics);
+        }
+
         // If the new source text is owned, that means it was modified,
-        // so we write the new source text to the file.
+        // so we write the new source text to the file, or keep it for dry runs.
         if let Cow::Owned(new_source_text) = new_source_text {
-            fs::write(path, new_source_text).unwrap();
+            match &self.fixed_files {
+                Some(fixed_files) => fixed_files.lock().unwrap().push(FixedFile {
+                    path: path.strip_prefix(&self.cwd).unwrap_or(path).to_path_buf(),
+                    source_text,
+                    fixed_source_text: new_source_text,
+                    fixes: applied_fixes,
+                }),
+                None => fs::write(path, new_source_text).unwrap(),
+            }
+        }
+    }
+
+    /// Send the diagnostics of a fully fixed source, or of a source that could
+    /// not be fixed any further, to the diagnostic service.
+    fn report(
+        &self,
+        path: &Path,
+        source_text: &str,
+        mut messages: Vec<Message>,
+        tx_error: &DiagnosticSender,
+    ) {
+        // Modules only resolved for cross-module rules are not linted, and
+        // must not prune their entries in the baseline.
+        if let Some(baseline) = self.baseline.as_ref().filter(|_| self.paths.contains(path)) {
+            messages = baseline.apply(path, source_text, messages);
+        }
+
+        if !messages.is_empty() {
+            self.ignore_path(path);
+            let errors = messages.into_iter().map(Into::into).collect();
+            let path = path.strip_prefix(&self.cwd).unwrap_or(path);
+            let diagnostics = DiagnosticService::wrap_diagnostics(path, source_text, errors);
+            tx_error.send(Some(diagnostics)).unwrap();
         }
     }
 
@@ -322,6 +564,7 @@ impl Runtime {
         source_text: &'a str,
         source_type: SourceType,
         check_syntax_errors: bool,
+        lint: bool,
         tx_error: &DiagnosticSender,
     ) -> Vec<Message<'a>> {
         let ret = Parser::new(allocator, source_text, source_type)
@@ -417,7 +660,7 @@ impl Runtime {
             }
 
             // Stop if the current module is not marked for lint.
-            if !self.paths.contains(path) {
+            if !lint || !self.paths.contains(path) {
                 return vec![];
             }
         }
@@ -428,7 +671,32 @@ impl Runtime {
             return semantic_ret.errors.into_iter().map(|err| Message::new(err, None)).collect();
         };
 
-        self.linter.run(path, Rc::new(semantic_ret.semantic))
+        let semantic = Rc::new(semantic_ret.semantic);
+        match self.discovered_config(path) {
+            Ok(discovered) => {
+                self.linter.run_with_discovered(path, semantic, discovered.as_deref(), &self.cwd)
+            }
+            Err(error) => vec![Message::new(error, None)],
+        }
+    }
+
+    /// Get the configuration discovered for `path`, if configuration discovery
+    /// is enabled and a configuration file is found.
+    fn discovered_config(
+        &self,
+        path: &Path,
+    ) -> Result<Option<Arc<DiscoveredConfig>>, OxcDiagnostic> {
+        match (&self.config_discovery, self.cwd.join(path).parent()) {
+            (Some(discovery), Some(dir)) => discovery.config_for(dir),
+            _ => Ok(None),
+        }
+    }
+
+    fn module_record(&self, path: &Path) -> Option<Arc<ModuleRecord>> {
+        match self.module_map.get(path)?.value() {
+            ModuleState::Resolved(module_record) => Some(Arc::clone(module_record)),
+            ModuleState::Ignored => None,
+        }
     }
 
     fn init_cache_state(&self, path: &Path) -> bool {
@@ -10,7 +10,10 @@ mod tree_shaking;
 mod unicorn;
 mod vitest;
 
-use std::{io, path::Path};
+use std::{fs, io, path::Path};
+
+use oxc_diagnostics::OxcDiagnostic;
+use serde::Serialize;
 
 pub use self::{
     config::*, express::*, jest::*, jsdoc::*, nextjs::*, promise::*, react::*, react_perf::*,
@@ -54,3 +57,41 @@ pub fn read_to_string(path: &Path) -> io::Result<String> {
     // SAFETY: `simdutf8` has ensured it's a valid UTF-8 string
     Ok(unsafe { String::from_utf8_unchecked(bytes) })
 }
+
+/// 64-bit FNV-1a hash of `bytes`.
+///
+/// Unlike the hashers of `std`, this is stable across Rust versions and
+/// platforms, so it can be used for hashes that are written to disk.
+pub fn stable_hash<I: IntoIterator<Item = u8>>(bytes: I) -> u64 {
+    const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+    bytes
+        .into_iter()
+        .fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME))
+}
+
+/// Serialize `value` as JSON and write it to `path`. Pretty-printed JSON ends
+/// with a newline, so that it can be checked in.
+///
+/// `name` describes the file in error messages, e.g. `"cache"`.
+///
+/// # Errors
+///
+/// Returns `Err` if `value` cannot be serialized or the file cannot be written.
+pub fn write_json<T: Serialize>(
+    path: &Path,
+    value: &T,
+    pretty: bool,
+    name: &str,
+) -> Result<(), OxcDiagnostic> {
+    let error = |err: &dyn std::fmt::Display| {
+        OxcDiagnostic::error(format!("Failed to write {name} {path:?}: {err}"))
+    };
+    let json = if pretty {
+        serde_json::to_string_pretty(value).map(|json| json + "\n")
+    } else {
+        serde_json::to_string(value)
+    };
+    fs::write(path, json.map_err(|err| error(&err))?).map_err(|err| error(&err))
+}
@@ -1,7 +1,7 @@
 use itertools::Itertools;
 use oxc_ast::ast::Program;
 use oxc_index::{index_vec, Idx, IndexVec};
-use oxc_semantic::{ReferenceId, ScopeTree, SemanticBuilder, SymbolId, SymbolTable};
+use oxc_semantic::{ReferenceId, ScopeId, ScopeTree, SemanticBuilder, SymbolId, SymbolTable};
 use oxc_span::CompactStr;
 
 type Slot = usize;
@@ -9,6 +9,9 @@ type Slot = usize;
 #[derive(Default)]
 pub struct MangleOptions {
     pub debug: bool,
+
+    /// Keep the scope tree for [Mangler::get_scope_bindings], e.g. to emit the scopes of a source map.
+    pub scope_bindings: bool,
 }
 
 /// # Name Mangler / Symbol Minification
@@ -56,6 +59,9 @@ pub struct MangleOptions {
 pub struct Mangler {
     symbol_table: SymbolTable,
 
+    /// Only kept with [MangleOptions::scope_bindings].
+    scope_tree: Option<ScopeTree>,
+
     options: MangleOptions,
 }
 
@@ -80,6 +86,21 @@ impl Mangler {
         Some(self.symbol_table.get_name(symbol_id))
     }
 
+    /// Get the bindings declared in a scope, as pairs of their original and mangled names.
+    ///
+    /// Returns `None` unless built with [MangleOptions::scope_bindings].
+    pub fn get_scope_bindings(
+        &self,
+        scope_id: ScopeId,
+    ) -> Option<impl ExactSizeIterator<Item = (&str, &str)> + '_> {
+        let bindings = self.scope_tree.as_ref()?.get_bindings(scope_id);
+        Some(
+            bindings
+                .iter()
+                .map(|(name, symbol_id)| (name.as_str(), self.symbol_table.get_name(*symbol_id))),
+        )
+    }
+
     #[must_use]
     pub fn build<'a>(mut self, program: &'a Program<'a>) -> Mangler {
         let semantic = SemanticBuilder::new("").build(program).semantic;
@@ -197,6 +218,7 @@ impl Mangler {
         }
 
         self.symbol_table = symbol_table;
+        self.scope_tree = self.options.scope_bindings.then_some(scope_tree);
         self
     }
 
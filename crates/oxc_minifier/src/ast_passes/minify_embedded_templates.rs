@@ -0,0 +1,200 @@
+use oxc_ast::{ast::*, Trivias};
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_span::{GetSpan, Span};
+use oxc_traverse::{Traverse, TraverseCtx};
+
+use crate::{
+    embedded::{minify_quasis, EmbeddedLanguage},
+    options::EmbeddedTemplateOptions,
+    CompressorPass,
+};
+
+/// Minify CSS, HTML and GraphQL embedded in template literals.
+///
+/// * Tagged templates whose tag is listed in [EmbeddedTemplateOptions], e.g. ``css`a { b: c }` ``
+/// * Template literals preceded by a comment hint, e.g. ``/* css */ `a { b: c }` ``
+///
+/// Templates which cannot be minified without changing their meaning are left untouched and
+/// reported as warnings.
+pub struct MinifyEmbeddedTemplates {
+    options: EmbeddedTemplateOptions,
+    /// Start positions of template literals preceded by a comment hint, sorted by position.
+    hints: std::vec::Vec<(u32, EmbeddedLanguage)>,
+    diagnostics: std::vec::Vec<OxcDiagnostic>,
+    changed: bool,
+}
+
+impl<'a> CompressorPass<'a> for MinifyEmbeddedTemplates {
+    fn changed(&self) -> bool {
+        self.changed
+    }
+
+    fn build(&mut self, program: &mut Program<'a>, ctx: &mut TraverseCtx<'a>) {
+        self.changed = false;
+        oxc_traverse::walk_program(self, program, ctx);
+    }
+}
+
+impl<'a> Traverse<'a> for MinifyEmbeddedTemplates {
+    fn enter_expression(&mut self, expr: &mut Expression<'a>, ctx: &mut TraverseCtx<'a>) {
+        match expr {
+            Expression::TaggedTemplateExpression(e) => {
+                if let Some(language) = self.tag_language(&e.tag) {
+                    self.minify_template(language, &mut e.quasi, ctx);
+                }
+            }
+            Expression::TemplateLiteral(e) => {
+                if let Ok(index) = self.hints.binary_search_by_key(&e.span.start, |hint| hint.0) {
+                    self.minify_template(self.hints[index].1, e, ctx);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> MinifyEmbeddedTemplates {
+    pub fn new(options: EmbeddedTemplateOptions) -> Self {
+        Self { options, hints: vec![], diagnostics: vec![], changed: false }
+    }
+
+    /// Find the template literals preceded by a `/* css */`, `/* html */` or `/* graphql */` comment.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn with_comment_hints(mut self, source_text: &str, trivias: &Trivias) -> Self {
+        self.hints = trivias
+            .comments()
+            .filter(|comment| comment.is_block())
+            .filter_map(|comment| {
+                let language = EmbeddedLanguage::from_hint(comment.span.source_text(source_text))?;
+                let end = comment.real_span_end() as usize;
+                let offset = source_text.get(end..)?.find(|c: char| !c.is_whitespace())?;
+                source_text[end + offset..]
+                    .starts_with('`')
+                    .then(|| ((end + offset) as u32, language))
+            })
+            .collect();
+        self
+    }
+
+    pub fn into_diagnostics(self) -> std::vec::Vec<OxcDiagnostic> {
+        self.diagnostics
+    }
+
+    fn tag_language(&self, tag: &Expression<'a>) -> Option<EmbeddedLanguage> {
+        let name = Self::get_tag_name(tag)?;
+        let matches = |tags: &[String]| {
+            tags.iter().any(|tag| match tag.strip_suffix('*') {
+                Some(prefix) if prefix.ends_with('.') => name.starts_with(prefix),
+                _ => *tag == name,
+            })
+        };
+        if matches(&self.options.css_tags) {
+            Some(EmbeddedLanguage::Css)
+        } else if matches(&self.options.html_tags) {
+            Some(EmbeddedLanguage::Html)
+        } else if matches(&self.options.graphql_tags) {
+            Some(EmbeddedLanguage::GraphQL)
+        } else {
+            None
+        }
+    }
+
+    /// `css`, `styled.div`, and the callee of calls such as `styled(Button)`.
+    fn get_tag_name(expr: &Expression<'a>) -> Option<String> {
+        match expr {
+            Expression::Identifier(ident) => Some(ident.name.to_string()),
+            Expression::StaticMemberExpression(member_expr) => {
+                let object = Self::get_tag_name(&member_expr.object)?;
+                Some(format!("{object}.{}", member_expr.property.name))
+            }
+            Expression::CallExpression(call_expr) => Self::get_tag_name(&call_expr.callee),
+            _ => None,
+        }
+    }
+
+    fn minify_template(
+        &mut self,
+        language: EmbeddedLanguage,
+        template: &mut TemplateLiteral<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        let quasis = template
+            .quasis
+            .iter()
+            .map(|quasi| quasi.value.raw.as_str())
+            .collect::<std::vec::Vec<_>>();
+        let minified = match minify_quasis(language, &quasis) {
+            Ok(minified) => minified,
+            Err(reason) => {
+                self.diagnostics.push(Self::cannot_minify(language, reason, template.span()));
+                return;
+            }
+        };
+        for (quasi, text) in template.quasis.iter_mut().zip(minified) {
+            if quasi.value.raw != text.as_str() {
+                let text = ctx.ast.atom(&text);
+                quasi.value.raw = text.clone();
+                quasi.value.cooked = Some(text);
+                self.changed = true;
+            }
+        }
+    }
+
+    fn cannot_minify(language: EmbeddedLanguage, reason: &str, span: Span) -> OxcDiagnostic {
+        OxcDiagnostic::warn(format!("Cannot minify embedded {}: {reason}", language.as_str()))
+            .with_label(span)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+
+    use crate::{options::EmbeddedTemplateOptions, tester};
+
+    fn test(source_text: &str, expected: &str) {
+        let allocator = Allocator::default();
+        let mut pass = super::MinifyEmbeddedTemplates::new(EmbeddedTemplateOptions::default());
+        tester::test(&allocator, source_text, expected, &mut pass);
+    }
+
+    fn test_same(source_text: &str) {
+        test(source_text, source_text);
+    }
+
+    #[test]
+    fn css() {
+        test("css`\n  color: ${color};\n  margin: 0;\n`", "css`color:${color};margin:0;`");
+        test("styled.div`\n  a { b: c; }\n`", "styled.div`a{b:c}`");
+        test("styled(Button)`\n  a { b: c; }\n`", "styled(Button)`a{b:c}`");
+        test("styled.div.attrs({})`\n  a { b: c; }\n`", "styled.div.attrs({})`a{b:c}`");
+        test_same("styledx.div`\n  a { b: c; }\n`");
+        test_same("`\n  a { b: c; }\n`");
+    }
+
+    #[test]
+    fn html() {
+        test(
+            "html`\n  <ul>\n    ${items.map((i) => html`<li>  ${i}  </li>`)}\n  </ul>\n`",
+            "html`<ul> ${items.map((i) => html`<li> ${i} </li>`)} </ul>`",
+        );
+        test("html`<input .value=${v} @input=${f} />`", "html`<input .value=${v} @input=${f}/>`");
+    }
+
+    #[test]
+    fn graphql() {
+        test(
+            "gql`\n  query {\n    user(id: ${id}) { name }\n  }\n  ${fragment}\n`",
+            "gql`query{user(id:${id}){name}}${fragment}`",
+        );
+    }
+
+    #[test]
+    fn bail() {
+        // A substitution inside a comment.
+        test_same("css`a { /* ${b} */ }`");
+        // Escape sequences.
+        test_same("css`a { content: '\\\\f101'; }`");
+    }
+}
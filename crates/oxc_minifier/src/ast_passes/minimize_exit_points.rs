@@ -0,0 +1,866 @@
+use oxc_allocator::{CloneIn, Vec};
+use oxc_ast::{ast::*, Visit};
+use oxc_span::{Atom, SPAN};
+use oxc_syntax::{
+    operator::{BinaryOperator, LogicalOperator, UnaryOperator},
+    scope::ScopeFlags,
+};
+use oxc_traverse::{Traverse, TraverseCtx};
+
+use crate::{node_util::IsLiteralValue, CompressorPass};
+
+/// Minimize Exit Points
+///
+/// Transform the structure of the AST so that the number of explicit exits
+/// are minimized and instead flows to implicit exits conditions.
+///
+/// Also merges `if (a) return x; return y;` into `return a ? x : y`,
+/// and rewrites simple `switch` statements into `if` chains or conditional expressions.
+///
+/// <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/MinimizeExitPoints.java>
+pub struct MinimizeExitPoints {
+    changed: bool,
+}
+
+/// The kind of exit which is redundant at the end of the statement being minimized.
+enum ExitType<'a> {
+    /// `return;` at the end of a function body.
+    Return,
+    /// `continue;` or `continue label;` at the end of a loop body.
+    Continue(Option<Atom<'a>>),
+    /// `break;` at the end of the last switch case, or `break label;` at the end of a labeled statement.
+    Break(Option<Atom<'a>>),
+}
+
+impl<'a> CompressorPass<'a> for MinimizeExitPoints {
+    fn changed(&self) -> bool {
+        self.changed
+    }
+
+    fn build(&mut self, program: &mut Program<'a>, ctx: &mut TraverseCtx<'a>) {
+        self.changed = false;
+        oxc_traverse::walk_program(self, program, ctx);
+    }
+}
+
+impl<'a> Traverse<'a> for MinimizeExitPoints {
+    fn exit_function_body(&mut self, body: &mut FunctionBody<'a>, ctx: &mut TraverseCtx<'a>) {
+        // Bail `if (key === "default" || key === "__esModule") return;` for `cjs-module-lexer`.
+        if body.statements.first().is_some_and(Self::is_cjs_reexport_guard) {
+            return;
+        }
+        self.try_minimize_exits_in_statements(&mut body.statements, &ExitType::Return, ctx);
+    }
+
+    fn exit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>, ctx: &mut TraverseCtx<'a>) {
+        self.try_merge_if_returns(stmts, ctx);
+    }
+
+    fn exit_statement(&mut self, stmt: &mut Statement<'a>, ctx: &mut TraverseCtx<'a>) {
+        match stmt {
+            Statement::DoWhileStatement(do_stmt) => {
+                self.try_minimize_exits(&mut do_stmt.body, &ExitType::Continue(None), ctx);
+            }
+            Statement::WhileStatement(while_stmt) => {
+                self.try_minimize_exits(&mut while_stmt.body, &ExitType::Continue(None), ctx);
+            }
+            Statement::ForStatement(for_stmt) => {
+                self.try_minimize_exits(&mut for_stmt.body, &ExitType::Continue(None), ctx);
+            }
+            Statement::ForInStatement(for_in_stmt) => {
+                self.try_minimize_exits(&mut for_in_stmt.body, &ExitType::Continue(None), ctx);
+            }
+            Statement::ForOfStatement(for_of_stmt) => {
+                self.try_minimize_exits(&mut for_of_stmt.body, &ExitType::Continue(None), ctx);
+            }
+            Statement::LabeledStatement(labeled_stmt) => {
+                let label = labeled_stmt.label.name.clone();
+                if let Some(body) = Self::loop_body_mut(&mut labeled_stmt.body) {
+                    self.try_minimize_exits(body, &ExitType::Continue(Some(label.clone())), ctx);
+                }
+                self.try_minimize_exits(&mut labeled_stmt.body, &ExitType::Break(Some(label)), ctx);
+            }
+            Statement::SwitchStatement(switch_stmt) => {
+                if let Some(last_case) = switch_stmt.cases.last_mut() {
+                    self.try_minimize_exits_in_statements(
+                        &mut last_case.consequent,
+                        &ExitType::Break(None),
+                        ctx,
+                    );
+                }
+                if let Some(new_stmt) = self.try_simplify_switch(switch_stmt, ctx) {
+                    *stmt = new_stmt;
+                    self.changed = true;
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'a> MinimizeExitPoints {
+    pub fn new() -> Self {
+        Self { changed: false }
+    }
+
+    fn is_cjs_reexport_guard(stmt: &Statement<'a>) -> bool {
+        let Statement::IfStatement(if_stmt) = stmt else { return false };
+        let Expression::LogicalExpression(logical_expr) = &if_stmt.test else { return false };
+        matches!(
+            &logical_expr.right,
+            Expression::BinaryExpression(binary_expr)
+                if matches!(&binary_expr.right, Expression::StringLiteral(lit) if lit.value == "__esModule")
+        )
+    }
+
+    fn loop_body_mut<'b>(stmt: &'b mut Statement<'a>) -> Option<&'b mut Statement<'a>> {
+        match stmt {
+            Statement::DoWhileStatement(s) => Some(&mut s.body),
+            Statement::WhileStatement(s) => Some(&mut s.body),
+            Statement::ForStatement(s) => Some(&mut s.body),
+            Statement::ForInStatement(s) => Some(&mut s.body),
+            Statement::ForOfStatement(s) => Some(&mut s.body),
+            _ => None,
+        }
+    }
+
+    fn is_matching_exit(stmt: &Statement<'a>, exit_type: &ExitType<'a>) -> bool {
+        match (stmt, exit_type) {
+            (Statement::ReturnStatement(return_stmt), ExitType::Return) => {
+                return_stmt.argument.is_none()
+            }
+            (Statement::ContinueStatement(continue_stmt), ExitType::Continue(label)) => {
+                match &continue_stmt.label {
+                    None => true,
+                    Some(l) => label.as_ref().is_some_and(|label| *label == l.name),
+                }
+            }
+            (Statement::BreakStatement(break_stmt), ExitType::Break(label)) => {
+                match (&break_stmt.label, label) {
+                    (None, None) => true,
+                    (Some(l), Some(label)) => *label == l.name,
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn is_empty_statement(stmt: &Statement<'a>) -> bool {
+        match stmt {
+            Statement::EmptyStatement(_) => true,
+            Statement::BlockStatement(block) => block.body.is_empty(),
+            _ => false,
+        }
+    }
+
+    /// Whether the statement can be moved into a nested block without changing its scope.
+    fn is_movable_into_block(stmt: &Statement<'a>) -> bool {
+        match stmt {
+            Statement::VariableDeclaration(decl) => decl.kind.is_var(),
+            Statement::FunctionDeclaration(_) | Statement::ClassDeclaration(_) => false,
+            _ => !stmt.is_module_declaration(),
+        }
+    }
+
+    /// Try to remove the exit of type `exit_type` from the end of `stmt`,
+    /// where `stmt` is already in an exit position.
+    fn try_minimize_exits(
+        &mut self,
+        stmt: &mut Statement<'a>,
+        exit_type: &ExitType<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        if Self::is_matching_exit(stmt, exit_type) {
+            *stmt = ctx.ast.statement_empty(SPAN);
+            self.changed = true;
+            return;
+        }
+        match stmt {
+            Statement::IfStatement(if_stmt) => {
+                self.try_minimize_exits(&mut if_stmt.consequent, exit_type, ctx);
+                if let Some(alternate) = &mut if_stmt.alternate {
+                    self.try_minimize_exits(alternate, exit_type, ctx);
+                }
+                self.try_remove_empty_branches(stmt, ctx);
+            }
+            Statement::TryStatement(try_stmt) => {
+                self.try_minimize_exits_in_statements(&mut try_stmt.block.body, exit_type, ctx);
+                if let Some(handler) = &mut try_stmt.handler {
+                    self.try_minimize_exits_in_statements(&mut handler.body.body, exit_type, ctx);
+                }
+                // Don't minimize the exits of `finally` blocks, as this can change the completion
+                // type of the `finally` block. See ECMA 262 Sections 8.9 & 12.14
+            }
+            Statement::LabeledStatement(labeled_stmt) => {
+                self.try_minimize_exits(&mut labeled_stmt.body, exit_type, ctx);
+            }
+            Statement::BlockStatement(block) => {
+                self.try_minimize_exits_in_statements(&mut block.body, exit_type, ctx);
+            }
+            _ => {}
+        }
+    }
+
+    fn try_minimize_exits_in_statements(
+        &mut self,
+        stmts: &mut Vec<'a, Statement<'a>>,
+        exit_type: &ExitType<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        // Multiple if-exits can be converted in a single pass.
+        let mut i = 0;
+        while i + 1 < stmts.len() {
+            self.try_minimize_if_block_exits(stmts, i, exit_type, ctx);
+            i += 1;
+        }
+
+        // Now try to minimize the exits of the last statement,
+        // if it is removed then look at what has become the last statement.
+        while let Some(last) = stmts.last_mut() {
+            self.try_minimize_exits(last, exit_type, ctx);
+            if !matches!(stmts.last(), Some(Statement::EmptyStatement(_))) {
+                break;
+            }
+            stmts.pop();
+        }
+    }
+
+    /// Look for exits (returns, breaks, or continues, depending on the context) at the end of
+    /// an `if` branch and move the statements following the `if` into the other branch:
+    ///
+    /// `if (x) { a(); return } b()` -> `if (x) { a() } else { b() }`
+    fn try_minimize_if_block_exits(
+        &mut self,
+        stmts: &mut Vec<'a, Statement<'a>>,
+        index: usize,
+        exit_type: &ExitType<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        if !stmts.iter().skip(index + 1).all(Self::is_movable_into_block) {
+            return;
+        }
+        let Some(Statement::IfStatement(if_stmt)) = stmts.get_mut(index) else { return };
+
+        // The statements following the `if` must stay reachable from the other branch.
+        let move_into_alternate = if Self::ends_with_exit(&if_stmt.consequent, exit_type)
+            && !if_stmt.alternate.as_ref().is_some_and(Self::ends_with_jump)
+        {
+            true
+        } else if if_stmt.alternate.as_ref().is_some_and(|alt| Self::ends_with_exit(alt, exit_type))
+            && !Self::ends_with_jump(&if_stmt.consequent)
+        {
+            false
+        } else {
+            return;
+        };
+        if move_into_alternate {
+            Self::remove_trailing_exit(&mut if_stmt.consequent, ctx);
+        } else {
+            Self::remove_trailing_exit(if_stmt.alternate.as_mut().unwrap(), ctx);
+        }
+
+        let rest = ctx.ast.vec_from_iter(stmts.drain(index + 1..));
+        let Some(Statement::IfStatement(if_stmt)) = stmts.get_mut(index) else { unreachable!() };
+        if move_into_alternate {
+            let alternate = if_stmt.alternate.take();
+            if_stmt.alternate = Some(Self::append_statements(alternate, rest, ctx));
+        } else {
+            let consequent = ctx.ast.move_statement(&mut if_stmt.consequent);
+            if_stmt.consequent = Self::append_statements(Some(consequent), rest, ctx);
+        }
+        self.changed = true;
+    }
+
+    fn last_statement<'b>(stmt: &'b Statement<'a>) -> Option<&'b Statement<'a>> {
+        match stmt {
+            Statement::BlockStatement(block) => block.body.last(),
+            stmt => Some(stmt),
+        }
+    }
+
+    fn ends_with_exit(stmt: &Statement<'a>, exit_type: &ExitType<'a>) -> bool {
+        Self::last_statement(stmt).is_some_and(|last| Self::is_matching_exit(last, exit_type))
+    }
+
+    fn ends_with_jump(stmt: &Statement<'a>) -> bool {
+        Self::last_statement(stmt).is_some_and(|last| {
+            matches!(
+                last,
+                Statement::ReturnStatement(_)
+                    | Statement::ThrowStatement(_)
+                    | Statement::BreakStatement(_)
+                    | Statement::ContinueStatement(_)
+            )
+        })
+    }
+
+    /// Remove the last statement of `stmt`, which is known to be an exit.
+    fn remove_trailing_exit(stmt: &mut Statement<'a>, ctx: &mut TraverseCtx<'a>) {
+        if let Statement::BlockStatement(block) = stmt {
+            block.body.pop();
+        } else {
+            *stmt = ctx.ast.statement_empty(SPAN);
+        }
+    }
+
+    /// `stmt` followed by `rest`, as a single statement.
+    fn append_statements(
+        stmt: Option<Statement<'a>>,
+        mut rest: Vec<'a, Statement<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Statement<'a> {
+        match stmt {
+            Some(Statement::BlockStatement(mut block)) => {
+                block.body.append(&mut rest);
+                Statement::BlockStatement(block)
+            }
+            Some(stmt) if Self::is_empty_statement(&stmt) => Self::wrap_statements(rest, ctx),
+            Some(stmt) => {
+                rest.insert(0, stmt);
+                Self::wrap_statements(rest, ctx)
+            }
+            None => Self::wrap_statements(rest, ctx),
+        }
+    }
+
+    /// Turn a list of statements into a single statement, creating a block if necessary.
+    fn wrap_statements(
+        mut stmts: Vec<'a, Statement<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Statement<'a> {
+        if stmts.len() == 1 && !stmts[0].is_declaration() {
+            return stmts.pop().unwrap();
+        }
+        let scope_id = ctx.create_child_scope_of_current(ScopeFlags::empty());
+        let block = ctx.ast.block_statement(SPAN, stmts);
+        block.scope_id.set(Some(scope_id));
+        Statement::BlockStatement(ctx.ast.alloc(block))
+    }
+
+    /// `if (x) {} else { a() }` -> `if (!x) a()`
+    /// `if (x) {}` -> `x`
+    fn try_remove_empty_branches(&mut self, stmt: &mut Statement<'a>, ctx: &mut TraverseCtx<'a>) {
+        let Statement::IfStatement(if_stmt) = stmt else { return };
+        if if_stmt.alternate.as_ref().is_some_and(Self::is_empty_statement) {
+            if_stmt.alternate = None;
+            self.changed = true;
+        }
+        if !Self::is_empty_statement(&if_stmt.consequent) {
+            return;
+        }
+        let test = ctx.ast.move_expression(&mut if_stmt.test);
+        if let Some(alternate) = if_stmt.alternate.take() {
+            if_stmt.test = ctx.ast.expression_unary(SPAN, UnaryOperator::LogicalNot, test);
+            if_stmt.consequent = alternate;
+        } else {
+            *stmt = ctx.ast.statement_expression(if_stmt.span, test);
+        }
+        self.changed = true;
+    }
+
+    /// `if (a) return x; return y;` -> `return a ? x : y`
+    /// `if (a) return x; else return y;` -> `return a ? x : y`
+    fn try_merge_if_returns(
+        &mut self,
+        stmts: &mut Vec<'a, Statement<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) {
+        // Iterate backwards so that chains of `if` returns are merged in a single pass.
+        for i in (0..stmts.len()).rev() {
+            let Statement::IfStatement(if_stmt) = &stmts[i] else { continue };
+            let has_alternate = if_stmt.alternate.is_some();
+            let alternate = match &if_stmt.alternate {
+                Some(alternate) => Self::as_single_return(alternate),
+                None => stmts.get(i + 1).and_then(Self::as_single_return),
+            };
+            let (Some(consequent), Some(alternate)) =
+                (Self::as_single_return(&if_stmt.consequent), alternate)
+            else {
+                continue;
+            };
+            if consequent.argument.is_none() && alternate.argument.is_none() {
+                continue;
+            }
+
+            let Some(Statement::IfStatement(if_stmt)) = stmts.get_mut(i) else { unreachable!() };
+            let test = ctx.ast.move_expression(&mut if_stmt.test);
+            let consequent = Self::take_return_argument(&mut if_stmt.consequent, ctx);
+            let alternate = if has_alternate {
+                Self::take_return_argument(if_stmt.alternate.as_mut().unwrap(), ctx)
+            } else {
+                let mut next = stmts.remove(i + 1);
+                Self::take_return_argument(&mut next, ctx)
+            };
+            let argument = ctx.ast.expression_conditional(SPAN, test, consequent, alternate);
+            *stmts.get_mut(i).unwrap() = ctx.ast.statement_return(SPAN, Some(argument));
+            self.changed = true;
+        }
+    }
+
+    fn as_single_return<'b>(stmt: &'b Statement<'a>) -> Option<&'b ReturnStatement<'a>> {
+        match stmt {
+            Statement::ReturnStatement(return_stmt) => Some(return_stmt),
+            Statement::BlockStatement(block) if block.body.len() == 1 => {
+                Self::as_single_return(&block.body[0])
+            }
+            _ => None,
+        }
+    }
+
+    fn take_return_argument(stmt: &mut Statement<'a>, ctx: &mut TraverseCtx<'a>) -> Expression<'a> {
+        match stmt {
+            Statement::ReturnStatement(return_stmt) => {
+                return_stmt.argument.take().unwrap_or_else(|| ctx.ast.void_0(SPAN))
+            }
+            Statement::BlockStatement(block) => {
+                Self::take_return_argument(block.body.first_mut().unwrap(), ctx)
+            }
+            _ => unreachable!("must match with `Self::as_single_return`"),
+        }
+    }
+
+    /// Simplify `switch` statements:
+    ///
+    /// * `switch (x) {}` -> `x`
+    /// * `switch (x) { case 1: return a; case 2: return b; default: return c }`
+    ///   -> `return x === 1 ? a : x === 2 ? b : c`
+    /// * `switch (x) { case 1: a(); break; default: b() }` -> `if (x === 1) a(); else b()`
+    fn try_simplify_switch(
+        &self,
+        switch_stmt: &mut SwitchStatement<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Statement<'a>> {
+        if switch_stmt.cases.is_empty() {
+            let discriminant = ctx.ast.move_expression(&mut switch_stmt.discriminant);
+            return Some(ctx.ast.statement_expression(switch_stmt.span, discriminant));
+        }
+
+        // The last case must have a body, otherwise it is another fall through to nowhere.
+        if switch_stmt.cases.last().unwrap().consequent.is_empty() {
+            return None;
+        }
+        // `default` must be the last case and must not share its body with other cases,
+        // so that the case tests are evaluated in the same order.
+        let default_index = switch_stmt.cases.iter().position(SwitchCase::is_default_case);
+        if let Some(default_index) = default_index {
+            if default_index != switch_stmt.cases.len() - 1
+                || (default_index > 0 && switch_stmt.cases[default_index - 1].consequent.is_empty())
+            {
+                return None;
+            }
+        }
+
+        // The discriminant is evaluated once per case test, so neither it nor the case tests
+        // evaluated before it may have side effects, e.g. `case f():` reassigning `x` in `switch (x)`.
+        let test_count = switch_stmt.cases.iter().filter(|case| case.test.is_some()).count();
+        if test_count > 1
+            && !(Self::is_side_effect_free_test(&switch_stmt.discriminant)
+                && switch_stmt
+                    .cases
+                    .iter()
+                    .filter_map(|case| case.test.as_ref())
+                    .all(Self::is_side_effect_free_test))
+        {
+            return None;
+        }
+
+        if default_index.is_some() && Self::is_return_lookup(switch_stmt) {
+            return Some(self.switch_to_return_lookup(switch_stmt, ctx));
+        }
+        if Self::is_if_chain_convertible(switch_stmt) {
+            return Some(self.switch_to_if_chain(switch_stmt, ctx));
+        }
+        None
+    }
+
+    /// A literal value or an identifier reference, which can be evaluated again
+    /// without reassigning the discriminant.
+    fn is_side_effect_free_test(expr: &Expression<'a>) -> bool {
+        expr.is_identifier_reference() || expr.is_literal_value(/* include_functions */ false)
+    }
+
+    /// Every case body is either empty or a single `return` with a value.
+    fn is_return_lookup(switch_stmt: &SwitchStatement<'a>) -> bool {
+        switch_stmt.cases.iter().all(|case| match case.consequent.as_slice() {
+            [] => true,
+            [Statement::ReturnStatement(return_stmt)] => return_stmt.argument.is_some(),
+            _ => false,
+        })
+    }
+
+    /// Every non-empty case body ends with an unconditional exit,
+    /// does not `break` out of the switch anywhere else and does not declare lexical bindings.
+    fn is_if_chain_convertible(switch_stmt: &SwitchStatement<'a>) -> bool {
+        let len = switch_stmt.cases.len();
+        switch_stmt.cases.iter().enumerate().all(|(i, case)| {
+            let Some((last, rest)) = case.consequent.split_last() else { return true };
+            if !case.consequent.iter().all(Self::is_movable_into_block) {
+                return false;
+            }
+            let ends_with_exit = matches!(
+                last,
+                Statement::ReturnStatement(_)
+                    | Statement::ThrowStatement(_)
+                    | Statement::ContinueStatement(_)
+                    | Statement::BreakStatement(_)
+            );
+            if !ends_with_exit && i != len - 1 {
+                return false;
+            }
+            let mut finder = SwitchBreakFinder::default();
+            for stmt in rest {
+                finder.visit_statement(stmt);
+            }
+            if !matches!(last, Statement::BreakStatement(_)) {
+                finder.visit_statement(last);
+            }
+            !finder.found
+        })
+    }
+
+    /// Group the cases by their shared bodies, returning the tests and the body of each group.
+    fn take_case_groups(
+        switch_stmt: &mut SwitchStatement<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> std::vec::Vec<(std::vec::Vec<Expression<'a>>, Vec<'a, Statement<'a>>)> {
+        let mut groups = vec![];
+        let mut tests = vec![];
+        for case in switch_stmt.cases.iter_mut() {
+            if let Some(test) = case.test.take() {
+                tests.push(test);
+            }
+            if !case.consequent.is_empty() {
+                let body = std::mem::replace(&mut case.consequent, ctx.ast.vec());
+                groups.push((std::mem::take(&mut tests), body));
+            }
+        }
+        groups
+    }
+
+    /// `x === a || x === b`
+    fn build_case_test(
+        discriminant: &Expression<'a>,
+        tests: std::vec::Vec<Expression<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Expression<'a> {
+        tests
+            .into_iter()
+            .map(|test| {
+                let discriminant = discriminant.clone_in(ctx.ast.allocator);
+                ctx.ast.expression_binary(SPAN, discriminant, BinaryOperator::StrictEquality, test)
+            })
+            .reduce(|left, right| {
+                ctx.ast.expression_logical(SPAN, left, LogicalOperator::Or, right)
+            })
+            .unwrap()
+    }
+
+    fn switch_to_return_lookup(
+        &self,
+        switch_stmt: &mut SwitchStatement<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Statement<'a> {
+        let mut groups = Self::take_case_groups(switch_stmt, ctx);
+        let (_, mut default_body) = groups.pop().unwrap();
+        let mut result = Self::take_return_argument(default_body.first_mut().unwrap(), ctx);
+        for (tests, mut body) in groups.into_iter().rev() {
+            let test = Self::build_case_test(&switch_stmt.discriminant, tests, ctx);
+            let consequent = Self::take_return_argument(body.first_mut().unwrap(), ctx);
+            result = ctx.ast.expression_conditional(SPAN, test, consequent, result);
+        }
+        ctx.ast.statement_return(switch_stmt.span, Some(result))
+    }
+
+    fn switch_to_if_chain(
+        &self,
+        switch_stmt: &mut SwitchStatement<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Statement<'a> {
+        let has_default = switch_stmt.cases.last().unwrap().is_default_case();
+        let mut groups = Self::take_case_groups(switch_stmt, ctx);
+        let mut result = None;
+        if has_default {
+            let (_, body) = groups.pop().unwrap();
+            result = Some(Self::wrap_case_body(body, ctx));
+        }
+        for (tests, body) in groups.into_iter().rev() {
+            let test = Self::build_case_test(&switch_stmt.discriminant, tests, ctx);
+            let consequent = Self::wrap_case_body(body, ctx);
+            result = Some(ctx.ast.statement_if(SPAN, test, consequent, result));
+        }
+        result.unwrap()
+    }
+
+    fn wrap_case_body(
+        mut body: Vec<'a, Statement<'a>>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Statement<'a> {
+        if matches!(body.last(), Some(Statement::BreakStatement(break_stmt)) if break_stmt.label.is_none())
+        {
+            body.pop();
+        }
+        if body.is_empty() {
+            return ctx.ast.statement_empty(SPAN);
+        }
+        Self::wrap_statements(body, ctx)
+    }
+}
+
+/// Finds unlabeled `break` statements which target the enclosing `switch` statement.
+#[derive(Default)]
+struct SwitchBreakFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for SwitchBreakFinder {
+    fn visit_break_statement(&mut self, stmt: &BreakStatement<'a>) {
+        if stmt.label.is_none() {
+            self.found = true;
+        }
+    }
+
+    // Unlabeled `break`s inside nested loops and switches target them instead.
+    fn visit_do_while_statement(&mut self, _: &DoWhileStatement<'a>) {}
+    fn visit_while_statement(&mut self, _: &WhileStatement<'a>) {}
+    fn visit_for_statement(&mut self, _: &ForStatement<'a>) {}
+    fn visit_for_in_statement(&mut self, _: &ForInStatement<'a>) {}
+    fn visit_for_of_statement(&mut self, _: &ForOfStatement<'a>) {}
+    fn visit_switch_statement(&mut self, _: &SwitchStatement<'a>) {}
+
+    // `break` cannot cross function boundaries.
+    fn visit_function(&mut self, _: &Function<'a>, _: ScopeFlags) {}
+    fn visit_arrow_function_expression(&mut self, _: &ArrowFunctionExpression<'a>) {}
+    fn visit_class(&mut self, _: &Class<'a>) {}
+}
+
+/// <https://github.com/google/closure-compiler/blob/master/test/com/google/javascript/jscomp/MinimizeExitPointsTest.java>
+#[cfg(test)]
+mod test {
+    use oxc_allocator::Allocator;
+
+    use crate::tester;
+
+    fn test(source_text: &str, expected: &str) {
+        let allocator = Allocator::default();
+        let mut pass = super::MinimizeExitPoints::new();
+        tester::test(&allocator, source_text, expected, &mut pass);
+    }
+
+    fn test_same(source_text: &str) {
+        test(source_text, source_text);
+    }
+
+    fn fold(js: &str, expected: &str) {
+        test(js, expected);
+    }
+
+    fn fold_same(js: &str) {
+        test_same(js);
+    }
+
+    #[test]
+    fn test_break_optimization() {
+        fold("f:{if(true){a();break f;}else;b();}", "f:{if(true){a()}else b()}");
+        fold("f:{if(false){a();break f;}else;b();break f;}", "f:{if(false){a()}else{b()}}");
+        fold("f:{if(a()){b();break f;}else;c();}", "f:{if(a()){b();}else c()}");
+        fold("f:{if(a()){b()}else{c();break f;}}", "f:{if(a()){b()}else{c();}}");
+        fold("f:{if(a()){b();break f;}else;}", "f:{if(a()){b();}}");
+        fold("f:{if(a()){break f;}else;}", "f:{a()}");
+
+        fold("f:while(a())break f;", "f:while(a())break f");
+        fold_same("f:for(x in a())break f");
+
+        fold_same("f:{while(a())break;}");
+        fold_same("f:{for(x in a())break}");
+
+        fold("f:try{break f;}catch(e){break f;}", "f:try{}catch(e){}");
+        fold("f:try{if(a()){break f;}else{break f;} break f;}catch(e){}", "f:try{a()}catch(e){}");
+
+        fold("f:g:break f", "f:g:;");
+        fold("f:g:{if(a()){break f;}else{break f;} break f;}", "f:g:{a()}");
+        fold("function f() { a: break a; }", "function f() { a: ; }");
+        fold("function f() { a: { break a; } }", "function f() { a: {} }");
+    }
+
+    #[test]
+    fn test_function_return_optimization() {
+        fold("function f(){if(a()){b();if(c())return;}}", "function f(){if(a()){b();c()}}");
+        fold("function f(){if(x)return; x=3; return; }", "function f(){if(!x){x=3}}");
+        fold("function f(){if(true){a();return;}else;b();}", "function f(){if(true){a()}else b()}");
+        fold(
+            "function f(){if(false){a();return;}else;b();return;}",
+            "function f(){if(false){a()}else{b()}}",
+        );
+        fold("function f(){if(a()){b();return;}else;c();}", "function f(){if(a()){b()}else c()}");
+        fold("function f(){if(a()){b()}else{c();return;}}", "function f(){if(a()){b()}else{c()}}");
+        fold("function f(){if(a()){b();return;}else;}", "function f(){if(a()){b()}}");
+        fold("function f(){if(a()){return;}else{return;} return;}", "function f(){a()}");
+        fold(
+            "function f(){if(a()){return;}else{return;} b();}",
+            "function f(){if(a()){return;}else{return;} b();}",
+        );
+        fold(
+            "function f(){ if (x) return; if (y) return; if (z) return; foo(); }",
+            "function f(){ if (!x) { if (!y) { if (!z) foo() } } }",
+        );
+
+        fold("function f(){while(a())return;}", "function f(){while(a())return}");
+        fold_same("function f(){for(x in a())return}");
+
+        fold("function f(){while(a())break;}", "function f(){while(a())break}");
+        fold_same("function f(){for(x in a())break}");
+
+        fold(
+            "function f(){try{return;}catch(e){throw 9;}finally{return}}",
+            "function f(){try{}catch(e){throw 9;}finally{return}}",
+        );
+        fold_same("function f(){try{throw 9;}finally{return;}}");
+
+        fold("function f(){try{return;}catch(e){return;}}", "function f(){try{}catch(e){}}");
+        fold(
+            "function f(){try{if(a()){return;}else{return;} return;}catch(e){}}",
+            "function f(){try{a()}catch(e){}}",
+        );
+
+        fold("function f(){g:return}", "function f(){g:;}");
+        fold("function f(){g:if(a()){return;}else{return;} return;}", "function f(){g:a()}");
+        fold("function f(){g:try{return;}catch(e){return;}}", "function f(){g:try{}catch(e){}}");
+    }
+
+    #[test]
+    fn test_while_continue_optimization() {
+        fold("while(true){if(x)continue; x=3; continue; }", "while(true){if(!x){x=3}}");
+        fold_same("while(true){a();continue;b();}");
+        fold("while(true){if(true){a();continue;}else;b();}", "while(true){if(true){a()}else b()}");
+        fold("while(true){if(a()){b();continue;}else;c();}", "while(true){if(a()){b()}else c()}");
+        fold("while(true){if(a()){b();}else{c();continue;}}", "while(true){if(a()){b()}else{c()}}");
+        fold("while(true){if(a()){b();continue;}else;}", "while(true){if(a()){b()}}");
+        fold("while(true){if(a()){continue;}else{continue;} continue;}", "while(true){a()}");
+        fold_same("while(true){if(a()){continue;}else{continue;} b();}");
+
+        fold("while(true)while(a())continue;", "while(true)while(a());");
+        fold("while(true)for(x in a())continue", "while(true)for(x in a());");
+
+        fold("while(true)while(a())break;", "while(true)while(a())break");
+        fold_same("while(true)for(x in a())break");
+
+        fold("while(true){try{continue;}catch(e){continue;}}", "while(true){try{}catch(e){}}");
+        fold(
+            "while(true){try{if(a()){continue;}else{continue;} continue;}catch(e){}}",
+            "while(true){try{a()}catch(e){}}",
+        );
+
+        fold("while(true){g:continue}", "while(true){g:;}");
+        fold("while(true){g:if(a()){continue;}else{continue;} continue;}", "while(true){g:a()}");
+        fold("while(true){g:try{continue;}catch(e){continue;}}", "while(true){g:try{}catch(e){}}");
+    }
+
+    #[test]
+    fn test_do_continue_optimization() {
+        fold("do{if(x)continue; x=3; continue; }while(true)", "do{if(!x){x=3}}while(true)");
+        fold_same("do{a();continue;b()}while(true)");
+        fold(
+            "do{if(a()){b();continue;}else;c();}while(true)",
+            "do{if(a()){b()}else c()}while(true)",
+        );
+        fold("do{if(a()){continue;}else{continue;} continue;}while(true)", "do{a()}while(true)");
+    }
+
+    #[test]
+    fn test_for_continue_optimization() {
+        fold("for(x in y){if(x)continue; x=3; continue; }", "for(x in y){if(!x){x=3}}");
+        fold_same("for(x in y){a();continue;b()}");
+        fold("for(x of y){if(a()){b();continue;}else;c();}", "for(x of y){if(a()){b()}else c()}");
+        fold("for(;;){if(a()){continue;}else{continue;} continue;}", "for(;;){a()}");
+    }
+
+    #[test]
+    fn test_labeled_continue() {
+        fold("a:while(true){if(x)continue a; x=3; continue a;}", "a:while(true){if(!x){x=3}}");
+        fold_same("a:while(true){b:while(true){continue a}}");
+    }
+
+    #[test]
+    fn test_dont_move_block_scoped_declarations() {
+        fold_same("function f(){if(x)return; let y = 1; g(y)}");
+        fold_same("function f(){if(x)return; const y = 1; g(y)}");
+        fold_same("function f(){if(x)return; class C {} g(C)}");
+        fold_same("function f(){if(x)return; function g() {} g()}");
+        fold("function f(){if(x)return; var y = 1; g(y)}", "function f(){if(!x){var y = 1; g(y)}}");
+    }
+
+    #[test]
+    fn test_fold_returns() {
+        fold("function f(){if(x)return 1;else return 2}", "function f(){return x?1:2}");
+        fold("function f(){if(x)return 1;return 2}", "function f(){return x?1:2}");
+        fold("function f(){if(x)return;return 2}", "function f(){return x?void 0:2}");
+        fold("function f(){if(x)return 1+x;else return 2-x}", "function f(){return x?1+x:2-x}");
+        fold("function f(){if(x)return 1+x;return 2-x}", "function f(){return x?1+x:2-x}");
+        fold(
+            "function f(){if(x)return y += 1;else return y += 2}",
+            "function f(){return x?(y+=1):(y+=2)}",
+        );
+        fold("function f(){if(x){return 1}return 2}", "function f(){return x?1:2}");
+        fold(
+            "function f(){if(x)return 1;if(y)return 2;return 3}",
+            "function f(){return x?1:y?2:3}",
+        );
+        fold("function f(){if(x)return;return}", "function f(){x}");
+
+        fold_same("function f(){for(var x in y) { return x.y; } return k}");
+        fold_same("function f(){if(x){a();return 1}return 2}");
+    }
+
+    #[test]
+    fn test_switch_exit_points() {
+        fold("switch(a){case 1:b();break}", "if(a===1)b()");
+        fold("switch(a){case 1:b();break;case 2:c();break}", "if(a===1)b();else if(a===2)c()");
+        fold("switch(a){case 1:b();break;default:c();break}", "if(a===1)b();else c()");
+        fold("switch(a){case 1:case 2:b();break;default:c()}", "if(a===1||a===2)b();else c()");
+        fold("switch(a()){case 1:b();break;default:c()}", "if(a()===1)b();else c()");
+        fold("switch(a){default:b();break}", "b()");
+        fold("switch(a){}", "a");
+
+        // Fall through
+        fold_same("switch(a){case 1:b();case 2:c()}");
+        // `default` is not the last case
+        fold_same("switch(a){default:b();break;case 1:c()}");
+        // The discriminant would be evaluated more than once
+        fold_same("switch(a()){case 1:b();break;case 2:c()}");
+        // A case test may reassign the discriminant before the next case test is evaluated
+        fold_same("switch(a){case b():c();break;case 1:d()}");
+        fold_same("function f(){switch(a){case 1:return b;case c():return d;default:return e}}");
+        // `break` in the middle of a case
+        fold_same("switch(a){case 1:if(x)break;b();break;default:c()}");
+        // Lexical declarations are scoped to the whole switch
+        fold_same("switch(a){case 1:let x = 1;b(x);break;default:c()}");
+
+        fold(
+            "for(;;)switch(a){case 1:b();continue;case 2:for(;;)break;break;default:c()}",
+            "for(;;)if(a===1){b()}else if(a===2)for(;;)break;else c()",
+        );
+        fold("l:switch(a){case 1:b();break l;default:c()}", "l:if(a===1){b()}else c()");
+    }
+
+    #[test]
+    fn test_switch_return_lookup() {
+        fold(
+            "function f(){switch(a){case 1:return 'a';case 2:return 'b';default:return 'c'}}",
+            "function f(){return a===1?'a':a===2?'b':'c'}",
+        );
+        fold(
+            "function f(){switch(a){case 1:case 2:return 'a';default:return 'c'}}",
+            "function f(){return a===1||a===2?'a':'c'}",
+        );
+        fold(
+            "function f(){switch(a){case 1:return 'a';default:return 'c';}}",
+            "function f(){return a===1?'a':'c'}",
+        );
+        // No `default`
+        fold(
+            "function f(){switch(a){case 1:return 'a';case 2:return 'b'}}",
+            "function f(){if(a===1)return 'a';else if(a===2)return 'b'}",
+        );
+    }
+}
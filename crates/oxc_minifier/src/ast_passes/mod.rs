@@ -1,5 +1,7 @@
 mod collapse_variable_declarations;
 mod exploit_assigns;
+mod minify_embedded_templates;
+mod minimize_exit_points;
 mod peephole_fold_constants;
 mod peephole_minimize_conditions;
 mod peephole_remove_dead_code;
@@ -10,6 +12,8 @@ mod statement_fusion;
 
 pub use collapse_variable_declarations::CollapseVariableDeclarations;
 pub use exploit_assigns::ExploitAssigns;
+pub use minify_embedded_templates::MinifyEmbeddedTemplates;
+pub use minimize_exit_points::MinimizeExitPoints;
 pub use peephole_fold_constants::PeepholeFoldConstants;
 pub use peephole_minimize_conditions::PeepholeMinimizeConditions;
 pub use peephole_remove_dead_code::PeepholeRemoveDeadCode;
@@ -16,13 +16,14 @@ use crate::{
     },
     tri::Tri,
     ty::Ty,
-    CompressorPass,
+    CompressOptions, CompressorPass,
 };
 
 /// Constant Folding
 ///
 /// <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeFoldConstants.java>
 pub struct PeepholeFoldConstants {
+    options: CompressOptions,
     changed: bool,
 }
 
@@ -63,8 +64,8 @@ impl<'a> Traverse<'a> for PeepholeFoldConstants {
 }
 
 impl<'a> PeepholeFoldConstants {
-    pub fn new() -> Self {
-        Self { changed: false }
+    pub fn new(options: CompressOptions) -> Self {
+        Self { options, changed: false }
     }
 
     fn try_fold_useless_object_dot_define_properties_call(
@@ -412,6 +413,9 @@ impl<'a> PeepholeFoldConstants {
             | BinaryOperator::Exponential => {
                 self.try_fold_arithmetic_op(e.span, &e.left, &e.right, ctx)
             }
+            BinaryOperator::Multiplication if self.options.unsafe_math => {
+                self.try_fold_unsafe_multiplication(e, ctx)
+            }
             BinaryOperator::Multiplication
             | BinaryOperator::BitwiseAnd
             | BinaryOperator::BitwiseOR
@@ -431,6 +435,42 @@ impl<'a> PeepholeFoldConstants {
         }
     }
 
+    /// `2 * x * 3` -> `6 * x`
+    ///
+    /// Enabled by `compress.unsafe_math`, this changes the order of floating point operations.
+    fn try_fold_unsafe_multiplication(
+        &self,
+        e: &mut BinaryExpression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Expression<'a>> {
+        let (value, nested) = match (&mut e.left, &mut e.right) {
+            (Expression::NumericLiteral(n), Expression::BinaryExpression(nested))
+            | (Expression::BinaryExpression(nested), Expression::NumericLiteral(n)) => {
+                (n.value, nested)
+            }
+            _ => return None,
+        };
+        if nested.operator != BinaryOperator::Multiplication {
+            return None;
+        }
+        let nested = &mut **nested;
+        let (nested_value, operand) = match (&mut nested.left, &mut nested.right) {
+            (Expression::NumericLiteral(n), operand) | (operand, Expression::NumericLiteral(n)) => {
+                (n.value, operand)
+            }
+            _ => return None,
+        };
+        let value = value * nested_value;
+        if !value.is_finite() {
+            return None;
+        }
+        let number_base =
+            if is_exact_int64(value) { NumberBase::Decimal } else { NumberBase::Float };
+        let left = ctx.ast.expression_numeric_literal(SPAN, value, "", number_base);
+        let right = ctx.ast.move_expression(operand);
+        Some(ctx.ast.expression_binary(e.span, left, BinaryOperator::Multiplication, right))
+    }
+
     fn try_fold_addition<'b>(
         &self,
         span: Span,
@@ -870,11 +910,11 @@ impl<'a> PeepholeFoldConstants {
 mod test {
     use oxc_allocator::Allocator;
 
-    use crate::tester;
+    use crate::{tester, CompressOptions};
 
     fn test(source_text: &str, expected: &str) {
         let allocator = Allocator::default();
-        let mut pass = super::PeepholeFoldConstants::new();
+        let mut pass = super::PeepholeFoldConstants::new(CompressOptions::default());
         tester::test(&allocator, source_text, expected, &mut pass);
     }
 
@@ -1556,4 +1596,21 @@ mod test {
         test("1 << -1", "1<<-1");
         test("1 >> 32", "1>>32");
     }
+
+    #[test]
+    fn test_fold_unsafe_math() {
+        let test_unsafe = |source_text: &str, expected: &str| {
+            let allocator = Allocator::default();
+            let options = CompressOptions { unsafe_math: true, ..CompressOptions::default() };
+            let mut pass = super::PeepholeFoldConstants::new(options);
+            tester::test(&allocator, source_text, expected, &mut pass);
+        };
+        test_same("2 * x * 3");
+        test_unsafe("2 * x * 3", "6 * x");
+        test_unsafe("x * 2 * 3", "6 * x");
+        test_unsafe("2 * (3 * x)", "6 * x");
+        test_unsafe("0.5 * x * 3", "1.5 * x");
+        test_unsafe("2 * x * y", "2 * x * y");
+        test_unsafe("2 * (x / 3)", "2 * (x / 3)");
+    }
 }
@@ -1,7 +1,8 @@
 use oxc_ast::ast::*;
+use oxc_syntax::operator::BinaryOperator;
 use oxc_traverse::{Traverse, TraverseCtx};
 
-use crate::CompressorPass;
+use crate::{CompressOptions, CompressorPass};
 
 /// Minimize Conditions
 ///
@@ -11,6 +12,7 @@ use crate::CompressorPass;
 ///
 /// <https://github.com/google/closure-compiler/blob/master/src/com/google/javascript/jscomp/PeepholeMinimizeConditions.java>
 pub struct PeepholeMinimizeConditions {
+    options: CompressOptions,
     changed: bool,
 }
 
@@ -38,8 +40,8 @@ impl<'a> Traverse<'a> for PeepholeMinimizeConditions {
 }
 
 impl<'a> PeepholeMinimizeConditions {
-    pub fn new() -> Self {
-        Self { changed: false }
+    pub fn new(options: CompressOptions) -> Self {
+        Self { options, changed: false }
     }
 
     /// Try to minimize NOT nodes such as `!(x==y)`.
@@ -54,6 +56,18 @@ impl<'a> PeepholeMinimizeConditions {
                 binary_expr.operator = new_op;
                 return Some(ctx.ast.move_expression(&mut expr.argument));
             }
+            // `!(a < b)` -> `a >= b`, which is incorrect when `a` or `b` is `NaN`.
+            if self.options.unsafe_comps {
+                let new_op = match binary_expr.operator {
+                    BinaryOperator::LessThan => BinaryOperator::GreaterEqualThan,
+                    BinaryOperator::LessEqualThan => BinaryOperator::GreaterThan,
+                    BinaryOperator::GreaterThan => BinaryOperator::LessEqualThan,
+                    BinaryOperator::GreaterEqualThan => BinaryOperator::LessThan,
+                    _ => return None,
+                };
+                binary_expr.operator = new_op;
+                return Some(ctx.ast.move_expression(&mut expr.argument));
+            }
         }
         None
     }
@@ -64,11 +78,11 @@ impl<'a> PeepholeMinimizeConditions {
 mod test {
     use oxc_allocator::Allocator;
 
-    use crate::tester;
+    use crate::{tester, CompressOptions};
 
     fn test(source_text: &str, positive: &str) {
         let allocator = Allocator::default();
-        let mut pass = super::PeepholeMinimizeConditions::new();
+        let mut pass = super::PeepholeMinimizeConditions::new(CompressOptions::default());
         tester::test(&allocator, source_text, positive, &mut pass);
     }
 
@@ -1123,4 +1137,20 @@ mod test {
         // lines("function x() {", "  return new.target ? 1 : 2;", "}"),
         // );
     }
+
+    #[test]
+    fn test_unsafe_comps() {
+        let test_unsafe = |source_text: &str, expected: &str| {
+            let allocator = Allocator::default();
+            let options = CompressOptions { unsafe_comps: true, ..CompressOptions::default() };
+            let mut pass = super::PeepholeMinimizeConditions::new(options);
+            tester::test(&allocator, source_text, expected, &mut pass);
+        };
+        test_same("!(a < b)");
+        test_unsafe("!(a < b)", "a >= b");
+        test_unsafe("!(a <= b)", "a > b");
+        test_unsafe("!(a > b)", "a <= b");
+        test_unsafe("!(a >= b)", "a < b");
+        test_unsafe("!(a instanceof b)", "!(a instanceof b)");
+    }
 }
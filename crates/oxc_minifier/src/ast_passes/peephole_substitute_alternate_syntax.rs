@@ -1,13 +1,18 @@
-use oxc_ast::ast::*;
+use std::borrow::Cow;
+
+use oxc_ast::{ast::*, syntax_directed_operations::BoundNames, Visit};
+use oxc_semantic::IsGlobalReference;
 use oxc_span::{GetSpan, SPAN};
 use oxc_syntax::number::ToJsInt32;
 use oxc_syntax::{
     number::NumberBase,
-    operator::{BinaryOperator, UnaryOperator},
+    operator::{BinaryOperator, LogicalOperator, UnaryOperator},
+    scope::ScopeFlags,
 };
 use oxc_traverse::{Ancestor, Traverse, TraverseCtx};
+use rustc_hash::FxHashSet;
 
-use crate::{node_util::NodeUtil, CompressOptions, CompressorPass};
+use crate::{node_util::NodeUtil, ty::Ty, CompressOptions, CompressorPass};
 
 /// A peephole optimization that minimizes code by simplifying conditional
 /// expressions, replacing IFs with HOOKs, replacing object constructors
@@ -84,11 +89,28 @@ impl<'a> Traverse<'a> for PeepholeSubstituteAlternateSyntax {
                 self.changed = true;
             }
         }
-        if !self.compress_undefined(expr, ctx) {
+        if !self.compress_undefined(expr, ctx) && !self.compress_infinity(expr, ctx) {
             self.compress_boolean(expr, ctx);
         }
     }
 
+    fn exit_expression(&mut self, expr: &mut Expression<'a>, ctx: &mut TraverseCtx<'a>) {
+        if let Some(new_expr) = match expr {
+            Expression::AssignmentExpression(e) => self.try_compress_logical_assignment(e, ctx),
+            Expression::ConditionalExpression(e) => self
+                .try_compress_nullish_coalescing(e, ctx)
+                .or_else(|| self.try_compress_optional_chaining(e, ctx)),
+            Expression::CallExpression(e) => self.try_compress_object_assign(e, ctx),
+            Expression::FunctionExpression(_) => self.try_compress_arrow_function(expr, ctx),
+            Expression::BinaryExpression(e) => self.try_compress_template_literal(e, ctx),
+            Expression::StaticMemberExpression(e) => self.try_compress_prototype(e, ctx),
+            _ => None,
+        } {
+            *expr = new_expr;
+            self.changed = true;
+        }
+    }
+
     fn enter_binary_expression(
         &mut self,
         expr: &mut BinaryExpression<'a>,
@@ -114,6 +136,35 @@ impl<'a> PeepholeSubstituteAlternateSyntax {
         false
     }
 
+    /// Transforms `Infinity` into `1/0` and `-Infinity` into `-1/0`.
+    ///
+    /// Disabled by `compress.keep_infinity`
+    fn compress_infinity(&self, expr: &mut Expression<'a>, ctx: &mut TraverseCtx<'a>) -> bool {
+        if self.options.keep_infinity {
+            return false;
+        }
+        let is_infinity = |expr: &Expression<'a>, ctx: &TraverseCtx<'a>| {
+            matches!(expr, Expression::Identifier(ident)
+                if ident.name == "Infinity" && ident.is_global_reference(ctx.symbols()))
+        };
+        let (span, negative) = match &*expr {
+            Expression::Identifier(ident) if is_infinity(expr, ctx) => (ident.span, false),
+            Expression::UnaryExpression(e)
+                if e.operator == UnaryOperator::UnaryNegation && is_infinity(&e.argument, ctx) =>
+            {
+                (e.span, true)
+            }
+            _ => return false,
+        };
+        let mut one = ctx.ast.expression_numeric_literal(SPAN, 1.0, "1", NumberBase::Decimal);
+        if negative {
+            one = ctx.ast.expression_unary(SPAN, UnaryOperator::UnaryNegation, one);
+        }
+        let zero = ctx.ast.expression_numeric_literal(SPAN, 0.0, "0", NumberBase::Decimal);
+        *expr = ctx.ast.expression_binary(span, one, BinaryOperator::Division, zero);
+        true
+    }
+
     /// Test `Object.defineProperty(exports, ...)`
     fn is_object_define_property_exports(call_expr: &CallExpression<'a>) -> bool {
         let Some(Argument::Identifier(ident)) = call_expr.arguments.first() else { return false };
@@ -325,12 +376,453 @@ impl<'a> PeepholeSubstituteAlternateSyntax {
     }
 }
 
+impl<'a> PeepholeSubstituteAlternateSyntax {
+    /// `Array.prototype.slice` -> `[].slice`
+    /// `Object.prototype.hasOwnProperty` -> `{}.hasOwnProperty`
+    /// `String.prototype.trim` -> `"".trim`
+    ///
+    /// Enabled by `compress.unsafe_proto`
+    fn try_compress_prototype(
+        &self,
+        expr: &mut StaticMemberExpression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Expression<'a>> {
+        if !self.options.unsafe_proto
+            || expr.optional
+            || expr.property.name != "prototype"
+            || !matches!(ctx.parent(), Ancestor::StaticMemberExpressionObject(_))
+        {
+            return None;
+        }
+        let Expression::Identifier(ident) = &expr.object else { return None };
+        if !ident.is_global_reference(ctx.symbols()) {
+            return None;
+        }
+        match ident.name.as_str() {
+            "Array" => Some(ctx.ast.expression_array(expr.span, ctx.ast.vec(), None)),
+            "Object" => Some(ctx.ast.expression_object(expr.span, ctx.ast.vec(), None)),
+            "String" => Some(ctx.ast.expression_string_literal(expr.span, "")),
+            _ => None,
+        }
+    }
+}
+
+/// Syntax which depends on the target engines, enabled by [crate::CompressTarget].
+impl<'a> PeepholeSubstituteAlternateSyntax {
+    /// Returns the name of `x` and whether the check is negated for
+    /// `x == null`, `x === null || x === void 0`,
+    /// `x != null` and `x !== null && x !== void 0`.
+    fn get_nullish_check<'b>(test: &'b Expression<'a>) -> Option<(&'b str, bool)> {
+        // `x == null`, `x != null`
+        if let Expression::BinaryExpression(e) = test {
+            let negated = match e.operator {
+                BinaryOperator::Equality => false,
+                BinaryOperator::Inequality => true,
+                _ => return None,
+            };
+            let Expression::Identifier(ident) = &e.left else { return None };
+            return (e.right.is_null() || e.right.is_void_0())
+                .then_some((ident.name.as_str(), negated));
+        }
+        // `x === null || x === void 0`, `x !== null && x !== void 0` in any order
+        let Expression::LogicalExpression(e) = test else { return None };
+        let negated = match e.operator {
+            LogicalOperator::Or => false,
+            LogicalOperator::And => true,
+            LogicalOperator::Coalesce => return None,
+        };
+        let check = |expr: &'b Expression<'a>| -> Option<(&'b str, bool, bool)> {
+            let Expression::BinaryExpression(b) = expr else { return None };
+            let op_negated = match b.operator {
+                BinaryOperator::StrictEquality => false,
+                BinaryOperator::StrictInequality => true,
+                _ => return None,
+            };
+            let Expression::Identifier(ident) = &b.left else { return None };
+            if b.right.is_null() {
+                Some((ident.name.as_str(), op_negated, true))
+            } else if b.right.is_void_0() {
+                Some((ident.name.as_str(), op_negated, false))
+            } else {
+                None
+            }
+        };
+        let (left_name, left_negated, left_null) = check(&e.left)?;
+        let (right_name, right_negated, right_null) = check(&e.right)?;
+        (left_name == right_name
+            && left_negated == negated
+            && right_negated == negated
+            && left_null != right_null)
+            .then_some((left_name, negated))
+    }
+
+    /// `a = a || b` -> `a ||= b`
+    fn try_compress_logical_assignment(
+        &self,
+        expr: &mut AssignmentExpression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Expression<'a>> {
+        if !self.options.target.logical_assignment_operators
+            || expr.operator != AssignmentOperator::Assign
+        {
+            return None;
+        }
+        let AssignmentTarget::AssignmentTargetIdentifier(target) = &expr.left else { return None };
+        let Expression::LogicalExpression(logical_expr) = &mut expr.right else { return None };
+        if !logical_expr.left.is_specific_id(&target.name) {
+            return None;
+        }
+        let operator = match logical_expr.operator {
+            LogicalOperator::Or => AssignmentOperator::LogicalOr,
+            LogicalOperator::And => AssignmentOperator::LogicalAnd,
+            LogicalOperator::Coalesce => AssignmentOperator::LogicalNullish,
+        };
+        let right = ctx.ast.move_expression(&mut logical_expr.right);
+        let left = ctx.ast.move_assignment_target(&mut expr.left);
+        Some(ctx.ast.expression_assignment(expr.span, operator, left, right))
+    }
+
+    /// `x !== null && x !== void 0 ? x : y` -> `x ?? y`
+    /// `x == null ? y : x` -> `x ?? y`
+    fn try_compress_nullish_coalescing(
+        &self,
+        expr: &mut ConditionalExpression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Expression<'a>> {
+        if !self.options.target.nullish_coalescing_operator {
+            return None;
+        }
+        let (name, negated) = Self::get_nullish_check(&expr.test)?;
+        let (value, fallback) = if negated {
+            (&mut expr.consequent, &mut expr.alternate)
+        } else {
+            (&mut expr.alternate, &mut expr.consequent)
+        };
+        if !value.is_specific_id(name) {
+            return None;
+        }
+        let left = ctx.ast.move_expression(value);
+        let right = ctx.ast.move_expression(fallback);
+        Some(ctx.ast.expression_logical(expr.span, left, LogicalOperator::Coalesce, right))
+    }
+
+    /// `x == null ? void 0 : x.y` -> `x?.y`
+    /// `x === null || x === void 0 ? void 0 : x.y()` -> `x?.y()`
+    fn try_compress_optional_chaining(
+        &self,
+        expr: &mut ConditionalExpression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Expression<'a>> {
+        if !self.options.target.optional_chaining {
+            return None;
+        }
+        let (name, negated) = Self::get_nullish_check(&expr.test)?;
+        let (undefined, chain) = if negated {
+            (&expr.alternate, &mut expr.consequent)
+        } else {
+            (&expr.consequent, &mut expr.alternate)
+        };
+        if !undefined.is_void_0() || !Self::set_optional_on_chain_root(chain, name) {
+            return None;
+        }
+        let element = match ctx.ast.move_expression(chain) {
+            Expression::CallExpression(call_expr) => ChainElement::CallExpression(call_expr),
+            chain => ChainElement::from(chain.into_member_expression()),
+        };
+        Some(ctx.ast.expression_chain(expr.span, element))
+    }
+
+    /// Mark the member access or call on `name` at the root of `expr` as optional.
+    fn set_optional_on_chain_root(expr: &mut Expression<'a>, name: &str) -> bool {
+        let (object, optional) = match expr {
+            Expression::StaticMemberExpression(e) => {
+                let e = &mut **e;
+                (&mut e.object, &mut e.optional)
+            }
+            Expression::ComputedMemberExpression(e) => {
+                let e = &mut **e;
+                (&mut e.object, &mut e.optional)
+            }
+            Expression::PrivateFieldExpression(e) => {
+                let e = &mut **e;
+                (&mut e.object, &mut e.optional)
+            }
+            Expression::CallExpression(e) => {
+                let e = &mut **e;
+                (&mut e.callee, &mut e.optional)
+            }
+            _ => return false,
+        };
+        if object.is_specific_id(name) && matches!(object, Expression::Identifier(_)) {
+            *optional = true;
+            return true;
+        }
+        Self::set_optional_on_chain_root(object, name)
+    }
+
+    /// `Object.assign({}, a, b)` -> `{ ...a, ...b }`
+    fn try_compress_object_assign(
+        &self,
+        expr: &mut CallExpression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Expression<'a>> {
+        if !self.options.target.object_spread || expr.optional || expr.arguments.len() < 2 {
+            return None;
+        }
+        let Expression::StaticMemberExpression(callee) = &expr.callee else { return None };
+        let Expression::Identifier(object) = &callee.object else { return None };
+        if object.name != "Object"
+            || callee.property.name != "assign"
+            || callee.optional
+            || !object.is_global_reference(ctx.symbols())
+        {
+            return None;
+        }
+        if !matches!(&expr.arguments[0], Argument::ObjectExpression(o) if o.properties.is_empty()) {
+            return None;
+        }
+        if expr.arguments.iter().any(Argument::is_spread) {
+            return None;
+        }
+        let properties = ctx.ast.vec_from_iter(expr.arguments.iter_mut().skip(1).map(|arg| {
+            let argument = ctx.ast.move_expression(arg.to_expression_mut());
+            ctx.ast.object_property_kind_spread_element(SPAN, argument)
+        }));
+        Some(ctx.ast.expression_object(expr.span, properties, None))
+    }
+
+    /// `foo(function() { return x })` -> `foo(() => x)`
+    ///
+    /// Arrow functions cannot be constructed, have no `prototype` and do not allow duplicate
+    /// parameters, so this only applies to anonymous, non-generator and non-async functions
+    /// passed directly as call arguments, without duplicate parameters and which do not use
+    /// `this`, `arguments`, `super` or `new.target`.
+    ///
+    /// `compress.unsafe_arrows` allows other positions, except where the function is obviously
+    /// used as a constructor.
+    fn try_compress_arrow_function(
+        &self,
+        expr: &mut Expression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Expression<'a>> {
+        if !self.options.target.arrow_functions {
+            return None;
+        }
+        let Expression::FunctionExpression(func) = expr else { return None };
+        if func.id.is_some()
+            || func.generator
+            || func.r#async
+            || func.this_param.is_some()
+            || func.body.is_none()
+        {
+            return None;
+        }
+        if !self.options.unsafe_arrows
+            && !matches!(ctx.parent(), Ancestor::CallExpressionArguments(_))
+        {
+            return None;
+        }
+        if matches!(
+            ctx.parent(),
+            Ancestor::NewExpressionCallee(_)
+                | Ancestor::StaticMemberExpressionObject(_)
+                | Ancestor::ComputedMemberExpressionObject(_)
+        ) {
+            return None;
+        }
+        // Bail `cjs-module-lexer`, which only detects `get: function() { return ... }` getters.
+        if matches!(ctx.parent(), Ancestor::ObjectPropertyValue(prop) if prop.key().is_specific_static_name("get"))
+        {
+            return None;
+        }
+        let mut names = FxHashSet::default();
+        let mut duplicate = false;
+        func.params.bound_names(&mut |ident| duplicate |= !names.insert(ident.name.clone()));
+        if duplicate {
+            return None;
+        }
+        let mut finder = FunctionContextFinder::default();
+        finder.visit_formal_parameters(&func.params);
+        finder.visit_function_body(func.body.as_ref().unwrap());
+        if finder.found {
+            return None;
+        }
+
+        let Expression::FunctionExpression(func) = ctx.ast.move_expression(expr) else {
+            unreachable!()
+        };
+        let func = func.unbox();
+        let mut body = func.body.unwrap();
+        let expression = body.directives.is_empty()
+            && matches!(body.statements.as_slice(), [Statement::ReturnStatement(ret)] if ret.argument.is_some());
+        if expression {
+            let Some(Statement::ReturnStatement(mut ret)) = body.statements.pop() else {
+                unreachable!()
+            };
+            let argument = ret.argument.take().unwrap();
+            body.statements.push(ctx.ast.statement_expression(argument.span(), argument));
+        }
+        if let Some(scope_id) = func.scope_id.get() {
+            ctx.scopes_mut().get_flags_mut(scope_id).insert(ScopeFlags::Arrow);
+        }
+        let arrow = ctx.ast.arrow_function_expression(
+            func.span,
+            expression,
+            false,
+            func.type_parameters,
+            func.params,
+            func.return_type,
+            body,
+        );
+        arrow.scope_id.set(func.scope_id.get());
+        Some(Expression::ArrowFunctionExpression(ctx.ast.alloc(arrow)))
+    }
+
+    /// `"a" + b + "c"` -> `` `a${b}c` `` when `b` is a primitive and the template literal is shorter.
+    fn try_compress_template_literal(
+        &self,
+        expr: &mut BinaryExpression<'a>,
+        ctx: &mut TraverseCtx<'a>,
+    ) -> Option<Expression<'a>> {
+        if !self.options.target.template_literals || expr.operator != BinaryOperator::Addition {
+            return None;
+        }
+        // Only start from the top of a `+` chain.
+        if matches!(ctx.parent(), Ancestor::BinaryExpressionLeft(parent) if *parent.operator() == BinaryOperator::Addition)
+        {
+            return None;
+        }
+
+        // Flatten the left-associative chain, the leftmost operand must be a string
+        // so that every `+` is a string concatenation.
+        let mut operands = vec![&expr.right];
+        let mut left = &expr.left;
+        while let Expression::BinaryExpression(e) = left {
+            if e.operator != BinaryOperator::Addition {
+                break;
+            }
+            operands.push(&e.right);
+            left = &e.left;
+        }
+        if !matches!(left, Expression::StringLiteral(_)) {
+            return None;
+        }
+        operands.push(left);
+        operands.reverse();
+
+        // `+` converts objects with `valueOf` first while templates use `toString`.
+        let mut expression_count = 0;
+        let mut concat_len = operands.len() - 1;
+        let mut template_len = 2;
+        for operand in &operands {
+            if let Expression::StringLiteral(lit) = operand {
+                concat_len += lit.value.len() + 2;
+                template_len += Self::escape_template_element(&lit.value).len();
+            } else {
+                if matches!(Ty::from(*operand), Ty::Object | Ty::Undetermined) {
+                    return None;
+                }
+                expression_count += 1;
+                template_len += 3;
+            }
+        }
+        if expression_count == 0 || template_len >= concat_len {
+            return None;
+        }
+
+        let mut quasis = ctx.ast.vec();
+        let mut expressions = ctx.ast.vec();
+        let mut raw = String::new();
+        let mut cooked = String::new();
+        let mut operands = std::vec::Vec::with_capacity(operands.len());
+        operands.push(ctx.ast.move_expression(&mut expr.right));
+        let mut left = ctx.ast.move_expression(&mut expr.left);
+        loop {
+            match left {
+                Expression::BinaryExpression(mut e) if e.operator == BinaryOperator::Addition => {
+                    operands.push(ctx.ast.move_expression(&mut e.right));
+                    left = ctx.ast.move_expression(&mut e.left);
+                }
+                left => {
+                    operands.push(left);
+                    break;
+                }
+            }
+        }
+        for operand in operands.into_iter().rev() {
+            if let Expression::StringLiteral(lit) = &operand {
+                raw.push_str(&Self::escape_template_element(&lit.value));
+                cooked.push_str(&lit.value);
+            } else {
+                let value = TemplateElementValue {
+                    raw: ctx.ast.atom(&std::mem::take(&mut raw)),
+                    cooked: Some(ctx.ast.atom(&std::mem::take(&mut cooked))),
+                };
+                quasis.push(ctx.ast.template_element(SPAN, false, value));
+                expressions.push(operand);
+            }
+        }
+        let value =
+            TemplateElementValue { raw: ctx.ast.atom(&raw), cooked: Some(ctx.ast.atom(&cooked)) };
+        quasis.push(ctx.ast.template_element(SPAN, true, value));
+        Some(ctx.ast.expression_template_literal(expr.span, quasis, expressions))
+    }
+
+    fn escape_template_element(value: &str) -> Cow<'_, str> {
+        if !value.contains(['\\', '`', '$', '\r']) {
+            return Cow::Borrowed(value);
+        }
+        let mut escaped = String::with_capacity(value.len() + 2);
+        let mut chars = value.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => escaped.push_str("\\\\"),
+                '`' => escaped.push_str("\\`"),
+                '$' if chars.peek() == Some(&'{') => escaped.push_str("\\$"),
+                '\r' => escaped.push_str("\\r"),
+                c => escaped.push(c),
+            }
+        }
+        Cow::Owned(escaped)
+    }
+}
+
+/// Finds usages of `this`, `arguments`, `super` and `new.target`,
+/// which would change meaning when a function is turned into an arrow function.
+#[derive(Default)]
+struct FunctionContextFinder {
+    found: bool,
+}
+
+impl<'a> Visit<'a> for FunctionContextFinder {
+    fn visit_this_expression(&mut self, _: &ThisExpression) {
+        self.found = true;
+    }
+
+    fn visit_super(&mut self, _: &Super) {
+        self.found = true;
+    }
+
+    fn visit_meta_property(&mut self, _: &MetaProperty<'a>) {
+        self.found = true;
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if ident.name == "arguments" {
+            self.found = true;
+        }
+    }
+
+    // Nested functions have their own `this` and `arguments`.
+    fn visit_function(&mut self, _: &Function<'a>, _: ScopeFlags) {}
+}
+
 /// <https://github.com/google/closure-compiler/blob/master/test/com/google/javascript/jscomp/PeepholeSubstituteAlternateSyntax.java>
 #[cfg(test)]
 mod test {
     use oxc_allocator::Allocator;
 
-    use crate::{tester, CompressOptions};
+    use crate::{tester, CompressOptions, CompressTarget};
 
     fn test(source_text: &str, expected: &str) {
         let allocator = Allocator::default();
@@ -338,6 +830,18 @@ mod test {
         tester::test(&allocator, source_text, expected, &mut pass);
     }
 
+    fn test_esnext(source_text: &str, expected: &str) {
+        let allocator = Allocator::default();
+        let options =
+            CompressOptions { target: CompressTarget::esnext(), ..CompressOptions::default() };
+        let mut pass = super::PeepholeSubstituteAlternateSyntax::new(options);
+        tester::test(&allocator, source_text, expected, &mut pass);
+    }
+
+    fn test_esnext_same(source_text: &str) {
+        test_esnext(source_text, source_text);
+    }
+
     fn test_same(source_text: &str) {
         test(source_text, source_text);
     }
@@ -393,4 +897,158 @@ mod test {
         test_same("x += 1"); // The string concatenation may be triggered, so we don't fold this.
         test_same("x += -1");
     }
+
+    #[test]
+    fn test_infinity() {
+        test_same("x = Infinity");
+
+        let allocator = Allocator::default();
+        let options = CompressOptions { keep_infinity: false, ..CompressOptions::default() };
+        let mut pass = super::PeepholeSubstituteAlternateSyntax::new(options);
+        let mut test = |source_text, expected| {
+            tester::test(&allocator, source_text, expected, &mut pass);
+        };
+        test("x = Infinity", "x = 1 / 0");
+        test("x = -Infinity", "x = -1 / 0");
+        test("x = Infinity.toString()", "x = (1 / 0).toString()");
+        test("var Infinity; x = Infinity", "var Infinity; x = Infinity");
+    }
+
+    #[test]
+    fn test_unsafe_proto() {
+        test_same("Array.prototype.slice.call(a)");
+
+        let allocator = Allocator::default();
+        let options = CompressOptions { unsafe_proto: true, ..CompressOptions::default() };
+        let mut pass = super::PeepholeSubstituteAlternateSyntax::new(options);
+        let mut test = |source_text, expected| {
+            tester::test(&allocator, source_text, expected, &mut pass);
+        };
+        test("Array.prototype.slice.call(a)", "[].slice.call(a)");
+        test("x = Object.prototype.hasOwnProperty", "x = {}.hasOwnProperty");
+        test("String.prototype.trim.call(a)", "''.trim.call(a)");
+        test("x = Array.prototype", "x = Array.prototype");
+        test("Function.prototype.call", "Function.prototype.call");
+        test("var Array; Array.prototype.slice", "var Array; Array.prototype.slice");
+    }
+
+    #[test]
+    fn test_target_default_keeps_syntax() {
+        test_same("a = a || b");
+        test_same("x != null ? x : y");
+        test_same("x == null ? void 0 : x.y");
+        test_same("Object.assign({}, a)");
+        test_same("foo(function() { return 1 })");
+        test_same("'a' + 1 + 'b'");
+    }
+
+    #[test]
+    fn test_logical_assignment() {
+        test_esnext("a = a || b", "a ||= b");
+        test_esnext("a = a && b", "a &&= b");
+        test_esnext("a = a ?? b", "a ??= b");
+        test_esnext_same("a = b || c");
+        test_esnext_same("a.b = a.b || c");
+        test_esnext_same("a += a || b");
+    }
+
+    #[test]
+    fn test_nullish_coalescing() {
+        test_esnext("x !== null && x !== void 0 ? x : y", "x ?? y");
+        test_esnext("x !== void 0 && x !== null ? x : y", "x ?? y");
+        test_esnext("x === null || x === void 0 ? y : x", "x ?? y");
+        test_esnext("x != null ? x : y", "x ?? y");
+        test_esnext("x == null ? y : x", "x ?? y");
+        test_esnext("x == void 0 ? y : x", "x ?? y");
+        test_esnext_same("x !== null ? x : y");
+        test_esnext_same("x !== null && z !== void 0 ? x : y");
+        test_esnext_same("x !== null && x !== null ? x : y");
+        test_esnext_same("x != null ? z : y");
+    }
+
+    #[test]
+    fn test_optional_chaining() {
+        test_esnext("x == null ? void 0 : x.y", "x?.y");
+        test_esnext("x == null ? void 0 : x.y.z", "x?.y.z");
+        test_esnext("x == null ? void 0 : x[y]", "x?.[y]");
+        test_esnext("x == null ? void 0 : x.y()", "x?.y()");
+        test_esnext("x == null ? void 0 : x()", "x?.()");
+        test_esnext("x === null || x === void 0 ? void 0 : x.y", "x?.y");
+        test_esnext("x != null ? x.y : void 0", "x?.y");
+        test_esnext_same("x == null ? void 0 : z.y");
+        test_esnext_same("x == null ? null : x.y");
+        test_esnext_same("x == null ? void 0 : y(x)");
+    }
+
+    #[test]
+    fn test_object_assign() {
+        test_esnext("Object.assign({}, a)", "({ ...a })");
+        test_esnext("Object.assign({}, a, b)", "({ ...a, ...b })");
+        test_esnext_same("Object.assign(a, b)");
+        test_esnext_same("Object.assign({ a }, b)");
+        test_esnext_same("Object.assign({}, ...a)");
+        test_esnext_same("Object.assign({})");
+        test_esnext_same("var Object; Object.assign({}, a)");
+    }
+
+    #[test]
+    fn test_arrow_function() {
+        test_esnext("foo(function() { return 1 })", "foo(() => 1)");
+        test_esnext("foo(function(a) { bar(a); })", "foo((a) => { bar(a); })");
+        test_esnext("foo(function() {})", "foo(() => {})");
+        test_esnext(
+            "foo(function() { return function() { return this } })",
+            "foo(() => function() { return this })",
+        );
+        test_esnext_same("foo(function() { return this })");
+        test_esnext_same("foo(function() { return arguments })");
+        test_esnext_same("foo(function() { return new.target })");
+        test_esnext_same("foo(function() { return () => this })");
+        test_esnext_same("foo(function f() { return 1 })");
+        test_esnext_same("foo(function*() { yield 1 })");
+        test_esnext_same("foo(async function() { await 1 })");
+        test_esnext_same("new function() { return 1 }");
+        test_esnext_same("(function() { return 1 }).call(a)");
+        test_esnext_same("foo(function(a = this) { return a })");
+        test_esnext_same("foo(function(a, a) { return a })");
+        // Not constructible and without `prototype` as an arrow function.
+        test_esnext_same("var F = function() {}; new F(); F.prototype.x = 1");
+        test_esnext_same("x = { f: function() { return 1 } }");
+        test_esnext_same("new Foo(function() { return 1 })");
+        test_esnext_same(
+            "Object.defineProperty(exports, 'a', { enumerable: !0, get: function() { return a } })",
+        );
+    }
+
+    #[test]
+    fn test_unsafe_arrows() {
+        let allocator = Allocator::default();
+        let options = CompressOptions {
+            target: CompressTarget::esnext(),
+            unsafe_arrows: true,
+            ..CompressOptions::default()
+        };
+        let mut pass = super::PeepholeSubstituteAlternateSyntax::new(options);
+        let mut test = |source_text, expected| {
+            tester::test(&allocator, source_text, expected, &mut pass);
+        };
+        test("var f = function() { return 1 }", "var f = () => 1");
+        test("x = { f: function() { return 1 } }", "x = { f: () => 1 }");
+        test("new function() { return 1 }", "new function() { return 1 }");
+        test("var f = function() { return this }", "var f = function() { return this }");
+    }
+
+    #[test]
+    fn test_template_literal() {
+        test_esnext("'abc' + 1 + 'def'", "`abc${1}def`");
+        test_esnext("'abc' + void 0 + 'def' + !0 + 'ghi'", "`abc${void 0}def${!0}ghi`");
+        test_esnext("'a`' + 1 + 'b' + 2 + 'c' + 3 + 'd'", "`a\\`${1}b${2}c${3}d`");
+        test_esnext("'${' + 1 + 'b' + 2 + 'c' + 3 + 'd'", "`\\${${1}b${2}c${3}d`");
+        // Escaping makes the template literal longer.
+        test_esnext_same("'a`b' + 1 + 'c'");
+        test_esnext_same("'a' + 1");
+        test_esnext_same("'abc' + x + 'def'");
+        test_esnext_same("1 + 2 + 'abc'");
+        test_esnext_same("'abc' + 'def'");
+    }
 }
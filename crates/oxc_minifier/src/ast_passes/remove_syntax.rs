@@ -1,15 +1,18 @@
 use oxc_allocator::Vec;
 use oxc_ast::ast::*;
 use oxc_span::GetSpan;
+use oxc_syntax::operator::UnaryOperator;
 use oxc_traverse::{Traverse, TraverseCtx};
 
-use crate::{CompressOptions, CompressorPass};
+use crate::{node_util::MayHaveSideEffects, CompressOptions, CompressorPass};
 
 /// Remove syntax from the AST.
 ///
 /// * Parenthesized Expression
 /// * `debugger`
 /// * `console.log`
+/// * Labeled statements from `drop_labels`
+/// * Unused calls to `pure_funcs`
 pub struct RemoveSyntax {
     options: CompressOptions,
 }
@@ -29,12 +32,29 @@ impl<'a> Traverse<'a> for RemoveSyntax {
         stmts.retain(|stmt| {
             !(matches!(stmt, Statement::EmptyStatement(_))
                 || self.drop_debugger(stmt)
-                || self.drop_console(stmt))
+                || self.drop_console(stmt)
+                || self.drop_label(stmt))
         });
     }
 
+    fn exit_statements(&mut self, stmts: &mut Vec<'a, Statement<'a>>, _ctx: &mut TraverseCtx<'a>) {
+        // Remove the statements emptied by `drop_pure_call`.
+        if stmts.iter().any(|stmt| matches!(stmt, Statement::EmptyStatement(_))) {
+            stmts.retain(|stmt| !matches!(stmt, Statement::EmptyStatement(_)));
+        }
+    }
+
+    fn enter_statement(&mut self, stmt: &mut Statement<'a>, ctx: &mut TraverseCtx<'a>) {
+        if self.drop_label(stmt) {
+            *stmt = ctx.ast.statement_empty(stmt.span());
+            return;
+        }
+        self.drop_pure_call(stmt, ctx);
+    }
+
     fn enter_expression(&mut self, expr: &mut Expression<'a>, ctx: &mut TraverseCtx<'a>) {
         self.compress_console(expr, ctx);
+        self.drop_pure_calls_in_expression(expr, ctx);
     }
 
     fn exit_expression(&mut self, expr: &mut Expression<'a>, ctx: &mut TraverseCtx<'a>) {
@@ -76,6 +96,139 @@ impl<'a> RemoveSyntax {
             && matches!(stmt, Statement::ExpressionStatement(expr) if Self::is_console(&expr.expression))
     }
 
+    /// Drop labeled statements.
+    ///
+    /// Enabled by `compress.drop_labels`
+    fn drop_label(&self, stmt: &Statement<'a>) -> bool {
+        matches!(stmt, Statement::LabeledStatement(labeled_stmt)
+            if self.options.drop_labels.iter().any(|label| labeled_stmt.label.name == label.as_str()))
+    }
+
+    /// Drop calls to pure functions whose return value is unused,
+    /// keeping the arguments which have side effects.
+    ///
+    /// Enabled by `compress.pure_funcs`
+    fn drop_pure_call(&self, stmt: &mut Statement<'a>, ctx: &mut TraverseCtx<'a>) {
+        if self.options.pure_funcs.is_empty() {
+            return;
+        }
+        match stmt {
+            Statement::ExpressionStatement(expr_stmt) => {
+                if self.drop_unused_pure_calls(&mut expr_stmt.expression, ctx) {
+                    *stmt = ctx.ast.statement_empty(expr_stmt.span);
+                }
+            }
+            Statement::ForStatement(for_stmt) => {
+                if let Some(init) = &mut for_stmt.init {
+                    if let Some(expr) = init.as_expression_mut() {
+                        if self.drop_unused_pure_calls(expr, ctx) {
+                            for_stmt.init = None;
+                        }
+                    }
+                }
+                if let Some(update) = &mut for_stmt.update {
+                    if self.drop_unused_pure_calls(update, ctx) {
+                        for_stmt.update = None;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Drop pure calls in the leading expressions of a sequence and in the argument of `void`.
+    ///
+    /// Enabled by `compress.pure_funcs`
+    fn drop_pure_calls_in_expression(&self, expr: &mut Expression<'a>, ctx: &mut TraverseCtx<'a>) {
+        if self.options.pure_funcs.is_empty() {
+            return;
+        }
+        match expr {
+            Expression::SequenceExpression(sequence_expr) => {
+                let Some(last) = sequence_expr.expressions.pop() else { return };
+                let mut expressions = ctx.ast.vec();
+                for mut expr in sequence_expr.expressions.drain(..) {
+                    if !self.drop_unused_pure_calls(&mut expr, ctx) {
+                        expressions.push(expr);
+                    }
+                }
+                expressions.push(last);
+                if expressions.len() == 1 {
+                    *expr = expressions.pop().unwrap();
+                } else {
+                    sequence_expr.expressions = expressions;
+                }
+            }
+            Expression::UnaryExpression(unary_expr)
+                if unary_expr.operator == UnaryOperator::Void =>
+            {
+                if self.drop_unused_pure_calls(&mut unary_expr.argument, ctx) {
+                    *expr = ctx.ast.void_0(unary_expr.span);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Replace calls to pure functions in `expr`, whose value is unused, with their arguments
+    /// which have side effects.
+    ///
+    /// Returns `true` when nothing is left of `expr`.
+    fn drop_unused_pure_calls(&self, expr: &mut Expression<'a>, ctx: &mut TraverseCtx<'a>) -> bool {
+        match expr {
+            Expression::CallExpression(call_expr) => {
+                let Some(name) = Self::get_callee_name(&call_expr.callee) else { return false };
+                if !self.options.pure_funcs.iter().any(|pure_func| *pure_func == name) {
+                    return false;
+                }
+                if call_expr.arguments.iter().any(Argument::is_spread) {
+                    return false;
+                }
+                let mut expressions = ctx.ast.vec();
+                for arg in call_expr.arguments.iter_mut() {
+                    let expr = arg.to_expression_mut();
+                    if expr.may_have_side_effects() {
+                        expressions.push(ctx.ast.move_expression(expr));
+                    }
+                }
+                let span = call_expr.span;
+                match expressions.len() {
+                    0 => return true,
+                    1 => *expr = expressions.pop().unwrap(),
+                    _ => *expr = ctx.ast.expression_sequence(span, expressions),
+                }
+                false
+            }
+            Expression::SequenceExpression(sequence_expr) => {
+                let mut expressions = ctx.ast.vec();
+                for mut expr in sequence_expr.expressions.drain(..) {
+                    if !self.drop_unused_pure_calls(&mut expr, ctx) {
+                        expressions.push(expr);
+                    }
+                }
+                match expressions.len() {
+                    0 => return true,
+                    1 => *expr = expressions.pop().unwrap(),
+                    _ => sequence_expr.expressions = expressions,
+                }
+                false
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns the dotted name of a callee, e.g. `console.log`.
+    fn get_callee_name(expr: &Expression<'a>) -> Option<String> {
+        match expr {
+            Expression::Identifier(ident) => Some(ident.name.to_string()),
+            Expression::StaticMemberExpression(member_expr) => {
+                let object = Self::get_callee_name(&member_expr.object)?;
+                Some(format!("{object}.{}", member_expr.property.name))
+            }
+            _ => None,
+        }
+    }
+
     fn compress_console(&mut self, expr: &mut Expression<'a>, ctx: &mut TraverseCtx<'a>) {
         if self.options.drop_console && Self::is_console(expr) {
             *expr = ctx.ast.void_0(expr.span());
@@ -125,4 +278,40 @@ mod test {
     fn drop_debugger() {
         test("debugger", "");
     }
+
+    #[test]
+    fn drop_labels() {
+        let allocator = Allocator::default();
+        let options =
+            CompressOptions { drop_labels: vec!["DEV".into()], ..CompressOptions::all_true() };
+        let mut pass = super::RemoveSyntax::new(options);
+        tester::test(&allocator, "DEV: foo(); bar()", "bar()", &mut pass);
+        tester::test(&allocator, "DEV: { foo() } bar()", "bar()", &mut pass);
+        tester::test(&allocator, "if (x) DEV: foo()", "if (x);", &mut pass);
+        tester::test(&allocator, "PROD: foo()", "PROD: foo()", &mut pass);
+    }
+
+    #[test]
+    fn pure_funcs() {
+        let allocator = Allocator::default();
+        let options = CompressOptions {
+            pure_funcs: vec!["debug".into(), "Logger.info".into()],
+            ..CompressOptions::all_true()
+        };
+        let mut pass = super::RemoveSyntax::new(options);
+        tester::test(&allocator, "debug(a, 1)", "", &mut pass);
+        tester::test(&allocator, "Logger.info('a')", "", &mut pass);
+        tester::test(&allocator, "debug(a(), b, c())", "a(), c()", &mut pass);
+        tester::test(&allocator, "debug(a())", "a()", &mut pass);
+        tester::test(&allocator, "x = debug(a)", "x = debug(a)", &mut pass);
+        tester::test(&allocator, "debug(...a)", "debug(...a)", &mut pass);
+        tester::test(&allocator, "Logger.warn('a')", "Logger.warn('a')", &mut pass);
+        tester::test(&allocator, "info('a')", "info('a')", &mut pass);
+        tester::test(&allocator, "debug(a), debug(b()), foo()", "b(), foo()", &mut pass);
+        tester::test(&allocator, "x = (debug(a), foo())", "x = foo()", &mut pass);
+        tester::test(&allocator, "x = (foo(), debug(a))", "x = (foo(), debug(a))", &mut pass);
+        tester::test(&allocator, "x = void debug(a)", "x = void 0", &mut pass);
+        tester::test(&allocator, "for (debug(a); ; debug(b)) {}", "for (;;) {}", &mut pass);
+        tester::test(&allocator, "for (debug(a()); ; ) {}", "for (a(); ; ) {}", &mut pass);
+    }
 }
@@ -1,31 +1,48 @@
 use oxc_allocator::Allocator;
-use oxc_ast::ast::*;
+use oxc_ast::{ast::*, Trivias};
+use oxc_diagnostics::OxcDiagnostic;
 use oxc_semantic::{ScopeTree, SemanticBuilder, SymbolTable};
+use oxc_transformer::{ReplaceGlobalDefines, ReplaceGlobalDefinesConfig};
 use oxc_traverse::TraverseCtx;
 
 use crate::{
     ast_passes::{
-        CollapseVariableDeclarations, ExploitAssigns, PeepholeFoldConstants,
-        PeepholeMinimizeConditions, PeepholeRemoveDeadCode, PeepholeReplaceKnownMethods,
-        PeepholeSubstituteAlternateSyntax, RemoveSyntax, StatementFusion,
+        CollapseVariableDeclarations, ExploitAssigns, MinifyEmbeddedTemplates, MinimizeExitPoints,
+        PeepholeFoldConstants, PeepholeMinimizeConditions, PeepholeRemoveDeadCode,
+        PeepholeReplaceKnownMethods, PeepholeSubstituteAlternateSyntax, RemoveSyntax,
+        StatementFusion,
     },
     CompressOptions, CompressorPass,
 };
 
+pub struct CompressorReturn {
+    /// Warnings about code which could not be compressed, e.g. embedded templates.
+    pub diagnostics: Vec<OxcDiagnostic>,
+}
+
 pub struct Compressor<'a> {
     allocator: &'a Allocator,
     options: CompressOptions,
+    comments: Option<(&'a str, Trivias)>,
 }
 
 impl<'a> Compressor<'a> {
     pub fn new(allocator: &'a Allocator, options: CompressOptions) -> Self {
-        Self { allocator, options }
+        Self { allocator, options, comments: None }
+    }
+
+    /// Provide the comments of the program, which are needed for the `/* css */` style hints of
+    /// [CompressOptions::embedded_templates].
+    #[must_use]
+    pub fn with_comments(mut self, source_text: &'a str, trivias: Trivias) -> Self {
+        self.comments = Some((source_text, trivias));
+        self
     }
 
-    pub fn build(self, program: &mut Program<'a>) {
+    pub fn build(self, program: &mut Program<'a>) -> CompressorReturn {
         let (symbols, scopes) =
             SemanticBuilder::new("").build(program).semantic.into_symbol_table_and_scope_tree();
-        self.build_with_symbols_and_scopes(symbols, scopes, program);
+        self.build_with_symbols_and_scopes(symbols, scopes, program)
     }
 
     pub fn build_with_symbols_and_scopes(
@@ -33,27 +50,55 @@ impl<'a> Compressor<'a> {
         symbols: SymbolTable,
         scopes: ScopeTree,
         program: &mut Program<'a>,
-    ) {
+    ) -> CompressorReturn {
+        let mut diagnostics = vec![];
+
+        // Replace `global_defs` first so that the branches they make dead are removed.
+        let (symbols, scopes) = if self.options.global_defs.is_empty() {
+            (symbols, scopes)
+        } else {
+            match ReplaceGlobalDefinesConfig::new(&self.options.global_defs) {
+                Ok(config) => {
+                    let ret = ReplaceGlobalDefines::new(self.allocator, config)
+                        .build(symbols, scopes, program);
+                    (ret.symbols, ret.scopes)
+                }
+                Err(errors) => {
+                    diagnostics.extend(errors);
+                    (symbols, scopes)
+                }
+            }
+        };
+
         let mut ctx = TraverseCtx::new(scopes, symbols, self.allocator);
-        RemoveSyntax::new(self.options).build(program, &mut ctx);
+        RemoveSyntax::new(self.options.clone()).build(program, &mut ctx);
+
+        if let Some(options) = &self.options.embedded_templates {
+            let mut pass = MinifyEmbeddedTemplates::new(options.clone());
+            if let Some((source_text, trivias)) = &self.comments {
+                pass = pass.with_comment_hints(source_text, trivias);
+            }
+            pass.build(program, &mut ctx);
+            diagnostics.extend(pass.into_diagnostics());
+        }
 
         if self.options.dead_code_elimination {
             self.dead_code_elimination(program, &mut ctx);
-            return;
+            return CompressorReturn { diagnostics };
         }
 
         ExploitAssigns::new().build(program, &mut ctx);
-        CollapseVariableDeclarations::new(self.options).build(program, &mut ctx);
+        CollapseVariableDeclarations::new(self.options.clone()).build(program, &mut ctx);
 
         // See `latePeepholeOptimizations`
-        let mut passes: [&mut dyn CompressorPass; 6] = [
+        let mut passes: [&mut dyn CompressorPass; 7] = [
             &mut StatementFusion::new(),
             &mut PeepholeRemoveDeadCode::new(),
-            // TODO: MinimizeExitPoints
-            &mut PeepholeMinimizeConditions::new(),
-            &mut PeepholeSubstituteAlternateSyntax::new(self.options),
+            &mut MinimizeExitPoints::new(),
+            &mut PeepholeMinimizeConditions::new(self.options.clone()),
+            &mut PeepholeSubstituteAlternateSyntax::new(self.options.clone()),
             &mut PeepholeReplaceKnownMethods::new(),
-            &mut PeepholeFoldConstants::new(),
+            &mut PeepholeFoldConstants::new(self.options.clone()),
         ];
 
         let mut i = 0;
@@ -68,17 +113,22 @@ impl<'a> Compressor<'a> {
             if !changed {
                 break;
             }
+            if self.options.passes > 0 && i + 1 >= self.options.passes {
+                break;
+            }
             if i > 50 {
                 debug_assert!(false, "Ran in a infinite loop.");
                 break;
             }
             i += 1;
         }
+
+        CompressorReturn { diagnostics }
     }
 
     fn dead_code_elimination(self, program: &mut Program<'a>, ctx: &mut TraverseCtx<'a>) {
-        PeepholeFoldConstants::new().build(program, ctx);
-        PeepholeMinimizeConditions::new().build(program, ctx);
+        PeepholeFoldConstants::new(self.options.clone()).build(program, ctx);
+        PeepholeMinimizeConditions::new(self.options.clone()).build(program, ctx);
         PeepholeRemoveDeadCode::new().build(program, ctx);
     }
 }
@@ -0,0 +1,85 @@
+use super::contains_placeholder;
+
+/// Remove comments and whitespace which is not significant in CSS.
+///
+/// Whitespace around `{`, `}`, `;`, `,`, `>` and `~`, after `:` and before `!` is removed,
+/// all other whitespace is collapsed into a single space.
+/// Whitespace around `+` and `-` is kept because it is significant inside `calc()`.
+pub fn minify(text: &str) -> Result<String, &'static str> {
+    let mut out = String::with_capacity(text.len());
+    let mut pending_space = false;
+    let mut chars = text.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '/' if chars.peek().is_some_and(|(_, c)| *c == '*') => {
+                let end = text[i + 2..].find("*/").ok_or("unterminated comment")? + i + 4;
+                if contains_placeholder(&text[i..end]) {
+                    return Err("a substitution is inside a comment");
+                }
+                while chars.peek().is_some_and(|(j, _)| *j < end) {
+                    chars.next();
+                }
+                pending_space = true;
+            }
+            c if c.is_whitespace() => pending_space = true,
+            _ => {
+                let prev = out.chars().last();
+                if pending_space && prev.is_some_and(|prev| needs_space(prev, c)) {
+                    out.push(' ');
+                }
+                pending_space = false;
+                if c == '}' && prev == Some(';') {
+                    out.pop();
+                }
+                out.push(c);
+                if matches!(c, '"' | '\'') {
+                    loop {
+                        let Some((_, s)) = chars.next() else { return Err("unterminated string") };
+                        out.push(s);
+                        if s == c {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn needs_space(prev: char, next: char) -> bool {
+    !matches!(prev, '{' | '}' | ';' | ',' | '>' | '~' | ':')
+        && !matches!(next, '{' | '}' | ';' | ',' | '>' | '~' | '!')
+}
+
+#[cfg(test)]
+mod test {
+    use super::minify;
+
+    fn test(source_text: &str, expected: &str) {
+        assert_eq!(minify(source_text).as_deref(), Ok(expected));
+    }
+
+    #[test]
+    fn whitespace() {
+        test(
+            "\n  .a  .b > .c {\n    color: red;\n    margin: 0 auto !important;\n  }\n",
+            ".a .b>.c{color:red;margin:0 auto!important}",
+        );
+        test("a:hover , b ~ c{}", "a:hover,b~c{}");
+        test("div :hover {}", "div :hover{}");
+        test("a { width: calc(100% - 2px + 1em) }", "a{width:calc(100% - 2px + 1em)}");
+        test(
+            "@media (min-width: 100px) and (max-width: 200px) { a { b: c } }",
+            "@media (min-width:100px) and (max-width:200px){a{b:c}}",
+        );
+    }
+
+    #[test]
+    fn comments_and_strings() {
+        test("a { /* comment */ color: red }", "a{color:red}");
+        test("a { content: '  /* x */  ' }", "a{content:'  /* x */  '}");
+        assert!(minify("a { /* comment }").is_err());
+        assert!(minify("a { content: 'x }").is_err());
+    }
+}
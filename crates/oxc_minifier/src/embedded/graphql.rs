@@ -0,0 +1,80 @@
+use super::contains_placeholder;
+
+/// Remove comments and insignificant whitespace and commas in GraphQL.
+pub fn minify(text: &str) -> Result<String, &'static str> {
+    let mut out = String::with_capacity(text.len());
+    let mut pending_space = false;
+    let mut pos = 0;
+    while pos < text.len() {
+        let rest = &text[pos..];
+        let c = rest.chars().next().unwrap();
+        match c {
+            '#' => {
+                let end = rest.find(['\n', '\r']).unwrap_or(rest.len());
+                if contains_placeholder(&rest[..end]) {
+                    return Err("a substitution is inside a comment");
+                }
+                pos += end;
+                pending_space = true;
+            }
+            ',' => {
+                pos += 1;
+                pending_space = true;
+            }
+            c if c.is_whitespace() => {
+                pos += c.len_utf8();
+                pending_space = true;
+            }
+            _ => {
+                if pending_space && out.ends_with(is_name_char) && (is_name_char(c) || c == '"') {
+                    out.push(' ');
+                }
+                pending_space = false;
+                let len = if let Some(block) = rest.strip_prefix("\"\"\"") {
+                    block.find("\"\"\"").ok_or("unterminated block string")? + 6
+                } else if c == '"' {
+                    rest[1..]
+                        .find(['"', '\n'])
+                        .filter(|&i| rest[1 + i..].starts_with('"'))
+                        .ok_or("unterminated string")?
+                        + 2
+                } else {
+                    c.len_utf8()
+                };
+                out.push_str(&rest[..len]);
+                pos += len;
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod test {
+    use super::minify;
+
+    fn test(source_text: &str, expected: &str) {
+        assert_eq!(minify(source_text).as_deref(), Ok(expected));
+    }
+
+    #[test]
+    fn whitespace() {
+        test(
+            "\n  query Foo($id: ID!, $n: Int = 10) {\n    user(id: $id) {\n      ...UserFields\n      friends(first: $n) { name }\n    }\n  }\n",
+            "query Foo($id:ID!$n:Int=10){user(id:$id){...UserFields friends(first:$n){name}}}",
+        );
+        test("fragment A on User @x { a }", "fragment A on User@x{a}");
+    }
+
+    #[test]
+    fn comments_and_strings() {
+        test("{ a # comment\n b }", "{a b}");
+        test("{ a(s: \"x,  # y\") }", "{a(s:\"x,  # y\")}");
+        test("\"\"\"\n  doc  \n\"\"\" type A { a: Int }", "\"\"\"\n  doc  \n\"\"\"type A{a:Int}");
+        assert!(minify("{ a(s: \"x) }").is_err());
+    }
+}
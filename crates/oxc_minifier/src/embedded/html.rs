@@ -0,0 +1,131 @@
+use super::contains_placeholder;
+
+/// Elements whose content is kept verbatim.
+const RAW_TEXT_ELEMENTS: [&str; 4] = ["pre", "textarea", "script", "style"];
+
+/// Remove comments and collapse whitespace in HTML.
+///
+/// Whitespace between elements may be rendered, so runs of whitespace are collapsed into a
+/// single space instead of being removed. Only whitespace containing a newline at the start
+/// and end of the template is removed, as well as whitespace inside tags.
+pub fn minify(text: &str) -> Result<String, &'static str> {
+    let mut out = String::with_capacity(text.len());
+    let mut pos = 0;
+    while pos < text.len() {
+        let rest = &text[pos..];
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            let end = comment.find("-->").ok_or("unterminated comment")? + 4 + 3;
+            if contains_placeholder(&rest[..end]) {
+                return Err("a substitution is inside a comment");
+            }
+            pos += end;
+        } else if rest.starts_with('<')
+            && rest[1..]
+                .starts_with(|c: char| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '_'))
+        {
+            let tag_end = minify_tag(rest, &mut out)?;
+            pos += tag_end;
+            if let Some(name) = raw_text_element(rest) {
+                let close = find_ignore_ascii_case(&text[pos..], &format!("</{name}"))
+                    .ok_or("unclosed raw text element")?;
+                out.push_str(&text[pos..pos + close]);
+                pos += close;
+            }
+        } else {
+            let c = rest.chars().next().unwrap();
+            if c.is_whitespace() {
+                let len = rest.find(|c: char| !c.is_whitespace()).unwrap_or(rest.len());
+                let at_boundary = pos == 0 || pos + len == text.len();
+                if !(at_boundary && rest[..len].contains('\n')) {
+                    out.push(' ');
+                }
+                pos += len;
+            } else {
+                out.push(c);
+                pos += c.len_utf8();
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Copy the tag at the start of `text` into `out` with its whitespace collapsed.
+/// Returns the length of the tag.
+fn minify_tag(text: &str, out: &mut String) -> Result<usize, &'static str> {
+    let mut chars = text.char_indices().peekable();
+    let mut pending_space = false;
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => pending_space = true,
+            '>' => {
+                out.push('>');
+                return Ok(i + 1);
+            }
+            _ => {
+                if pending_space
+                    && !out.ends_with(['<', '='])
+                    && c != '='
+                    && !(c == '/' && chars.peek().is_some_and(|(_, c)| *c == '>'))
+                {
+                    out.push(' ');
+                }
+                pending_space = false;
+                out.push(c);
+                if matches!(c, '"' | '\'') {
+                    loop {
+                        let Some((_, s)) = chars.next() else {
+                            return Err("unterminated attribute");
+                        };
+                        out.push(s);
+                        if s == c {
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Err("unterminated tag")
+}
+
+fn raw_text_element(tag: &str) -> Option<&'static str> {
+    let name_end = tag[1..].find(|c: char| c.is_whitespace() || c == '>' || c == '/')? + 1;
+    let name = &tag[1..name_end];
+    RAW_TEXT_ELEMENTS.into_iter().find(|element| element.eq_ignore_ascii_case(name))
+}
+
+fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    haystack.char_indices().map(|(i, _)| i).find(|&i| {
+        haystack.get(i..i + needle.len()).is_some_and(|s| s.eq_ignore_ascii_case(needle))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::minify;
+
+    fn test(source_text: &str, expected: &str) {
+        assert_eq!(minify(source_text).as_deref(), Ok(expected));
+    }
+
+    #[test]
+    fn whitespace() {
+        test(
+            "\n  <div\n    class=\"a  b\"\n  >\n    <b>x</b>  <i>y</i>\n  </div>\n",
+            "<div class=\"a  b\"> <b>x</b> <i>y</i> </div>",
+        );
+        test("<input type = 'text' />", "<input type='text'/>");
+        test(" <span>a</span> ", " <span>a</span> ");
+        test("<p>a > b</p>", "<p>a > b</p>");
+        test("<p>a  <  b</p>", "<p>a < b</p>");
+    }
+
+    #[test]
+    fn comments_and_raw_text() {
+        test("<p><!-- comment -->a</p>", "<p>a</p>");
+        test("<pre>\n  a  b\n</pre>", "<pre>\n  a  b\n</pre>");
+        test("<STYLE> a  { } </style>", "<STYLE> a  { } </style>");
+        assert!(minify("<p><!-- a </p>").is_err());
+        assert!(minify("<pre>a").is_err());
+    }
+}
@@ -0,0 +1,111 @@
+//! Minifiers for CSS, HTML and GraphQL embedded in template literals.
+//!
+//! The minifiers work on the raw text of a template literal, where each `${}` substitution is
+//! replaced by a placeholder, and only remove comments and insignificant whitespace.
+
+mod css;
+mod graphql;
+mod html;
+
+/// Languages which can be minified inside template literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmbeddedLanguage {
+    Css,
+    Html,
+    GraphQL,
+}
+
+impl EmbeddedLanguage {
+    /// Parse the language of a comment hint such as `/* css */`.
+    pub fn from_hint(comment: &str) -> Option<Self> {
+        match comment.trim() {
+            "css" => Some(Self::Css),
+            "html" => Some(Self::Html),
+            "graphql" | "GraphQL" | "gql" => Some(Self::GraphQL),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Css => "CSS",
+            Self::Html => "HTML",
+            Self::GraphQL => "GraphQL",
+        }
+    }
+}
+
+const PLACEHOLDER_PREFIX: &str = "__OXC_EMBED_";
+
+fn placeholder(index: usize) -> String {
+    format!("{PLACEHOLDER_PREFIX}{index}__")
+}
+
+/// Minify the raw text of the quasis of a template literal.
+///
+/// Returns the minified quasis, which has the same length as `quasis`.
+///
+/// # Errors
+///
+/// Returns the reason when the template cannot be minified without changing its meaning,
+/// e.g. a substitution inside a comment.
+pub fn minify_quasis(
+    language: EmbeddedLanguage,
+    quasis: &[&str],
+) -> Result<Vec<String>, &'static str> {
+    if quasis.iter().any(|quasi| quasi.contains('\\')) {
+        return Err("escape sequences are not supported");
+    }
+    if quasis.iter().any(|quasi| quasi.contains(PLACEHOLDER_PREFIX)) {
+        return Err("the template contains a reserved placeholder name");
+    }
+
+    let mut text = String::new();
+    for (i, quasi) in quasis.iter().enumerate() {
+        if i > 0 {
+            text.push_str(&placeholder(i - 1));
+        }
+        text.push_str(quasi);
+    }
+
+    let minified = match language {
+        EmbeddedLanguage::Css => css::minify(&text)?,
+        EmbeddedLanguage::Html => html::minify(&text)?,
+        EmbeddedLanguage::GraphQL => graphql::minify(&text)?,
+    };
+
+    let mut result = Vec::with_capacity(quasis.len());
+    let mut rest = minified.as_str();
+    for i in 0..quasis.len() - 1 {
+        let placeholder = placeholder(i);
+        let Some(index) = rest.find(&placeholder) else {
+            return Err("a substitution was removed");
+        };
+        result.push(rest[..index].to_string());
+        rest = &rest[index + placeholder.len()..];
+    }
+    if rest.contains(PLACEHOLDER_PREFIX) {
+        return Err("a substitution was reordered");
+    }
+    result.push(rest.to_string());
+    Ok(result)
+}
+
+fn contains_placeholder(text: &str) -> bool {
+    text.contains(PLACEHOLDER_PREFIX)
+}
+
+#[cfg(test)]
+mod test {
+    use super::{minify_quasis, EmbeddedLanguage};
+
+    #[test]
+    fn quasis() {
+        let css = minify_quasis(EmbeddedLanguage::Css, &["\n  a {\n    color: ", ";\n  }\n"]);
+        assert_eq!(css, Ok(vec!["a{color:".to_string(), "}".to_string()]));
+        let css = minify_quasis(EmbeddedLanguage::Css, &["a { /* ", " */ }"]);
+        assert_eq!(css, Err("a substitution is inside a comment"));
+        let css = minify_quasis(EmbeddedLanguage::Css, &["a { content: '\\f101' }"]);
+        assert_eq!(css, Err("escape sequences are not supported"));
+    }
+}
@@ -4,9 +4,11 @@
 
 mod ast_passes;
 mod compressor;
+mod embedded;
 mod keep_var;
 mod node_util;
 mod options;
+mod terser;
 mod tri;
 mod ty;
 
@@ -15,11 +17,17 @@ mod tester;
 
 use oxc_allocator::Allocator;
 use oxc_ast::ast::Program;
+use oxc_diagnostics::OxcDiagnostic;
 use oxc_mangler::Mangler;
 
-pub use crate::{ast_passes::CompressorPass, compressor::Compressor, options::CompressOptions};
+pub use crate::{
+    ast_passes::CompressorPass,
+    compressor::{Compressor, CompressorReturn},
+    options::{CompressOptions, CompressTarget, EmbeddedTemplateOptions},
+    terser::{TerserCompress, TerserCompressOptions, TerserOptions},
+};
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct MinifierOptions {
     pub mangle: bool,
     pub compress: CompressOptions,
@@ -33,6 +41,7 @@ impl Default for MinifierOptions {
 
 pub struct MinifierReturn {
     pub mangler: Option<Mangler>,
+    pub diagnostics: Vec<OxcDiagnostic>,
 }
 
 pub struct Minifier {
@@ -45,8 +54,8 @@ impl Minifier {
     }
 
     pub fn build<'a>(self, allocator: &'a Allocator, program: &mut Program<'a>) -> MinifierReturn {
-        Compressor::new(allocator, self.options.compress).build(program);
+        let ret = Compressor::new(allocator, self.options.compress).build(program);
         let mangler = self.options.mangle.then(|| Mangler::default().build(program));
-        MinifierReturn { mangler }
+        MinifierReturn { mangler, diagnostics: ret.diagnostics }
     }
 }
@@ -1,4 +1,4 @@
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct CompressOptions {
     pub dead_code_elimination: bool,
 
@@ -36,6 +36,70 @@ pub struct CompressOptions {
     ///
     /// Default `true`
     pub typeofs: bool,
+
+    /// Modern syntax which is allowed in the output, see [CompressTarget].
+    ///
+    /// Default: no modern syntax is introduced.
+    pub target: CompressTarget,
+
+    /// Remove calls to these functions when their return value is unused, e.g. `["console.info", "debug"]`.
+    /// Arguments with side effects are kept.
+    ///
+    /// Default `[]`
+    pub pure_funcs: Vec<String>,
+
+    /// Remove labeled statements with these labels, e.g. `["DEV"]` removes `DEV: check()`.
+    ///
+    /// Default `[]`
+    pub drop_labels: Vec<String>,
+
+    /// Replace global identifiers and member expressions with the given source text before
+    /// compressing, e.g. `[("DEBUG", "false")]` removes `if (DEBUG) {}`.
+    /// See `oxc_transformer::ReplaceGlobalDefinesConfig::new` for the accepted keys and values.
+    ///
+    /// Default `[]`
+    pub global_defs: Vec<(String, String)>,
+
+    /// The maximum number of times the peephole passes are run.
+    /// `0` runs them until no more changes are made.
+    ///
+    /// Default `0`
+    pub passes: usize,
+
+    /// Keep `Infinity` instead of compressing it into `1/0`.
+    ///
+    /// Default `true`
+    pub keep_infinity: bool,
+
+    /// Transforms function expressions into arrow functions in any position when
+    /// [CompressTarget::arrow_functions] is enabled, not only when passed as call arguments.
+    /// This breaks code which uses such a function as a constructor or reads its `prototype`.
+    ///
+    /// Default `false`
+    pub unsafe_arrows: bool,
+
+    /// Transforms `!(a < b)` into `a >= b`, which is incorrect when an operand is `NaN`.
+    ///
+    /// Default `false`
+    pub unsafe_comps: bool,
+
+    /// Transforms `2 * x * 3` into `6 * x`, which may give imprecise floating point results.
+    ///
+    /// Default `false`
+    pub unsafe_math: bool,
+
+    /// Transforms `Array.prototype.slice.call(a)` into `[].slice.call(a)`, and likewise for
+    /// `Object.prototype` and `String.prototype`, which is incorrect if the prototype methods
+    /// are shadowed by the literal.
+    ///
+    /// Default `false`
+    pub unsafe_proto: bool,
+
+    /// Minify CSS, HTML and GraphQL embedded in tagged templates and in template literals
+    /// preceded by a `/* css */`, `/* html */` or `/* graphql */` comment.
+    ///
+    /// Default `None`
+    pub embedded_templates: Option<EmbeddedTemplateOptions>,
 }
 
 #[allow(clippy::derivable_impls)]
@@ -56,6 +120,17 @@ impl CompressOptions {
             join_vars: true,
             loops: true,
             typeofs: true,
+            target: CompressTarget::default(),
+            pure_funcs: vec![],
+            drop_labels: vec![],
+            global_defs: vec![],
+            passes: 0,
+            keep_infinity: true,
+            unsafe_arrows: false,
+            unsafe_comps: false,
+            unsafe_math: false,
+            unsafe_proto: false,
+            embedded_templates: None,
         }
     }
 
@@ -69,6 +144,17 @@ impl CompressOptions {
             join_vars: false,
             loops: false,
             typeofs: false,
+            target: CompressTarget::default(),
+            pure_funcs: vec![],
+            drop_labels: vec![],
+            global_defs: vec![],
+            passes: 0,
+            keep_infinity: true,
+            unsafe_arrows: false,
+            unsafe_comps: false,
+            unsafe_math: false,
+            unsafe_proto: false,
+            embedded_templates: None,
         }
     }
 
@@ -76,3 +162,99 @@ impl CompressOptions {
         Self { dead_code_elimination: true, ..Self::all_false() }
     }
 }
+
+/// Syntax features which the compressor may use to shorten the output,
+/// resolved from the engines being targeted.
+///
+/// All features are disabled by default so that the output runs wherever the input does.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct CompressTarget {
+    /// Transforms `function() {}` into `() => {}` when `this` and `arguments` are not used.
+    pub arrow_functions: bool,
+
+    /// Transforms `a = a || b` into `a ||= b`.
+    pub logical_assignment_operators: bool,
+
+    /// Transforms `a !== null && a !== void 0 ? a : b` into `a ?? b`.
+    pub nullish_coalescing_operator: bool,
+
+    /// Transforms `Object.assign({}, a)` into `{ ...a }`.
+    pub object_spread: bool,
+
+    /// Transforms `a == null ? void 0 : a.b` into `a?.b`.
+    pub optional_chaining: bool,
+
+    /// Transforms `"a" + b + "c"` into `` `a${b}c` `` when it is shorter.
+    pub template_literals: bool,
+}
+
+impl CompressTarget {
+    /// Allow all syntax features.
+    pub fn esnext() -> Self {
+        Self {
+            arrow_functions: true,
+            logical_assignment_operators: true,
+            nullish_coalescing_operator: true,
+            object_spread: true,
+            optional_chaining: true,
+            template_literals: true,
+        }
+    }
+
+    /// Allow the syntax features supported by all engines of `targets`,
+    /// using the same compatibility data as `oxc_transformer`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Err` if the browserslist query of `targets` is invalid or not supported.
+    pub fn from_targets(
+        targets: &oxc_transformer::Targets,
+    ) -> Result<Self, oxc_diagnostics::Error> {
+        use oxc_transformer::can_enable_plugin;
+
+        let versions = targets.clone().get_targets()?;
+        let supports = |plugin: &str| !can_enable_plugin(plugin, Some(&versions), false);
+        Ok(Self {
+            arrow_functions: supports("transform-arrow-functions"),
+            logical_assignment_operators: supports("transform-logical-assignment-operators"),
+            nullish_coalescing_operator: supports("transform-nullish-coalescing-operator"),
+            object_spread: supports("transform-object-rest-spread"),
+            optional_chaining: supports("transform-optional-chaining"),
+            template_literals: supports("transform-template-literals"),
+        })
+    }
+}
+
+/// Tag names of templates which are minified by [CompressOptions::embedded_templates].
+///
+/// A tag matches by its dotted name, e.g. `styled.div`, or by the callee of a call such as
+/// `styled(Button)`. A name ending with `.*` matches any property, e.g. `styled.*`.
+#[derive(Debug, Clone)]
+pub struct EmbeddedTemplateOptions {
+    /// Default `["css", "keyframes", "createGlobalStyle", "injectGlobal", "styled", "styled.*"]`
+    pub css_tags: Vec<String>,
+
+    /// Default `["html", "svg"]`
+    pub html_tags: Vec<String>,
+
+    /// Default `["gql", "graphql"]`
+    pub graphql_tags: Vec<String>,
+}
+
+impl Default for EmbeddedTemplateOptions {
+    fn default() -> Self {
+        let tags = |tags: &[&str]| tags.iter().map(ToString::to_string).collect();
+        Self {
+            css_tags: tags(&[
+                "css",
+                "keyframes",
+                "createGlobalStyle",
+                "injectGlobal",
+                "styled",
+                "styled.*",
+            ]),
+            html_tags: tags(&["html", "svg"]),
+            graphql_tags: tags(&["gql", "graphql"]),
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Translation from [terser](https://github.com/terser/terser) options.
+//!
+//! Only the options which have an equivalent in [CompressOptions] are read,
+//! unknown options are ignored so that existing terser configs can be used as is.
+//! Of the `unsafe_*` options, only `unsafe_arrows`, `unsafe_comps`, `unsafe_math` and
+//! `unsafe_proto` are supported.
+
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+use crate::{CompressOptions, CompressTarget, MinifierOptions};
+
+/// Terser `minify` options.
+///
+/// <https://github.com/terser/terser#minify-options>
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct TerserOptions {
+    /// `false` to skip compressing, or the compress options.
+    pub compress: Option<TerserCompress>,
+
+    /// `false` to skip mangling. Mangle options are not supported.
+    pub mangle: Option<Value>,
+
+    /// Used for `compress.ecma` when it is not set.
+    pub ecma: Option<u32>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+pub enum TerserCompress {
+    Bool(bool),
+    Options(TerserCompressOptions),
+}
+
+/// Terser `compress` options.
+///
+/// <https://github.com/terser/terser#compress-options>
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(default)]
+pub struct TerserCompressOptions {
+    pub booleans: Option<bool>,
+    pub drop_console: Option<bool>,
+    pub drop_debugger: Option<bool>,
+    pub drop_labels: Option<Vec<String>>,
+    pub ecma: Option<u32>,
+    pub evaluate: Option<bool>,
+    pub global_defs: Option<Map<String, Value>>,
+    pub join_vars: Option<bool>,
+    pub keep_infinity: Option<bool>,
+    pub loops: Option<bool>,
+    pub passes: Option<usize>,
+    pub pure_funcs: Option<Vec<String>>,
+    pub typeofs: Option<bool>,
+    pub unsafe_arrows: Option<bool>,
+    pub unsafe_comps: Option<bool>,
+    pub unsafe_math: Option<bool>,
+    pub unsafe_proto: Option<bool>,
+}
+
+impl MinifierOptions {
+    /// Create [MinifierOptions] from terser's `minify` options.
+    pub fn from_terser_options(options: &TerserOptions) -> Self {
+        let mangle = !matches!(options.mangle, Some(Value::Bool(false)));
+        let compress = match &options.compress {
+            None | Some(TerserCompress::Bool(true)) => {
+                let compress_options = TerserCompressOptions::default();
+                CompressOptions::from_terser_compress_options(&compress_options, options.ecma)
+            }
+            Some(TerserCompress::Bool(false)) => CompressOptions::all_false(),
+            Some(TerserCompress::Options(compress_options)) => {
+                CompressOptions::from_terser_compress_options(compress_options, options.ecma)
+            }
+        };
+        Self { mangle, compress }
+    }
+}
+
+impl CompressOptions {
+    /// Create [CompressOptions] from terser's `compress` options.
+    ///
+    /// Options which are not set keep the values from [CompressOptions::default].
+    /// `ecma` is used when `options.ecma` is not set.
+    pub fn from_terser_compress_options(
+        options: &TerserCompressOptions,
+        ecma: Option<u32>,
+    ) -> Self {
+        let default = Self::default();
+        Self {
+            booleans: options.booleans.unwrap_or(default.booleans),
+            drop_console: options.drop_console.unwrap_or(default.drop_console),
+            drop_debugger: options.drop_debugger.unwrap_or(default.drop_debugger),
+            evaluate: options.evaluate.unwrap_or(default.evaluate),
+            join_vars: options.join_vars.unwrap_or(default.join_vars),
+            loops: options.loops.unwrap_or(default.loops),
+            typeofs: options.typeofs.unwrap_or(default.typeofs),
+            target: options.ecma.or(ecma).map_or(default.target, CompressTarget::from_ecma),
+            pure_funcs: options.pure_funcs.clone().unwrap_or(default.pure_funcs),
+            drop_labels: options.drop_labels.clone().unwrap_or(default.drop_labels),
+            global_defs: options
+                .global_defs
+                .as_ref()
+                .map_or(default.global_defs, translate_global_defs),
+            passes: options.passes.unwrap_or(default.passes),
+            keep_infinity: options.keep_infinity.unwrap_or(default.keep_infinity),
+            unsafe_arrows: options.unsafe_arrows.unwrap_or(default.unsafe_arrows),
+            unsafe_comps: options.unsafe_comps.unwrap_or(default.unsafe_comps),
+            unsafe_math: options.unsafe_math.unwrap_or(default.unsafe_math),
+            unsafe_proto: options.unsafe_proto.unwrap_or(default.unsafe_proto),
+            ..default
+        }
+    }
+}
+
+impl CompressTarget {
+    /// Allow the syntax features of an ECMAScript version,
+    /// e.g. `2020` or its edition number `11`.
+    pub fn from_ecma(ecma: u32) -> Self {
+        let year = if (6..2015).contains(&ecma) { ecma + 2009 } else { ecma };
+        Self {
+            arrow_functions: year >= 2015,
+            template_literals: year >= 2015,
+            object_spread: year >= 2018,
+            nullish_coalescing_operator: year >= 2020,
+            optional_chaining: year >= 2020,
+            logical_assignment_operators: year >= 2021,
+        }
+    }
+}
+
+/// Terser `global_defs` values are JSON values, or source text when the key is prefixed with `@`.
+fn translate_global_defs(global_defs: &Map<String, Value>) -> Vec<(String, String)> {
+    global_defs
+        .iter()
+        .map(|(key, value)| match (key.strip_prefix('@'), value) {
+            (Some(name), Value::String(source_text)) => (name.to_string(), source_text.clone()),
+            _ => (key.clone(), value.to_string()),
+        })
+        .collect()
+}
@@ -219,3 +219,20 @@ fn dce_from_terser() {
         ",
     );
 }
+
+#[test]
+fn dce_global_defs() {
+    let test = |source_text: &str, expected: &str| {
+        let options = CompressOptions {
+            global_defs: vec![
+                ("DEBUG".to_string(), "false".to_string()),
+                ("process.env.NODE_ENV".to_string(), "'production'".to_string()),
+            ],
+            ..CompressOptions::dead_code_elimination()
+        };
+        crate::test(source_text, expected, options);
+    };
+    test("if (DEBUG) { foo } else { bar }", "{ bar }");
+    test("if (process.env.NODE_ENV === 'development') { foo } else { bar }", "{ bar }");
+    test("var DEBUG = true; if (DEBUG) { foo }", "var DEBUG = true; if (DEBUG) { foo }");
+}
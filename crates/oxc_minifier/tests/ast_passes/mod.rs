@@ -57,3 +57,69 @@ fn tagged_template() {
     test("foo(true && o.f)", "foo(o.f)");
     test("foo(true ? o.f : false)", "foo(o.f)");
 }
+
+#[test]
+fn target() {
+    use oxc_minifier::CompressTarget;
+
+    let source_text = "foo(function() { return a = a || b })";
+    let options =
+        CompressOptions { target: CompressTarget::from_ecma(2015), ..CompressOptions::default() };
+    crate::test(source_text, "foo(() => a = a || b)", options);
+    let options =
+        CompressOptions { target: CompressTarget::from_ecma(2021), ..CompressOptions::default() };
+    crate::test(source_text, "foo(() => a ||= b)", options);
+    let options = CompressOptions::default();
+    crate::test(source_text, source_text, options);
+}
+
+#[test]
+fn target_from_browserslist() {
+    use oxc_minifier::CompressTarget;
+    use oxc_transformer::Targets;
+
+    let source_text = "foo(function() { return a = a || b })";
+    let target = CompressTarget::from_targets(&Targets::from_query("chrome 51")).unwrap();
+    let options = CompressOptions { target, ..CompressOptions::default() };
+    crate::test(source_text, "foo(() => a = a || b)", options);
+    let target = CompressTarget::from_targets(&Targets::from_query("chrome 85")).unwrap();
+    let options = CompressOptions { target, ..CompressOptions::default() };
+    crate::test(source_text, "foo(() => a ||= b)", options);
+    assert!(CompressTarget::from_targets(&Targets::from_query("not a browser")).is_err());
+}
+
+#[test]
+fn embedded_templates() {
+    use oxc_allocator::Allocator;
+    use oxc_codegen::CodeGenerator;
+    use oxc_minifier::{Compressor, EmbeddedTemplateOptions};
+    use oxc_parser::Parser;
+    use oxc_span::SourceType;
+
+    let source_text = "
+        x(/* css */ `\n  a { b: c; }\n`);
+        x(/* html */ `\n  <p>  a  </p>\n`);
+        x(/* gql */ `\n  { a   b }\n`);
+        x(/* other */ `\n  a { b: c; }\n`);
+        x(css`a { /* ${b} */ }`);
+    ";
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source_text, SourceType::mjs()).parse();
+    let program = allocator.alloc(ret.program);
+    let options = CompressOptions {
+        embedded_templates: Some(EmbeddedTemplateOptions::default()),
+        ..CompressOptions::all_false()
+    };
+    let ret =
+        Compressor::new(&allocator, options).with_comments(source_text, ret.trivias).build(program);
+    let code = CodeGenerator::new().build(program).source_text;
+    assert_eq!(
+        code,
+        "x(`a{b:c}`), x(`<p> a </p>`), x(`{a b}`), x(`\n  a { b: c; }\n`), x(css`a { /* ${b} */ }`);\n"
+    );
+    assert_eq!(ret.diagnostics.len(), 1);
+    assert_eq!(
+        ret.diagnostics[0].to_string(),
+        "Cannot minify embedded CSS: a substitution is inside a comment"
+    );
+}
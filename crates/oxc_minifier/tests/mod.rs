@@ -1,5 +1,6 @@
 mod ast_passes;
 mod mangler;
+mod terser;
 
 use oxc_allocator::Allocator;
 use oxc_codegen::{CodeGenerator, CodegenOptions};
@@ -0,0 +1,97 @@
+use oxc_allocator::Allocator;
+use oxc_codegen::CodeGenerator;
+use oxc_minifier::{Minifier, MinifierOptions, TerserOptions};
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+fn test(config: &str, source_text: &str, expected: &str) {
+    let terser_options: TerserOptions = serde_json::from_str(config).unwrap();
+    let options = MinifierOptions::from_terser_options(&terser_options);
+    crate::test(source_text, expected, options.compress);
+}
+
+#[test]
+fn compress_options() {
+    let config = r#"{
+        "compress": {
+            "global_defs": { "DEBUG": false, "@log": "console.log" },
+            "drop_labels": ["DEV"],
+            "pure_funcs": ["assert"],
+            "passes": 2,
+            "keep_infinity": true,
+            "some_unsupported_option": true
+        }
+    }"#;
+    test(
+        config,
+        "if (DEBUG) { foo() } DEV: bar(); assert(x); log(Infinity)",
+        "console.log(Infinity)",
+    );
+}
+
+#[test]
+fn ecma() {
+    test(r#"{ "ecma": 2015 }"#, "a = a || b", "a = a || b");
+    test(r#"{ "ecma": 2021 }"#, "a = a || b", "a ||= b");
+    test(r#"{ "ecma": 2015, "compress": { "ecma": 12 } }"#, "a = a || b", "a ||= b");
+}
+
+#[test]
+fn compress_and_mangle() {
+    let options: TerserOptions =
+        serde_json::from_str(r#"{ "compress": false, "mangle": false }"#).unwrap();
+    let options = MinifierOptions::from_terser_options(&options);
+    assert!(!options.mangle);
+    assert!(!options.compress.booleans);
+
+    let options: TerserOptions =
+        serde_json::from_str(r#"{ "mangle": { "toplevel": true } }"#).unwrap();
+    let options = MinifierOptions::from_terser_options(&options);
+    assert!(options.mangle);
+    assert!(options.compress.booleans);
+}
+
+#[test]
+fn global_defs() {
+    let options: TerserOptions = serde_json::from_str(
+        r#"{ "compress": { "global_defs": { "DEBUG": false, "@log": "console.log", "NAME": "a" } } }"#,
+    )
+    .unwrap();
+    let mut global_defs = MinifierOptions::from_terser_options(&options).compress.global_defs;
+    global_defs.sort();
+    let expected = [("DEBUG", "false"), ("NAME", "\"a\""), ("log", "console.log")]
+        .map(|(key, value)| (key.to_string(), value.to_string()));
+    assert_eq!(global_defs, expected);
+
+    let options: TerserOptions = serde_json::from_str(r#"{ "compress": true }"#).unwrap();
+    assert!(MinifierOptions::from_terser_options(&options).compress.global_defs.is_empty());
+}
+
+#[test]
+fn unsafe_options() {
+    test(
+        r#"{ "compress": { "unsafe_proto": true } }"#,
+        "Array.prototype.slice.call(a)",
+        "[].slice.call(a)",
+    );
+    test(
+        r#"{ "ecma": 2015, "compress": { "unsafe_arrows": true } }"#,
+        "var f = function() {}",
+        "var f = () => {}",
+    );
+    test(r#"{ "compress": { "keep_infinity": false } }"#, "x = Infinity", "x = 1 / 0");
+}
+
+/// `global_defs` are applied by the minifier itself, without replacing them beforehand.
+#[test]
+fn minify_global_defs() {
+    let config = r#"{ "compress": { "global_defs": { "DEBUG": false } }, "mangle": false }"#;
+    let terser_options: TerserOptions = serde_json::from_str(config).unwrap();
+    let allocator = Allocator::default();
+    let source_text = "if (DEBUG) { foo() } bar()";
+    let ret = Parser::new(&allocator, source_text, SourceType::mjs()).parse();
+    let program = allocator.alloc(ret.program);
+    Minifier::new(MinifierOptions::from_terser_options(&terser_options))
+        .build(&allocator, program);
+    assert_eq!(CodeGenerator::new().build(program).source_text, "bar();\n");
+}
@@ -1,17 +1,41 @@
 use std::sync::Arc;
 
+use rustc_hash::FxHashMap;
+
 use crate::{token::TokenChunk, SourceMap, Token};
 
 /// The `ConcatSourceMapBuilder` is a helper to concat sourcemaps.
+///
+/// Identical `sources` with identical `sourcesContent` are only added once.
 #[derive(Debug, Default)]
 pub struct ConcatSourceMapBuilder {
     pub(crate) names: Vec<Arc<str>>,
     pub(crate) sources: Vec<Arc<str>>,
     pub(crate) source_contents: Vec<Arc<str>>,
+    /// Map from source and source content to index in `sources`, for deduplication.
+    pub(crate) sources_map: FxHashMap<(Arc<str>, Arc<str>), u32>,
     pub(crate) tokens: Vec<Token>,
     /// The `token_chunks` is used for encode tokens to vlq mappings at parallel.
     pub(crate) token_chunks: Vec<TokenChunk>,
     pub(crate) token_chunk_prev_name_id: u32,
+    pub(crate) token_chunk_prev_source_id: u32,
+    pub(crate) token_chunk_prev_src_line: u32,
+    pub(crate) token_chunk_prev_src_col: u32,
+    /// Each added sourcemap, used for encoding to an index map.
+    pub(crate) sections: Vec<ConcatSection>,
+    /// The end of the generated code appended with `append_chunk`.
+    pub(crate) generated_line: u32,
+    pub(crate) generated_column: u32,
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct ConcatSection {
+    line_offset: u32,
+    column_offset: u32,
+    token_start: usize,
+    name_start: usize,
+    /// The index in `sources` of each source of the sourcemap.
+    source_ids: Vec<u32>,
 }
 
 #[allow(clippy::cast_possible_truncation)]
@@ -34,7 +58,8 @@ impl ConcatSourceMapBuilder {
             source_contents: Vec::with_capacity(sources_len),
             tokens: Vec::with_capacity(tokens_len),
             token_chunks: Vec::with_capacity(token_chunks_len),
-            token_chunk_prev_name_id: 0,
+            sections: Vec::with_capacity(token_chunks_len),
+            ..Self::default()
         }
     }
 
@@ -77,7 +102,19 @@ impl ConcatSourceMapBuilder {
     }
 
     pub fn add_sourcemap(&mut self, sourcemap: &SourceMap, line_offset: u32) {
-        let source_offset = self.sources.len() as u32;
+        self.add_sourcemap_with_offset(sourcemap, line_offset, 0);
+    }
+
+    /// Add a sourcemap whose generated code starts at `line_offset` and `column_offset`.
+    ///
+    /// `column_offset` only applies to the first line of the generated code, e.g. for a chunk
+    /// joined after a wrapper such as `(function(){` on the same line.
+    pub fn add_sourcemap_with_offset(
+        &mut self,
+        sourcemap: &SourceMap,
+        line_offset: u32,
+        column_offset: u32,
+    ) {
         let name_offset = self.names.len() as u32;
 
         // Add `token_chunks`, See `TokenChunk`.
@@ -87,10 +124,10 @@ impl ConcatSourceMapBuilder {
                 self.tokens.len() as u32 + sourcemap.tokens.len() as u32,
                 last_token.get_dst_line(),
                 last_token.get_dst_col(),
-                last_token.get_src_line(),
-                last_token.get_src_col(),
+                self.token_chunk_prev_src_line,
+                self.token_chunk_prev_src_col,
                 self.token_chunk_prev_name_id,
-                source_offset - 1,
+                self.token_chunk_prev_source_id,
             ));
         } else {
             self.token_chunks.push(TokenChunk::new(
@@ -105,38 +142,99 @@ impl ConcatSourceMapBuilder {
             ));
         }
 
-        // Extend `sources` and `source_contents`.
-        self.sources.extend(sourcemap.get_sources().map(Into::into));
-
-        if let Some(source_contents) = &sourcemap.source_contents {
-            // Clone `Arc` instead of generating a new `Arc` and copying string data because
-            // source texts are generally long strings. Cost of copying a large string is higher
-            // than cloning an `Arc`.
-            self.source_contents.extend(source_contents.iter().map(Arc::clone));
-        } else {
-            self.source_contents.extend((0..sourcemap.sources.len()).map(|_| "".into()));
-        }
+        // Add `sources` and `source_contents`, reusing identical sources of previous sourcemaps.
+        let source_ids = sourcemap
+            .sources
+            .iter()
+            .enumerate()
+            .map(|(index, source)| {
+                // Clone `Arc` instead of generating a new `Arc` and copying string data because
+                // source texts are generally long strings. Cost of copying a large string is higher
+                // than cloning an `Arc`.
+                let source_content = sourcemap
+                    .source_contents
+                    .as_ref()
+                    .and_then(|source_contents| source_contents.get(index))
+                    .map_or_else(|| "".into(), Arc::clone);
+                self.add_source_and_content(Arc::clone(source), source_content)
+            })
+            .collect::<Vec<_>>();
 
         // Extend `names`.
         self.names.reserve(sourcemap.names.len());
-        self.names.extend(sourcemap.get_names().map(Into::into));
+        self.names.extend(sourcemap.names.iter().map(Arc::clone));
 
         // Extend `tokens`.
         self.tokens.reserve(sourcemap.tokens.len());
-        let tokens = sourcemap.get_tokens().map(|token| {
-            Token::new(
+        for token in sourcemap.get_tokens() {
+            let source_id = token.get_source_id().map(|id| source_ids[id as usize]);
+            if let Some(source_id) = source_id {
+                self.token_chunk_prev_source_id = source_id;
+                self.token_chunk_prev_src_line = token.get_src_line();
+                self.token_chunk_prev_src_col = token.get_src_col();
+            }
+            let name_id = token.get_name_id().map(|id| {
+                self.token_chunk_prev_name_id = id + name_offset;
+                self.token_chunk_prev_name_id
+            });
+            self.tokens.push(Token::new(
                 token.get_dst_line() + line_offset,
-                token.get_dst_col(),
+                if token.get_dst_line() == 0 {
+                    token.get_dst_col() + column_offset
+                } else {
+                    token.get_dst_col()
+                },
                 token.get_src_line(),
                 token.get_src_col(),
-                token.get_source_id().map(|x| x + source_offset),
-                token.get_name_id().map(|x| {
-                    self.token_chunk_prev_name_id = x + name_offset;
-                    self.token_chunk_prev_name_id
-                }),
-            )
+                source_id,
+                name_id,
+            ));
+        }
+
+        self.sections.push(ConcatSection {
+            line_offset,
+            column_offset,
+            token_start: self.tokens.len() - sourcemap.tokens.len(),
+            name_start: name_offset as usize,
+            source_ids,
         });
-        self.tokens.extend(tokens);
+    }
+
+    /// Append a chunk of generated code and its sourcemap after the previously appended chunks.
+    ///
+    /// The builder tracks the end of the appended code, so chunks can be streamed one at a time,
+    /// including chunks without a sourcemap, e.g. a wrapper such as `(function(){`.
+    /// Don't mix with `add_sourcemap`, which doesn't update the end of the appended code.
+    pub fn append_chunk(&mut self, code: &str, sourcemap: Option<&SourceMap>) {
+        if let Some(sourcemap) = sourcemap {
+            self.add_sourcemap_with_offset(sourcemap, self.generated_line, self.generated_column);
+        }
+
+        let mut chars = code.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' | '\r' | '\u{2028}' | '\u{2029}' => {
+                    self.generated_line += 1;
+                    self.generated_column = 0;
+                }
+                // Columns are counted in UTF-16 code units.
+                _ => self.generated_column += c.len_utf16() as u32,
+            }
+        }
+    }
+
+    fn add_source_and_content(&mut self, source: Arc<str>, source_content: Arc<str>) -> u32 {
+        let count = self.sources.len() as u32;
+        let id = *self
+            .sources_map
+            .entry((Arc::clone(&source), Arc::clone(&source_content)))
+            .or_insert(count);
+        if id == count {
+            self.sources.push(source);
+            self.source_contents.push(source_content);
+        }
+        id
     }
 
     pub fn into_sourcemap(self) -> SourceMap {
@@ -150,6 +248,133 @@ impl ConcatSourceMapBuilder {
             Some(self.token_chunks),
         )
     }
+
+    /// Encode to an index map string, with a section for each added sourcemap at its offset,
+    /// instead of merging the mappings of all sourcemaps.
+    ///
+    /// See <https://tc39.es/ecma426/#sec-index-source-map>.
+    pub fn into_index_map_json_string(self) -> String {
+        let mut json = String::from("{\"version\":3,\"sections\":[");
+        for (i, section) in self.sections.iter().enumerate() {
+            let next = self.sections.get(i + 1);
+            let token_end = next.map_or(self.tokens.len(), |next| next.token_start);
+            let name_end = next.map_or(self.names.len(), |next| next.name_start);
+
+            // The sources of the section, without the sources deduplicated within the section.
+            let mut sources = vec![];
+            let mut local_source_ids = FxHashMap::default();
+            for &source_id in &section.source_ids {
+                local_source_ids.entry(source_id).or_insert_with(|| {
+                    sources.push(source_id as usize);
+                    sources.len() as u32 - 1
+                });
+            }
+
+            let name_start = section.name_start as u32;
+            let tokens = self.tokens[section.token_start..token_end]
+                .iter()
+                .map(|token| {
+                    let dst_line = token.get_dst_line() - section.line_offset;
+                    Token::new(
+                        dst_line,
+                        if dst_line == 0 {
+                            token.get_dst_col() - section.column_offset
+                        } else {
+                            token.get_dst_col()
+                        },
+                        token.get_src_line(),
+                        token.get_src_col(),
+                        token.get_source_id().map(|id| local_source_ids[&id]),
+                        token.get_name_id().map(|id| id - name_start),
+                    )
+                })
+                .collect();
+            let sourcemap = SourceMap::new(
+                None,
+                self.names[section.name_start..name_end].to_vec(),
+                None,
+                sources.iter().map(|&id| Arc::clone(&self.sources[id])).collect(),
+                Some(sources.iter().map(|&id| Arc::clone(&self.source_contents[id])).collect()),
+                tokens,
+                None,
+            );
+
+            if i > 0 {
+                json.push(',');
+            }
+            json.push_str(&format!(
+                "{{\"offset\":{{\"line\":{},\"column\":{}}},\"map\":{}}}",
+                section.line_offset,
+                section.column_offset,
+                sourcemap.to_json_string()
+            ));
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+#[test]
+fn test_concat_sourcemap_builder_index_map() {
+    let sm1 = SourceMap::new(
+        None,
+        vec!["foo".into()],
+        None,
+        vec!["foo.js".into()],
+        Some(vec!["foo();".into()]),
+        vec![Token::new(0, 0, 0, 0, Some(0), Some(0))],
+        None,
+    );
+    let sm2 = SourceMap::new(
+        None,
+        vec!["bar".into()],
+        None,
+        vec!["bar.js".into()],
+        None,
+        vec![Token::new(1, 2, 0, 0, Some(0), Some(0))],
+        None,
+    );
+    let create_builder = || {
+        let mut builder = ConcatSourceMapBuilder::from_sourcemaps(&[(&sm1, 0), (&sm2, 3)]);
+        // `foo.js` again, after a wrapper on the same line.
+        builder.add_sourcemap_with_offset(&sm1, 5, 7);
+        builder
+    };
+    let concat_sm = create_builder().into_sourcemap();
+    let json = create_builder().into_index_map_json_string();
+    assert_eq!(
+        json,
+        r#"{"version":3,"sections":[{"offset":{"line":0,"column":0},"map":{"version":3,"names":["foo"],"sources":["foo.js"],"sourcesContent":["foo();"],"mappings":"AAAAA"}},{"offset":{"line":3,"column":0},"map":{"version":3,"names":["bar"],"sources":["bar.js"],"sourcesContent":[""],"mappings":";EAAAA"}},{"offset":{"line":5,"column":7},"map":{"version":3,"names":["foo"],"sources":["foo.js"],"sourcesContent":["foo();"],"mappings":"AAAAA"}}]}"#
+    );
+
+    // The concatenated sourcemap only has `foo.js` once.
+    assert_eq!(concat_sm.get_sources().collect::<Vec<_>>(), ["foo.js", "bar.js"]);
+    assert_eq!(
+        concat_sm.tokens,
+        [
+            Token::new(0, 0, 0, 0, Some(0), Some(0)),
+            Token::new(4, 2, 0, 0, Some(1), Some(1)),
+            Token::new(5, 7, 0, 0, Some(0), Some(2)),
+        ]
+    );
+
+    // Decoding the index map gives the same mappings as concatenating.
+    let sm = SourceMap::from_json_string(&json).unwrap();
+    let mappings = |sm: &SourceMap| {
+        sm.get_tokens()
+            .map(|token| {
+                let source = token.get_source_id().and_then(|id| sm.get_source(id));
+                let name = token.get_name_id().and_then(|id| sm.get_name(id));
+                (
+                    token.get_dst_line(),
+                    token.get_dst_col(),
+                    source.map(String::from),
+                    name.map(String::from),
+                )
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(mappings(&sm), mappings(&concat_sm));
 }
 
 #[test]
@@ -232,3 +457,99 @@ where
 
     assert_eq!(sm.to_json().mappings, concat_sm.to_json().mappings);
 }
+
+#[test]
+fn test_concat_sourcemap_builder_column_offset() {
+    let sm = SourceMap::new(
+        None,
+        vec![],
+        None,
+        vec!["a.js".into()],
+        Some(vec!["a;\nb;".into()]),
+        vec![Token::new(0, 0, 0, 0, Some(0), None), Token::new(1, 0, 1, 0, Some(0), None)],
+        None,
+    );
+    let mut builder = ConcatSourceMapBuilder::default();
+    builder.add_sourcemap_with_offset(&sm, 2, 12);
+    let concat_sm = builder.into_sourcemap();
+    assert_eq!(
+        concat_sm.tokens,
+        [Token::new(2, 12, 0, 0, Some(0), None), Token::new(3, 0, 1, 0, Some(0), None)]
+    );
+}
+
+#[test]
+fn test_concat_sourcemap_builder_append_chunk() {
+    let sm1 = SourceMap::new(
+        None,
+        vec!["foo".into()],
+        None,
+        vec!["shared.js".into(), "a.js".into()],
+        Some(vec!["shared".into(), "a".into()]),
+        vec![Token::new(0, 0, 0, 0, Some(1), Some(0)), Token::new(1, 2, 0, 0, Some(0), None)],
+        None,
+    );
+    let sm2 = SourceMap::new(
+        None,
+        vec!["bar".into()],
+        None,
+        vec!["shared.js".into(), "b.js".into()],
+        Some(vec!["shared".into(), "b".into()]),
+        vec![Token::new(0, 0, 0, 0, Some(1), Some(0)), Token::new(0, 4, 1, 0, Some(0), None)],
+        None,
+    );
+
+    let append_chunks = || {
+        let mut builder = ConcatSourceMapBuilder::default();
+        builder.append_chunk("(function(){", None);
+        builder.append_chunk("foo();\n  x;", Some(&sm1));
+        builder.append_chunk("bar(", Some(&sm2));
+        builder.append_chunk("é", None);
+        builder
+    };
+    let builder = append_chunks();
+    assert_eq!((builder.generated_line, builder.generated_column), (1, 9));
+    let concat_sm = builder.into_sourcemap();
+
+    // "shared.js" is only added once.
+    assert_eq!(concat_sm.get_sources().collect::<Vec<_>>(), ["shared.js", "a.js", "b.js"]);
+    assert_eq!(concat_sm.get_source_contents().unwrap().collect::<Vec<_>>(), ["shared", "a", "b"]);
+    assert_eq!(
+        concat_sm.tokens,
+        [
+            Token::new(0, 12, 0, 0, Some(1), Some(0)),
+            Token::new(1, 2, 0, 0, Some(0), None),
+            Token::new(1, 4, 0, 0, Some(2), Some(1)),
+            Token::new(1, 8, 1, 0, Some(0), None),
+        ]
+    );
+    assert_eq!(
+        concat_sm.token_chunks.as_ref().unwrap()[1],
+        TokenChunk::new(2, 4, 1, 2, 0, 0, 0, 0)
+    );
+    let sm = SourceMap::new(
+        None,
+        concat_sm.names.clone(),
+        None,
+        concat_sm.sources.clone(),
+        None,
+        concat_sm.tokens.clone(),
+        None,
+    );
+    assert_eq!(sm.to_json().mappings, concat_sm.to_json().mappings);
+
+    // The index map of the chunks flattens to the same mappings.
+    let index_map =
+        SourceMap::from_json_string(&append_chunks().into_index_map_json_string()).unwrap();
+    let mappings = |sm: &SourceMap| {
+        sm.get_tokens()
+            .map(|token| {
+                let source = token.get_source_id().and_then(|id| sm.get_source(id));
+                let name = token.get_name_id().and_then(|id| sm.get_name(id));
+                let src = (source.map(String::from), token.get_src_line(), name.map(String::from));
+                (token.get_dst_line(), token.get_dst_col(), src)
+            })
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(mappings(&index_map), mappings(&concat_sm));
+}
@@ -3,6 +3,7 @@
 use std::sync::Arc;
 
 use crate::error::{Error, Result};
+use crate::scopes::{decode_generated_ranges, decode_original_scope};
 use crate::{SourceMap, Token};
 
 /// See <https://github.com/tc39/source-map/blob/main/source-map-rev3.md>.
@@ -12,21 +13,67 @@ pub struct JSONSourceMap {
     /// An optional name of the generated code that this source map is associated with.
     pub file: Option<String>,
     /// A string with the encoded mapping data.
+    #[serde(default)]
     pub mappings: String,
     /// An optional source root, useful for relocating source files on a server or removing repeated values in the “sources” entry.
     /// This value is prepended to the individual entries in the “source” field.
     pub source_root: Option<String>,
     /// A list of original sources used by the “mappings” entry.
+    #[serde(default)]
     pub sources: Vec<String>,
     /// An optional list of source content, useful when the “source” can’t be hosted.
     /// The contents are listed in the same order as the sources in line 5. “null” may be used if some original sources should be retrieved by name.
     pub sources_content: Option<Vec<Option<String>>>,
     /// A list of symbol names used by the “mappings” entry.
+    #[serde(default)]
     pub names: Vec<String>,
+    /// An optional globally unique identifier of the generated code, used to associate it with
+    /// the source map when symbolicating stack traces.
+    #[serde(alias = "debug_id")]
+    pub debug_id: Option<String>,
+    /// The scope tree of each source, see the source map scopes proposal.
+    pub original_scopes: Option<Vec<Option<String>>>,
+    /// The ranges of the generated code that correspond to original scopes.
+    pub generated_ranges: Option<String>,
+    /// The sections of an index map. If present, the other mapping fields are not used.
+    pub sections: Option<Vec<JSONSourceMapSection>>,
+}
+
+/// A section of an index map.
+#[derive(serde::Deserialize)]
+pub struct JSONSourceMapSection {
+    /// The position in the generated code where the section starts.
+    pub offset: JSONSourceMapOffset,
+    /// The source map of the section.
+    pub map: JSONSourceMap,
+}
+
+#[derive(serde::Deserialize, Clone, Copy)]
+pub struct JSONSourceMapOffset {
+    pub line: u32,
+    pub column: u32,
 }
 
 pub fn decode(json: JSONSourceMap) -> Result<SourceMap> {
+    if json.sections.is_some() {
+        return decode_index_map(json);
+    }
     let tokens = decode_mapping(&json.mappings, json.names.len(), json.sources.len())?;
+    let original_scopes = json
+        .original_scopes
+        .map(|scopes| {
+            scopes
+                .iter()
+                .map(|scope| {
+                    scope.as_deref().map(|s| decode_original_scope(s, json.names.len())).transpose()
+                })
+                .collect::<Result<Vec<_>>>()
+        })
+        .transpose()?;
+    let generated_ranges = json
+        .generated_ranges
+        .map(|ranges| decode_generated_ranges(&ranges, json.names.len(), json.sources.len()))
+        .transpose()?;
     Ok(SourceMap {
         file: json.file.map(Arc::from),
         names: json.names.into_iter().map(Arc::from).collect(),
@@ -38,9 +85,66 @@ pub fn decode(json: JSONSourceMap) -> Result<SourceMap> {
         tokens,
         token_chunks: None,
         x_google_ignore_list: None,
+        debug_id: json.debug_id,
+        original_scopes,
+        generated_ranges,
     })
 }
 
+/// Flatten an index map into a regular `SourceMap`, shifting the tokens of each section by its offset.
+#[allow(clippy::cast_possible_truncation)]
+fn decode_index_map(json: JSONSourceMap) -> Result<SourceMap> {
+    let mut sourcemap = SourceMap {
+        file: json.file.map(Arc::from),
+        debug_id: json.debug_id,
+        ..SourceMap::default()
+    };
+    let mut source_contents = vec![];
+    let mut has_source_contents = false;
+    let mut prev_offset = None;
+
+    for section in json.sections.unwrap_or_default() {
+        let JSONSourceMapOffset { line, column } = section.offset;
+        if prev_offset.is_some_and(|prev| prev > (line, column)) {
+            return Err(Error::BadSectionOffset(line, column));
+        }
+        prev_offset = Some((line, column));
+
+        let map = decode(section.map)?;
+        let source_offset = sourcemap.sources.len() as u32;
+        let name_offset = sourcemap.names.len() as u32;
+
+        let source_root = map.source_root.as_deref().filter(|root| !root.is_empty());
+        sourcemap.sources.extend(map.sources.iter().map(|source| match source_root {
+            Some(root) => format!("{}/{source}", root.trim_end_matches('/')).into(),
+            None => Arc::clone(source),
+        }));
+        if let Some(contents) = &map.source_contents {
+            has_source_contents = true;
+            source_contents.extend(contents.iter().map(Arc::clone));
+        }
+        // Keep `source_contents` aligned with `sources` when a section has no contents.
+        source_contents.resize(sourcemap.sources.len(), Arc::from(""));
+        sourcemap.names.extend(map.names.iter().map(Arc::clone));
+
+        sourcemap.tokens.extend(map.tokens.iter().map(|token| {
+            Token::new(
+                token.dst_line + line,
+                if token.dst_line == 0 { token.dst_col + column } else { token.dst_col },
+                token.src_line,
+                token.src_col,
+                token.source_id.map(|id| id + source_offset),
+                token.name_id.map(|id| id + name_offset),
+            )
+        }));
+    }
+
+    if has_source_contents {
+        sourcemap.source_contents = Some(source_contents);
+    }
+    Ok(sourcemap)
+}
+
 pub fn decode_from_string(value: &str) -> Result<SourceMap> {
     decode(serde_json::from_str(value)?)
 }
@@ -114,7 +218,7 @@ fn decode_mapping(mapping: &str, names_len: usize, sources_len: usize) -> Result
 #[rustfmt::skip]
 const B64: [i8; 256] = [ -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, 62, -1, -1, -1, 63, 52, 53, 54, 55, 56, 57, 58, 59, 60, 61, -1, -1, -1, -1, -1, -1, -1, 0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, -1, -1, -1, -1, -1, -1, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43, 44, 45, 46, 47, 48, 49, 50, 51, -1, -1, -1, -1, -1 - 1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, -1, ];
 
-fn parse_vlq_segment_into(segment: &str, rv: &mut Vec<i64>) -> Result<()> {
+pub(crate) fn parse_vlq_segment_into(segment: &str, rv: &mut Vec<i64>) -> Result<()> {
     let mut cur = 0;
     let mut shift = 0;
 
@@ -174,3 +278,61 @@ fn test_decode_sourcemap_optional_filed() {
     }"#;
     SourceMap::from_json_string(input).expect("should success");
 }
+
+#[test]
+fn test_decode_index_map() {
+    let input = r#"{
+        "version": 3,
+        "file": "bundle.js",
+        "debugId": "85314830-023f-4cf1-a267-535f4e37bb17",
+        "sections": [
+            {
+                "offset": { "line": 0, "column": 0 },
+                "map": {
+                    "version": 3,
+                    "sources": ["a.js"],
+                    "sourcesContent": ["a();"],
+                    "names": ["a"],
+                    "mappings": "AAAAA"
+                }
+            },
+            {
+                "offset": { "line": 1, "column": 4 },
+                "map": {
+                    "version": 3,
+                    "sourceRoot": "src/",
+                    "sources": ["b.js"],
+                    "names": ["b"],
+                    "mappings": "AAAAA;AACA"
+                }
+            }
+        ]
+    }"#;
+    let sm = SourceMap::from_json_string(input).unwrap();
+    assert_eq!(sm.get_file(), Some("bundle.js"));
+    assert_eq!(sm.get_debug_id(), Some("85314830-023f-4cf1-a267-535f4e37bb17"));
+    assert_eq!(sm.get_sources().collect::<Vec<_>>(), ["a.js", "src/b.js"]);
+    assert_eq!(sm.get_source_contents().unwrap().collect::<Vec<_>>(), ["a();", ""]);
+    assert_eq!(sm.get_names().collect::<Vec<_>>(), ["a", "b"]);
+    assert_eq!(
+        sm.get_tokens().collect::<Vec<_>>(),
+        [
+            &Token::new(0, 0, 0, 0, Some(0), Some(0)),
+            // The column offset only applies to the first line of the section.
+            &Token::new(1, 4, 0, 0, Some(1), Some(1)),
+            &Token::new(2, 0, 1, 0, Some(1), None),
+        ]
+    );
+}
+
+#[test]
+fn test_decode_index_map_unsorted_sections() {
+    let input = r#"{
+        "version": 3,
+        "sections": [
+            { "offset": { "line": 1, "column": 0 }, "map": { "version": 3, "sources": [], "names": [], "mappings": "" } },
+            { "offset": { "line": 0, "column": 0 }, "map": { "version": 3, "sources": [], "names": [], "mappings": "" } }
+        ]
+    }"#;
+    assert!(matches!(SourceMap::from_json_string(input), Err(Error::BadSectionOffset(0, 0))));
+}
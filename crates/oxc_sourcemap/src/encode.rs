@@ -8,7 +8,11 @@ use crate::JSONSourceMap;
 /// It is a helper for encode `SourceMap` to vlq sourcemap string, but here some different.
 /// - Quote `source_content` at parallel.
 /// - If you using `ConcatSourceMapBuilder`, serialize `tokens` to vlq `mappings` at parallel.
-use crate::{token::TokenChunk, SourceMap, Token};
+use crate::{
+    scopes::{encode_generated_ranges, encode_original_scope},
+    token::TokenChunk,
+    SourceMap, Token,
+};
 
 pub fn encode(sourcemap: &SourceMap) -> JSONSourceMap {
     JSONSourceMap {
@@ -21,17 +25,26 @@ pub fn encode(sourcemap: &SourceMap) -> JSONSourceMap {
             .as_ref()
             .map(|x| x.iter().map(ToString::to_string).map(Some).collect()),
         names: sourcemap.names.iter().map(ToString::to_string).collect(),
+        debug_id: sourcemap.get_debug_id().map(ToString::to_string),
+        original_scopes: sourcemap.original_scopes.as_ref().map(|scopes| {
+            scopes.iter().map(|scope| scope.as_ref().map(encode_original_scope)).collect()
+        }),
+        generated_ranges: sourcemap.generated_ranges.as_deref().map(encode_generated_ranges),
+        sections: None,
     }
 }
 
 // Here using `serde_json` to serialize `names` / `source_contents` / `sources`.
 // It will escape the string to avoid invalid JSON string.
 pub fn encode_to_string(sourcemap: &SourceMap) -> String {
-    let max_segments = 12
+    let max_segments = 13
         + sourcemap.names.len() * 2
         + sourcemap.sources.len() * 2
         + sourcemap.source_contents.as_ref().map_or(0, |sources| sources.len() * 2 + 1)
-        + sourcemap.x_google_ignore_list.as_ref().map_or(0, |x| x.len() * 2 + 1);
+        + sourcemap.x_google_ignore_list.as_ref().map_or(0, |x| x.len() * 2 + 1)
+        + sourcemap.debug_id.as_ref().map_or(0, |_| 3)
+        + sourcemap.original_scopes.as_ref().map_or(0, |x| x.len() * 2 + 1)
+        + sourcemap.generated_ranges.as_ref().map_or(0, |_| 3);
     let mut contents = PreAllocatedString::new(max_segments);
 
     contents.push("{\"version\":3,".into());
@@ -74,7 +87,31 @@ pub fn encode_to_string(sourcemap: &SourceMap) -> String {
         contents.push_list(x_google_ignore_list.iter().map(ToString::to_string));
     }
 
-    contents.push("],\"mappings\":\"".into());
+    contents.push("],".into());
+    if let Some(original_scopes) = &sourcemap.original_scopes {
+        contents.push("\"originalScopes\":[".into());
+        contents.push_list(original_scopes.iter().map(|scope| {
+            scope.as_ref().map_or_else(
+                || "null".to_string(),
+                |scope| escape_json_string(encode_original_scope(scope)),
+            )
+        }));
+        contents.push("],".into());
+    }
+
+    if let Some(generated_ranges) = &sourcemap.generated_ranges {
+        contents.push("\"generatedRanges\":".into());
+        contents.push(escape_json_string(encode_generated_ranges(generated_ranges)).into());
+        contents.push(",".into());
+    }
+
+    if let Some(debug_id) = sourcemap.get_debug_id() {
+        contents.push("\"debugId\":".into());
+        contents.push(escape_json_string(debug_id).into());
+        contents.push(",".into());
+    }
+
+    contents.push("\"mappings\":\"".into());
     contents.push(serialize_sourcemap_mappings(sourcemap).into());
     contents.push("\"}".into());
 
@@ -206,6 +243,13 @@ static B64_CHARS: Aligned64 = Aligned64([
     b'w', b'x', b'y', b'z', b'0', b'1', b'2', b'3', b'4', b'5', b'6', b'7', b'8', b'9', b'+', b'/',
 ]);
 
+/// Encode number as VLQ and push encoding into `out`, reserving the capacity it needs.
+pub(crate) fn push_vlq(out: &mut String, num: i64) {
+    out.reserve(MAX_VLQ_BYTES);
+    // SAFETY: We have reserved `MAX_VLQ_BYTES` bytes capacity
+    unsafe { encode_vlq(out, num) };
+}
+
 /// Encode number as VLQ and push encoding into `out`.
 /// Will push between 1 byte (num = 0) and 7 bytes (num = -u32::MAX).
 ///
@@ -407,6 +451,56 @@ fn test_encode_escape_string() {
     );
 }
 
+#[test]
+fn test_encode_debug_id() {
+    let mut sm = SourceMap::default();
+    sm.set_debug_id("85314830-023f-4cf1-a267-535f4e37bb17");
+    let json = sm.to_json_string();
+    assert_eq!(
+        json,
+        r#"{"version":3,"names":[],"sources":[],"debugId":"85314830-023f-4cf1-a267-535f4e37bb17","mappings":""}"#
+    );
+    let sm2 = SourceMap::from_json_string(&json).unwrap();
+    assert_eq!(sm2.get_debug_id(), sm.get_debug_id());
+}
+
+#[test]
+fn test_encode_scopes() {
+    let mut sm = SourceMap::new(
+        None,
+        vec!["global".into(), "a".into()],
+        None,
+        vec!["a.js".into(), "b.js".into()],
+        None,
+        vec![],
+        None,
+    );
+    let scope = crate::OriginalScope {
+        start: (0, 0),
+        end: (1, 0),
+        kind: Some(0),
+        variables: vec![1],
+        ..crate::OriginalScope::default()
+    };
+    let range = crate::GeneratedRange {
+        start: (0, 0),
+        end: (0, 10),
+        definition: Some((0, 0)),
+        bindings: vec![Some(1)],
+        ..crate::GeneratedRange::default()
+    };
+    sm.set_original_scopes(vec![Some(scope), None]);
+    sm.set_generated_ranges(vec![range]);
+    let json = sm.to_json_string();
+    assert_eq!(
+        json,
+        r#"{"version":3,"names":["global","a"],"sources":["a.js","b.js"],"originalScopes":["AAEAC,CA",null],"generatedRanges":"ACAAC,U","mappings":""}"#
+    );
+    let sm2 = SourceMap::from_json_string(&json).unwrap();
+    assert_eq!(sm2.get_original_scopes(), sm.get_original_scopes());
+    assert_eq!(sm2.get_generated_ranges(), sm.get_generated_ranges());
+}
+
 #[test]
 fn test_vlq_encode_diff() {
     // Most import tests here are that with maximum values, `encode_vlq_diff` pushes maximum of 7 bytes.
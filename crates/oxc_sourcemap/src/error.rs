@@ -16,6 +16,10 @@ pub enum Error {
     BadSourceReference(u32),
     /// a reference to a non existing name was encountered
     BadNameReference(u32),
+    /// an index map section started before the previous section
+    BadSectionOffset(u32, u32),
+    /// a scope or range was started without being ended, or ended without being started
+    UnbalancedScopes,
 }
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -33,6 +37,10 @@ impl fmt::Display for Error {
             Error::BadNameReference(idx) => {
                 write!(f, "Reference to non-existing name at position {idx}")
             }
+            Error::BadSectionOffset(line, column) => {
+                write!(f, "Index map section at line {line}, column {column} is out of order")
+            }
+            Error::UnbalancedScopes => write!(f, "Scopes have unbalanced start and end items"),
         }
     }
 }
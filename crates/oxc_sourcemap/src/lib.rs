@@ -3,15 +3,19 @@ mod concat_sourcemap_builder;
 mod decode;
 mod encode;
 mod error;
+mod remapping;
+mod scopes;
 mod sourcemap;
 mod sourcemap_builder;
 mod sourcemap_visualizer;
 mod token;
 
 pub use concat_sourcemap_builder::ConcatSourceMapBuilder;
-pub use decode::JSONSourceMap;
+pub use decode::{JSONSourceMap, JSONSourceMapOffset, JSONSourceMapSection};
 pub use error::Error;
-pub use sourcemap::SourceMap;
+pub use remapping::remapping;
+pub use scopes::{GeneratedRange, OriginalScope};
+pub use sourcemap::{Bias, SourceMap};
 pub use sourcemap_builder::SourceMapBuilder;
 pub use sourcemap_visualizer::SourcemapVisualizer;
 pub use token::{SourceViewToken, Token, TokenChunk};
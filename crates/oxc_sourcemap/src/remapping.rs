@@ -0,0 +1,267 @@
+use std::sync::Arc;
+
+use crate::{SourceMap, SourceMapBuilder, Token};
+
+/// Compose a sourcemap with the sourcemaps of its sources, producing a single sourcemap that maps
+/// the final output back to the original sources. E.g. for `a.ts` transformed to `a.js` and then
+/// minified to `a.min.js`, remapping the sourcemap of `a.min.js` with a loader returning the
+/// sourcemap of `a.js` gives a sourcemap from `a.min.js` to `a.ts`.
+///
+/// `loader` is called with each source of `sourcemap`, and returns the sourcemap of that source
+/// if it was generated from other files, or `None` if it is an original source. It is called
+/// recursively for the sources of the returned sourcemaps, whose relative paths are resolved
+/// against the path of the source they belong to.
+///
+/// `sourcesContent` and `names` are taken from the sourcemaps closest to the original sources.
+/// Mappings that point to a location without a mapping in an upstream sourcemap are dropped.
+///
+/// # Example
+/// ```
+/// let sourcemap = remapping(&minified_sourcemap, |source| {
+///     (source == "a.js").then(|| transformed_sourcemap.clone())
+/// });
+/// ```
+pub fn remapping<F>(sourcemap: &SourceMap, mut loader: F) -> SourceMap
+where
+    F: FnMut(&str) -> Option<SourceMap>,
+{
+    let sources = load_sources(sourcemap, None, &mut loader, &mut vec![]);
+
+    let mut builder = SourceMapBuilder::default();
+    if let Some(file) = sourcemap.get_file() {
+        builder.set_file(file);
+    }
+    for token in sourcemap.get_tokens() {
+        let Some(source_id) = token.get_source_id() else {
+            builder.add_token(token.get_dst_line(), token.get_dst_col(), 0, 0, None, None);
+            continue;
+        };
+        let name = token.get_name_id().and_then(|id| sourcemap.get_name(id));
+        let Some(traced) =
+            trace(&sources, source_id, token.get_src_line(), token.get_src_col(), name)
+        else {
+            continue;
+        };
+        let source_id = builder.add_source_and_content(
+            &traced.source.source,
+            traced.source.content.as_deref().unwrap_or(""),
+        );
+        let name_id = traced.name.map(|name| builder.add_name(name));
+        builder.add_token(
+            token.get_dst_line(),
+            token.get_dst_col(),
+            traced.line,
+            traced.col,
+            Some(source_id),
+            name_id,
+        );
+    }
+    builder.into_sourcemap()
+}
+
+/// An upstream sourcemap and the sourcemaps of its sources.
+struct MapNode {
+    sourcemap: SourceMap,
+    lookup_table: Vec<(u32, u32, u32)>,
+    sources: Vec<SourceNode>,
+}
+
+enum SourceNode {
+    Original(OriginalSource),
+    Map(Box<MapNode>),
+}
+
+struct OriginalSource {
+    source: Arc<str>,
+    content: Option<Arc<str>>,
+}
+
+struct Traced<'a> {
+    source: &'a OriginalSource,
+    line: u32,
+    col: u32,
+    name: Option<&'a str>,
+}
+
+/// Load the sourcemaps of the sources of `sourcemap`.
+///
+/// `path` is the resolved path of the source `sourcemap` belongs to, `None` for the root.
+/// `stack` holds the paths being loaded, to avoid loading a sourcemap into itself.
+fn load_sources<F>(
+    sourcemap: &SourceMap,
+    path: Option<&str>,
+    loader: &mut F,
+    stack: &mut Vec<String>,
+) -> Vec<SourceNode>
+where
+    F: FnMut(&str) -> Option<SourceMap>,
+{
+    sourcemap
+        .get_sources()
+        .enumerate()
+        .map(|(id, source)| {
+            let source = match sourcemap.get_source_root() {
+                Some(root) if !root.is_empty() => {
+                    format!("{}/{source}", root.trim_end_matches('/'))
+                }
+                _ => source.to_string(),
+            };
+            let source = match path {
+                Some(path) => resolve_source(path, &source),
+                None => source,
+            };
+            if !stack.contains(&source) {
+                if let Some(upstream) = loader(&source) {
+                    stack.push(source.clone());
+                    let sources = load_sources(&upstream, Some(&source), loader, stack);
+                    stack.pop();
+                    let lookup_table = upstream.generate_lookup_table();
+                    let node = MapNode { sourcemap: upstream, lookup_table, sources };
+                    return SourceNode::Map(Box::new(node));
+                }
+            }
+            #[allow(clippy::cast_possible_truncation)]
+            let content = sourcemap.get_source_content(id as u32).map(Into::into);
+            SourceNode::Original(OriginalSource { source: source.into(), content })
+        })
+        .collect()
+}
+
+/// Trace a location in source `source_id` back to an original source.
+fn trace<'a>(
+    sources: &'a [SourceNode],
+    source_id: u32,
+    line: u32,
+    col: u32,
+    name: Option<&'a str>,
+) -> Option<Traced<'a>> {
+    match sources.get(source_id as usize)? {
+        SourceNode::Original(source) => Some(Traced { source, line, col, name }),
+        SourceNode::Map(node) => {
+            let token = node.lookup_token_on_line(line, col)?;
+            let name = token.get_name_id().and_then(|id| node.sourcemap.get_name(id)).or(name);
+            let (line, col) = (token.get_src_line(), token.get_src_col());
+            trace(&node.sources, token.get_source_id()?, line, col, name)
+        }
+    }
+}
+
+impl MapNode {
+    /// The token with the greatest column less than or equal to `col` on `line`.
+    fn lookup_token_on_line(&self, line: u32, col: u32) -> Option<&Token> {
+        self.sourcemap
+            .lookup_token(&self.lookup_table, line, col)
+            .filter(|token| token.get_dst_line() == line)
+    }
+}
+
+/// Resolve a relative `source` of a sourcemap against the path of the file it belongs to.
+fn resolve_source(path: &str, source: &str) -> String {
+    if source.starts_with('/') || source.contains("://") {
+        return source.to_string();
+    }
+    let dir = path.rfind('/').map_or("", |i| &path[..=i]);
+    let joined = format!("{dir}{source}");
+
+    let mut segments: Vec<&str> = vec![];
+    for segment in joined.split('/') {
+        match segment {
+            "." => {}
+            ".." if segments.last().is_some_and(|last| !last.is_empty() && *last != "..") => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+#[test]
+fn test_remapping() {
+    // `a.ts` -> `a.js`
+    let transformed = SourceMap::new(
+        Some("a.js".into()),
+        vec!["foo".into()],
+        None,
+        vec!["../src/a.ts".into()],
+        Some(vec!["let foo: number = 1;".into()]),
+        vec![Token::new(0, 0, 0, 0, Some(0), None), Token::new(0, 4, 0, 4, Some(0), Some(0))],
+        None,
+    );
+    // `a.js` + `b.js` -> `bundle.js`
+    let bundled = SourceMap::new(
+        Some("bundle.js".into()),
+        vec!["bar".into()],
+        None,
+        vec!["dist/a.js".into(), "b.js".into()],
+        Some(vec!["let foo = 1;".into(), "bar();".into()]),
+        vec![
+            Token::new(0, 0, 0, 0, Some(0), None),
+            Token::new(0, 4, 0, 4, Some(0), None),
+            Token::new(0, 9, 0, 8, Some(0), None),
+            Token::new(1, 0, 0, 0, Some(1), Some(0)),
+            Token::new(2, 0, 5, 0, Some(0), None),
+        ],
+        None,
+    );
+
+    let mut loaded = vec![];
+    let sm = remapping(&bundled, |source| {
+        loaded.push(source.to_string());
+        (source == "dist/a.js").then(|| transformed.clone())
+    });
+    assert_eq!(loaded, ["dist/a.js", "src/a.ts", "b.js"]);
+
+    assert_eq!(sm.get_file(), Some("bundle.js"));
+    assert_eq!(sm.get_sources().collect::<Vec<_>>(), ["src/a.ts", "b.js"]);
+    assert_eq!(
+        sm.get_source_contents().unwrap().collect::<Vec<_>>(),
+        ["let foo: number = 1;", "bar();"]
+    );
+    let tokens = sm
+        .get_tokens()
+        .map(|token| {
+            let source = token.get_source_id().and_then(|id| sm.get_source(id));
+            let name = token.get_name_id().and_then(|id| sm.get_name(id));
+            let src = (source, token.get_src_line(), token.get_src_col(), name);
+            (token.get_dst_line(), token.get_dst_col(), src)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        tokens,
+        [
+            (0, 0, (Some("src/a.ts"), 0, 0, None)),
+            // The name is taken from the upstream sourcemap.
+            (0, 4, (Some("src/a.ts"), 0, 4, Some("foo"))),
+            // Traced to the closest mapping on the same line.
+            (0, 9, (Some("src/a.ts"), 0, 4, Some("foo"))),
+            (1, 0, (Some("b.js"), 0, 0, Some("bar"))),
+            // Line 5 of `a.js` has no mapping.
+        ]
+    );
+}
+
+#[test]
+fn test_remapping_cycle() {
+    let sm = SourceMap::new(
+        None,
+        vec![],
+        None,
+        vec!["a.js".into()],
+        None,
+        vec![Token::new(0, 0, 0, 0, Some(0), None)],
+        None,
+    );
+    let remapped = remapping(&sm, |_| Some(sm.clone()));
+    assert_eq!(remapped.get_sources().collect::<Vec<_>>(), ["a.js"]);
+    assert_eq!(remapped.get_tokens().collect::<Vec<_>>(), [&Token::new(0, 0, 0, 0, Some(0), None)]);
+}
+
+#[test]
+fn test_resolve_source() {
+    assert_eq!(resolve_source("dist/a.js", "../src/a.ts"), "src/a.ts");
+    assert_eq!(resolve_source("dist/a.js", "./a.ts"), "dist/a.ts");
+    assert_eq!(resolve_source("a.js", "../a.ts"), "../a.ts");
+    assert_eq!(resolve_source("dist/a.js", "/abs/a.ts"), "/abs/a.ts");
+    assert_eq!(resolve_source("dist/a.js", "webpack://app/a.ts"), "webpack://app/a.ts");
+}
@@ -0,0 +1,446 @@
+//! Encode and decode the scope information of the source map scopes proposal,
+//! see <https://github.com/tc39/source-map/blob/main/proposals/scopes.md>.
+//!
+//! `originalScopes` has a string for each source, with a single scope tree encoded as a list of
+//! comma separated items in the order of their positions:
+//! - start of a scope: `line, column, flags, [name], [kind], [variables...]`
+//! - end of a scope: `line, column`
+//!
+//! Lines are relative to the previous item, columns are absolute, and names, kinds and variables
+//! are indices into `names` relative to the previous occurrence of the same field.
+//!
+//! `generatedRanges` is a string of items in the order of their positions, where lines are
+//! separated by `;` like `mappings`, and items on the same line by `,`:
+//! - start of a range: `column, flags, [source, scope], [source, line, column], [bindings...]`
+//! - end of a range: `column`
+//!
+//! Columns are relative to the previous item on the same line. The definition is the index of
+//! the source, relative to the previous definition, and the index of its original scope in
+//! pre-order, relative to the previous definition if in the same source. The callsite is encoded
+//! the same way, with the column relative only if on the same line. Each binding is the absolute
+//! index of an expression in `names`, or `-1` if the variable is not available.
+use crate::{
+    decode::parse_vlq_segment_into,
+    encode::push_vlq,
+    error::{Error, Result},
+};
+
+const ORIGINAL_SCOPE_HAS_NAME: i64 = 0x1;
+const ORIGINAL_SCOPE_HAS_KIND: i64 = 0x2;
+const ORIGINAL_SCOPE_IS_STACK_FRAME: i64 = 0x4;
+
+const GENERATED_RANGE_HAS_DEFINITION: i64 = 0x1;
+const GENERATED_RANGE_HAS_CALLSITE: i64 = 0x2;
+const GENERATED_RANGE_IS_STACK_FRAME: i64 = 0x4;
+const GENERATED_RANGE_IS_HIDDEN: i64 = 0x8;
+
+/// A scope in an original source, e.g. a function or a block.
+///
+/// Positions are `(line, column)`, and names are indices into `SourceMap::names`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct OriginalScope {
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+    /// The name of the scope, e.g. the name of a function.
+    pub name: Option<u32>,
+    /// The kind of the scope, e.g. `"global"`, `"function"` or `"block"`.
+    pub kind: Option<u32>,
+    /// Whether the scope is a function, which shows up as a frame in stack traces.
+    pub is_stack_frame: bool,
+    /// The names of the variables declared in the scope.
+    pub variables: Vec<u32>,
+    pub children: Vec<OriginalScope>,
+}
+
+/// A range in the generated code, e.g. the output of a function.
+///
+/// Positions are `(line, column)`, and names are indices into `SourceMap::names`.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct GeneratedRange {
+    pub start: (u32, u32),
+    pub end: (u32, u32),
+    /// The original scope of the range, as the index of its source and the index of the scope
+    /// in a pre-order traversal of the scope tree of that source.
+    pub definition: Option<(u32, u32)>,
+    /// The original position `(source, line, column)` of the call a range was inlined at.
+    pub callsite: Option<(u32, u32, u32)>,
+    pub is_stack_frame: bool,
+    pub is_hidden: bool,
+    /// For each variable of the original scope, the expression holding its value in the range,
+    /// e.g. the mangled name of the variable, or `None` if it isn't available.
+    pub bindings: Vec<Option<u32>>,
+    pub children: Vec<GeneratedRange>,
+}
+
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn encode_original_scope(scope: &OriginalScope) -> String {
+    #[derive(Default)]
+    struct Encoder {
+        out: String,
+        line: u32,
+        name: u32,
+        kind: u32,
+        variable: u32,
+    }
+
+    impl Encoder {
+        fn position(&mut self, (line, column): (u32, u32)) {
+            if !self.out.is_empty() {
+                self.out.push(',');
+            }
+            push_vlq(&mut self.out, i64::from(line) - i64::from(self.line));
+            push_vlq(&mut self.out, i64::from(column));
+            self.line = line;
+        }
+
+        fn scope(&mut self, scope: &OriginalScope) {
+            self.position(scope.start);
+            let mut flags = 0;
+            if scope.name.is_some() {
+                flags |= ORIGINAL_SCOPE_HAS_NAME;
+            }
+            if scope.kind.is_some() {
+                flags |= ORIGINAL_SCOPE_HAS_KIND;
+            }
+            if scope.is_stack_frame {
+                flags |= ORIGINAL_SCOPE_IS_STACK_FRAME;
+            }
+            push_vlq(&mut self.out, flags);
+            if let Some(name) = scope.name {
+                push_vlq(&mut self.out, i64::from(name) - i64::from(self.name));
+                self.name = name;
+            }
+            if let Some(kind) = scope.kind {
+                push_vlq(&mut self.out, i64::from(kind) - i64::from(self.kind));
+                self.kind = kind;
+            }
+            for &variable in &scope.variables {
+                push_vlq(&mut self.out, i64::from(variable) - i64::from(self.variable));
+                self.variable = variable;
+            }
+            for child in &scope.children {
+                self.scope(child);
+            }
+            self.position(scope.end);
+        }
+    }
+
+    let mut encoder = Encoder::default();
+    encoder.scope(scope);
+    encoder.out
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn decode_original_scope(value: &str, names_len: usize) -> Result<OriginalScope> {
+    let mut stack: Vec<OriginalScope> = vec![];
+    let mut root = None;
+    let (mut line, mut name, mut kind, mut variable) = (0, 0, 0, 0);
+    let mut nums = Vec::with_capacity(8);
+
+    for item in value.split(',') {
+        if item.is_empty() {
+            continue;
+        }
+        nums.clear();
+        parse_vlq_segment_into(item, &mut nums)?;
+        if nums.len() < 2 {
+            return Err(Error::BadSegmentSize(nums.len() as u32));
+        }
+        line += nums[0];
+        let position = (line as u32, nums[1] as u32);
+
+        if nums.len() == 2 {
+            let mut scope = stack.pop().ok_or(Error::UnbalancedScopes)?;
+            scope.end = position;
+            match stack.last_mut() {
+                Some(parent) => parent.children.push(scope),
+                None => root = Some(scope),
+            }
+            continue;
+        }
+
+        if root.is_some() {
+            // Only one top-level scope per source.
+            return Err(Error::UnbalancedScopes);
+        }
+        let flags = nums[2];
+        let mut rest = nums[3..].iter().copied();
+        let mut next_name = |prev: &mut i64| -> Result<u32> {
+            *prev += rest.next().ok_or(Error::BadSegmentSize(nums.len() as u32))?;
+            name_reference(*prev, names_len)
+        };
+        let scope_name =
+            if flags & ORIGINAL_SCOPE_HAS_NAME == 0 { None } else { Some(next_name(&mut name)?) };
+        let scope_kind =
+            if flags & ORIGINAL_SCOPE_HAS_KIND == 0 { None } else { Some(next_name(&mut kind)?) };
+        let variables = rest
+            .map(|delta| {
+                variable += delta;
+                name_reference(variable, names_len)
+            })
+            .collect::<Result<_>>()?;
+        stack.push(OriginalScope {
+            start: position,
+            end: position,
+            name: scope_name,
+            kind: scope_kind,
+            is_stack_frame: flags & ORIGINAL_SCOPE_IS_STACK_FRAME != 0,
+            variables,
+            children: vec![],
+        });
+    }
+
+    match root {
+        Some(root) if stack.is_empty() => Ok(root),
+        _ => Err(Error::UnbalancedScopes),
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+pub(crate) fn encode_generated_ranges(ranges: &[GeneratedRange]) -> String {
+    #[derive(Default)]
+    struct Encoder {
+        out: String,
+        line: u32,
+        column: u32,
+        has_item_on_line: bool,
+        definition: (u32, u32),
+        callsite: (u32, u32, u32),
+    }
+
+    impl Encoder {
+        fn position(&mut self, (line, column): (u32, u32)) {
+            if line > self.line {
+                self.out.extend(std::iter::repeat(';').take((line - self.line) as usize));
+                self.line = line;
+                self.column = 0;
+            } else if self.has_item_on_line {
+                self.out.push(',');
+            }
+            push_vlq(&mut self.out, i64::from(column) - i64::from(self.column));
+            self.column = column;
+            self.has_item_on_line = true;
+        }
+
+        fn range(&mut self, range: &GeneratedRange) {
+            self.position(range.start);
+            let mut flags = 0;
+            if range.definition.is_some() {
+                flags |= GENERATED_RANGE_HAS_DEFINITION;
+            }
+            if range.callsite.is_some() {
+                flags |= GENERATED_RANGE_HAS_CALLSITE;
+            }
+            if range.is_stack_frame {
+                flags |= GENERATED_RANGE_IS_STACK_FRAME;
+            }
+            if range.is_hidden {
+                flags |= GENERATED_RANGE_IS_HIDDEN;
+            }
+            push_vlq(&mut self.out, flags);
+            if let Some((source, scope)) = range.definition {
+                let (prev_source, prev_scope) = self.definition;
+                push_vlq(&mut self.out, i64::from(source) - i64::from(prev_source));
+                let prev_scope = if source == prev_source { prev_scope } else { 0 };
+                push_vlq(&mut self.out, i64::from(scope) - i64::from(prev_scope));
+                self.definition = (source, scope);
+            }
+            if let Some((source, line, column)) = range.callsite {
+                let (prev_source, prev_line, prev_column) = self.callsite;
+                push_vlq(&mut self.out, i64::from(source) - i64::from(prev_source));
+                let prev_line = if source == prev_source { prev_line } else { 0 };
+                push_vlq(&mut self.out, i64::from(line) - i64::from(prev_line));
+                let prev_column =
+                    if source == prev_source && line == prev_line { prev_column } else { 0 };
+                push_vlq(&mut self.out, i64::from(column) - i64::from(prev_column));
+                self.callsite = (source, line, column);
+            }
+            for binding in &range.bindings {
+                push_vlq(&mut self.out, binding.map_or(-1, i64::from));
+            }
+            for child in &range.children {
+                self.range(child);
+            }
+            self.position(range.end);
+        }
+    }
+
+    let mut encoder = Encoder::default();
+    for range in ranges {
+        encoder.range(range);
+    }
+    encoder.out
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn decode_generated_ranges(
+    value: &str,
+    names_len: usize,
+    sources_len: usize,
+) -> Result<Vec<GeneratedRange>> {
+    let mut stack: Vec<GeneratedRange> = vec![];
+    let mut ranges = vec![];
+    let mut definition = (0, 0);
+    let mut callsite = (0, 0, 0);
+    let mut nums = Vec::with_capacity(8);
+
+    for (line, items) in value.split(';').enumerate() {
+        let mut column = 0;
+        for item in items.split(',') {
+            if item.is_empty() {
+                continue;
+            }
+            nums.clear();
+            parse_vlq_segment_into(item, &mut nums)?;
+            column += nums[0];
+            let position = (line as u32, column as u32);
+
+            if nums.len() == 1 {
+                let mut range = stack.pop().ok_or(Error::UnbalancedScopes)?;
+                range.end = position;
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(range),
+                    None => ranges.push(range),
+                }
+                continue;
+            }
+
+            let flags = nums[1];
+            let mut rest = nums[2..].iter().copied();
+            let mut next = || rest.next().ok_or(Error::BadSegmentSize(nums.len() as u32));
+            let range_definition = if flags & GENERATED_RANGE_HAS_DEFINITION == 0 {
+                None
+            } else {
+                let (prev_source, prev_scope) = definition;
+                let source = prev_source + next()?;
+                let prev_scope = if source == prev_source { prev_scope } else { 0 };
+                definition = (source, prev_scope + next()?);
+                Some((source_reference(source, sources_len)?, definition.1 as u32))
+            };
+            let range_callsite = if flags & GENERATED_RANGE_HAS_CALLSITE == 0 {
+                None
+            } else {
+                let (prev_source, prev_line, prev_column) = callsite;
+                let source = prev_source + next()?;
+                let prev_line = if source == prev_source { prev_line } else { 0 };
+                let line = prev_line + next()?;
+                let prev_column =
+                    if source == prev_source && line == prev_line { prev_column } else { 0 };
+                callsite = (source, line, prev_column + next()?);
+                Some((source_reference(source, sources_len)?, line as u32, callsite.2 as u32))
+            };
+            let bindings = rest
+                .map(|binding| {
+                    if binding == -1 {
+                        Ok(None)
+                    } else {
+                        name_reference(binding, names_len).map(Some)
+                    }
+                })
+                .collect::<Result<_>>()?;
+            stack.push(GeneratedRange {
+                start: position,
+                end: position,
+                definition: range_definition,
+                callsite: range_callsite,
+                is_stack_frame: flags & GENERATED_RANGE_IS_STACK_FRAME != 0,
+                is_hidden: flags & GENERATED_RANGE_IS_HIDDEN != 0,
+                bindings,
+                children: vec![],
+            });
+        }
+    }
+
+    if !stack.is_empty() {
+        return Err(Error::UnbalancedScopes);
+    }
+    Ok(ranges)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn name_reference(id: i64, names_len: usize) -> Result<u32> {
+    if id < 0 || id as usize >= names_len {
+        return Err(Error::BadNameReference(id as u32));
+    }
+    Ok(id as u32)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn source_reference(id: i64, sources_len: usize) -> Result<u32> {
+    if id < 0 || id as usize >= sources_len {
+        return Err(Error::BadSourceReference(id as u32));
+    }
+    Ok(id as u32)
+}
+
+#[test]
+fn test_original_scope() {
+    // function foo(a) {
+    //   let b;
+    //   { let c; }
+    // }
+    let scope = OriginalScope {
+        start: (0, 0),
+        end: (4, 0),
+        kind: Some(0),
+        variables: vec![1],
+        children: vec![OriginalScope {
+            start: (0, 12),
+            end: (3, 1),
+            name: Some(1),
+            kind: Some(2),
+            is_stack_frame: true,
+            variables: vec![3, 4],
+            children: vec![OriginalScope {
+                start: (2, 2),
+                end: (2, 12),
+                kind: Some(5),
+                variables: vec![6],
+                ..OriginalScope::default()
+            }],
+        }],
+        ..OriginalScope::default()
+    };
+    let encoded = encode_original_scope(&scope);
+    assert_eq!(encoded, "AAEAC,AYOCEEC,EEEGE,AY,CC,CA");
+    assert_eq!(decode_original_scope(&encoded, 7).unwrap(), scope);
+
+    assert!(matches!(decode_original_scope(&encoded, 6), Err(Error::BadNameReference(6))));
+    assert!(matches!(decode_original_scope("AAA", 0), Err(Error::UnbalancedScopes)));
+    assert!(matches!(decode_original_scope("AAA,AA,AA", 0), Err(Error::UnbalancedScopes)));
+}
+
+#[test]
+fn test_generated_ranges() {
+    let ranges = vec![GeneratedRange {
+        start: (0, 0),
+        end: (2, 0),
+        definition: Some((0, 0)),
+        bindings: vec![Some(0)],
+        children: vec![
+            GeneratedRange {
+                start: (0, 10),
+                end: (1, 5),
+                definition: Some((0, 1)),
+                is_stack_frame: true,
+                bindings: vec![Some(2), None],
+                ..GeneratedRange::default()
+            },
+            GeneratedRange {
+                start: (1, 6),
+                end: (1, 20),
+                definition: Some((1, 0)),
+                callsite: Some((0, 3, 4)),
+                is_hidden: true,
+                ..GeneratedRange::default()
+            },
+        ],
+        ..GeneratedRange::default()
+    }];
+    let encoded = encode_generated_ranges(&ranges);
+    assert_eq!(encoded, "ACAAA,UKACED;K,CWCAAGI,c;A");
+    assert_eq!(decode_generated_ranges(&encoded, 3, 2).unwrap(), ranges);
+
+    assert!(matches!(decode_generated_ranges(&encoded, 3, 1), Err(Error::BadSourceReference(1))));
+    assert!(matches!(decode_generated_ranges("AA", 0, 0), Err(Error::UnbalancedScopes)));
+}
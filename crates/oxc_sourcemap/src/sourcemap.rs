@@ -5,9 +5,19 @@ use crate::{
     encode::{encode, encode_to_string},
     error::Result,
     token::{Token, TokenChunk},
-    SourceViewToken,
+    GeneratedRange, OriginalScope, SourceViewToken,
 };
 
+/// Which token to pick when there is no token at the exact position of a lookup.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Bias {
+    /// The closest token before the position.
+    #[default]
+    GreatestLowerBound,
+    /// The closest token after the position.
+    LeastUpperBound,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SourceMap {
     pub(crate) file: Option<Arc<str>>,
@@ -21,6 +31,13 @@ pub struct SourceMap {
     /// The `x_google_ignoreList` field refers to the `sources` array, and lists the indices of all the known third-party sources in that source map.
     /// When parsing the source map, developer tools can use this to determine sections of the code that the browser loads and runs that could be automatically ignore-listed.
     pub(crate) x_google_ignore_list: Option<Vec<u32>>,
+    /// A globally unique identifier of the generated code, see <https://github.com/tc39/ecma426/blob/main/proposals/debug-id.md>.
+    /// Error tracking services use it to find the source map of a stack trace without relying on file names.
+    pub(crate) debug_id: Option<String>,
+    /// The scope tree of each source, see [`OriginalScope`].
+    pub(crate) original_scopes: Option<Vec<Option<OriginalScope>>>,
+    /// The ranges of the generated code that correspond to original scopes, see [`GeneratedRange`].
+    pub(crate) generated_ranges: Option<Vec<GeneratedRange>>,
 }
 
 #[allow(clippy::cast_possible_truncation)]
@@ -43,6 +60,9 @@ impl SourceMap {
             tokens,
             token_chunks,
             x_google_ignore_list: None,
+            debug_id: None,
+            original_scopes: None,
+            generated_ranges: None,
         }
     }
 
@@ -95,6 +115,33 @@ impl SourceMap {
         self.x_google_ignore_list = Some(x_google_ignore_list);
     }
 
+    /// Set `debugId`.
+    pub fn set_debug_id(&mut self, debug_id: &str) {
+        self.debug_id = Some(debug_id.into());
+    }
+
+    pub fn get_debug_id(&self) -> Option<&str> {
+        self.debug_id.as_deref()
+    }
+
+    /// Set `originalScopes`, with the scope tree of each source in the same order as `sources`.
+    pub fn set_original_scopes(&mut self, original_scopes: Vec<Option<OriginalScope>>) {
+        self.original_scopes = Some(original_scopes);
+    }
+
+    pub fn get_original_scopes(&self) -> Option<&[Option<OriginalScope>]> {
+        self.original_scopes.as_deref()
+    }
+
+    /// Set `generatedRanges`.
+    pub fn set_generated_ranges(&mut self, generated_ranges: Vec<GeneratedRange>) {
+        self.generated_ranges = Some(generated_ranges);
+    }
+
+    pub fn get_generated_ranges(&self) -> Option<&[GeneratedRange]> {
+        self.generated_ranges.as_deref()
+    }
+
     pub fn get_names(&self) -> impl Iterator<Item = &str> {
         self.names.iter().map(AsRef::as_ref)
     }
@@ -185,6 +232,119 @@ impl SourceMap {
     ) -> Option<SourceViewToken<'_>> {
         self.lookup_token(lookup_table, line, col).map(|token| SourceViewToken::new(token, self))
     }
+
+    /// Lookup a token by line and column, picking the closest token in the direction of `bias`
+    /// when there is no token at the position.
+    pub fn lookup_token_with_bias(
+        &self,
+        lookup_table: &[(u32, u32, u32)],
+        line: u32,
+        col: u32,
+        bias: Bias,
+    ) -> Option<&Token> {
+        let key = (line, col);
+        let table = match bias {
+            Bias::GreatestLowerBound => {
+                greatest_lower_bound(lookup_table, &key, |table| (table.0, table.1))
+            }
+            Bias::LeastUpperBound => {
+                least_upper_bound(lookup_table, &key, |table| (table.0, table.1))
+            }
+        }?;
+        self.get_token(table.2)
+    }
+
+    /// Generate a lookup table of the original positions of the tokens, sorted by source, line and column.
+    /// It will be used at `lookup_generated_token` or `lookup_generated_tokens`.
+    pub fn generate_original_lookup_table(&self) -> Vec<(u32, u32, u32, u32)> {
+        let mut table = self
+            .tokens
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, token)| {
+                Some((token.source_id?, token.src_line, token.src_col, idx as u32))
+            })
+            .collect::<Vec<_>>();
+        table.sort_unstable();
+        table
+    }
+
+    /// Lookup the token of an original position in source `source_id`, picking the closest token
+    /// of the same source in the direction of `bias` when there is no token at the position.
+    pub fn lookup_generated_token(
+        &self,
+        original_lookup_table: &[(u32, u32, u32, u32)],
+        source_id: u32,
+        line: u32,
+        col: u32,
+        bias: Bias,
+    ) -> Option<&Token> {
+        let key = (source_id, line, col);
+        let map = |table: &(u32, u32, u32, u32)| (table.0, table.1, table.2);
+        let table = match bias {
+            Bias::GreatestLowerBound => greatest_lower_bound(original_lookup_table, &key, map),
+            Bias::LeastUpperBound => least_upper_bound(original_lookup_table, &key, map),
+        }?;
+        if table.0 != source_id {
+            return None;
+        }
+        self.get_token(table.3)
+    }
+
+    /// Lookup all tokens of an original position in source `source_id`.
+    ///
+    /// If there are no tokens at the position, the tokens of the closest position after it are returned:
+    /// on the same line if `col` is given, or on the closest line with tokens if `col` is `None`.
+    pub fn lookup_generated_tokens(
+        &self,
+        original_lookup_table: &[(u32, u32, u32, u32)],
+        source_id: u32,
+        line: u32,
+        col: Option<u32>,
+    ) -> Vec<&Token> {
+        let key = (source_id, line, col.unwrap_or(0));
+        let start =
+            original_lookup_table.partition_point(|table| (table.0, table.1, table.2) < key);
+        let Some(first) = original_lookup_table.get(start) else {
+            return vec![];
+        };
+        if first.0 != source_id || (col.is_some() && first.1 != line) {
+            return vec![];
+        }
+        let len = original_lookup_table[start..].partition_point(|table| {
+            table.0 == first.0 && table.1 == first.1 && (col.is_none() || table.2 == first.2)
+        });
+        original_lookup_table[start..start + len]
+            .iter()
+            .filter_map(|table| self.get_token(table.3))
+            .collect()
+    }
+
+    /// Map a generated range to the tokens of the start and end of its original range, e.g. to
+    /// report code coverage of the generated code on the original sources.
+    ///
+    /// The start token is the token covering `start`, and the end token is the last token before
+    /// the exclusive `end`. Returns `None` if the range maps to more than one source.
+    pub fn lookup_original_range(
+        &self,
+        lookup_table: &[(u32, u32, u32)],
+        start: (u32, u32),
+        end: (u32, u32),
+    ) -> Option<(&Token, &Token)> {
+        let map = |table: &(u32, u32, u32)| (table.0, table.1);
+        let first = greatest_lower_bound(lookup_table, &start, map)
+            .or_else(|| least_upper_bound(lookup_table, &start, map))?;
+        let last_idx = lookup_table.partition_point(|table| map(table) < end).checked_sub(1)?;
+        let last = &lookup_table[last_idx];
+        if map(first) > map(last) {
+            return None;
+        }
+        let (first, last) = (self.get_token(first.2)?, self.get_token(last.2)?);
+        if first.source_id.is_none() || first.source_id != last.source_id {
+            return None;
+        }
+        Some((first, last))
+    }
 }
 
 fn greatest_lower_bound<'a, T, K: Ord, F: Fn(&'a T) -> K>(
@@ -214,6 +374,26 @@ fn greatest_lower_bound<'a, T, K: Ord, F: Fn(&'a T) -> K>(
     slice.get(idx)
 }
 
+fn least_upper_bound<'a, T, K: Ord, F: Fn(&'a T) -> K>(
+    slice: &'a [T],
+    key: &K,
+    map: F,
+) -> Option<&'a T> {
+    // The first token with a key greater than or equal to `key`.
+    let index = match slice.binary_search_by_key(key, &map) {
+        Ok(index) => {
+            // Continue looking at previous tokens for exact matches, same as `greatest_lower_bound`.
+            let mut index = index;
+            while index > 0 && map(&slice[index - 1]) == *key {
+                index -= 1;
+            }
+            index
+        }
+        Err(index) => index,
+    };
+    slice.get(index)
+}
+
 #[test]
 fn test_sourcemap_lookup_token() {
     let input = r#"{
@@ -251,6 +431,100 @@ fn test_sourcemap_lookup_token() {
     );
 }
 
+#[test]
+fn test_sourcemap_lookup_token_with_bias() {
+    let sm = SourceMap::new(
+        None,
+        vec![],
+        None,
+        vec!["a.js".into()],
+        None,
+        vec![Token::new(0, 0, 0, 0, Some(0), None), Token::new(0, 10, 1, 0, Some(0), None)],
+        None,
+    );
+    let lookup_table = sm.generate_lookup_table();
+    let lookup = |line, col, bias| sm.lookup_token_with_bias(&lookup_table, line, col, bias);
+    assert_eq!(lookup(0, 5, Bias::GreatestLowerBound), sm.get_token(0));
+    assert_eq!(lookup(0, 5, Bias::LeastUpperBound), sm.get_token(1));
+    assert_eq!(lookup(0, 10, Bias::LeastUpperBound), sm.get_token(1));
+    assert_eq!(lookup(0, 11, Bias::LeastUpperBound), None);
+}
+
+#[test]
+fn test_sourcemap_lookup_generated_token() {
+    let sm = SourceMap::new(
+        None,
+        vec![],
+        None,
+        vec!["a.js".into(), "b.js".into()],
+        None,
+        vec![
+            Token::new(0, 0, 1, 4, Some(0), None),
+            Token::new(0, 8, 1, 4, Some(0), None),
+            Token::new(1, 0, 1, 10, Some(0), None),
+            Token::new(2, 0, 3, 0, Some(0), None),
+            Token::new(3, 0, 0, 0, Some(1), None),
+            Token::new(3, 2, 0, 0, None, None),
+        ],
+        None,
+    );
+    let table = sm.generate_original_lookup_table();
+    assert_eq!(table.len(), 5);
+
+    let lookup = |source_id, line, col, bias| {
+        sm.lookup_generated_token(&table, source_id, line, col, bias).map(Token::get_dst_line)
+    };
+    assert_eq!(lookup(0, 1, 4, Bias::GreatestLowerBound), Some(0));
+    assert_eq!(lookup(0, 1, 6, Bias::GreatestLowerBound), Some(0));
+    assert_eq!(lookup(0, 1, 6, Bias::LeastUpperBound), Some(1));
+    assert_eq!(lookup(0, 0, 0, Bias::GreatestLowerBound), None);
+    // Doesn't cross into another source.
+    assert_eq!(lookup(0, 5, 0, Bias::LeastUpperBound), None);
+    assert_eq!(lookup(1, 0, 0, Bias::GreatestLowerBound), Some(3));
+
+    let lookup_all = |source_id, line, col| {
+        sm.lookup_generated_tokens(&table, source_id, line, col)
+            .into_iter()
+            .map(|token| (token.get_dst_line(), token.get_dst_col()))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(lookup_all(0, 1, Some(4)), [(0, 0), (0, 8)]);
+    assert_eq!(lookup_all(0, 1, Some(5)), [(1, 0)]);
+    assert_eq!(lookup_all(0, 1, Some(11)), []);
+    assert_eq!(lookup_all(0, 1, None), [(0, 0), (0, 8), (1, 0)]);
+    assert_eq!(lookup_all(0, 2, None), [(2, 0)]);
+    assert_eq!(lookup_all(0, 4, None), []);
+}
+
+#[test]
+fn test_sourcemap_lookup_original_range() {
+    let sm = SourceMap::new(
+        None,
+        vec![],
+        None,
+        vec!["a.js".into(), "b.js".into()],
+        None,
+        vec![
+            Token::new(0, 0, 0, 0, Some(0), None),
+            Token::new(0, 4, 0, 6, Some(0), None),
+            Token::new(0, 10, 2, 0, Some(0), None),
+            Token::new(1, 0, 0, 0, Some(1), None),
+        ],
+        None,
+    );
+    let table = sm.generate_lookup_table();
+    let lookup = |start, end| {
+        sm.lookup_original_range(&table, start, end).map(|(first, last)| {
+            ((first.get_src_line(), first.get_src_col()), (last.get_src_line(), last.get_src_col()))
+        })
+    };
+    assert_eq!(lookup((0, 2), (0, 12)), Some(((0, 0), (2, 0))));
+    assert_eq!(lookup((0, 4), (0, 10)), Some(((0, 6), (0, 6))));
+    assert_eq!(lookup((0, 5), (0, 8)), Some(((0, 6), (0, 6))));
+    // Spans two sources.
+    assert_eq!(lookup((0, 0), (1, 1)), None);
+}
+
 #[test]
 fn test_sourcemap_source_view_token() {
     let sm = SourceMap::new(
@@ -4,7 +4,7 @@ use rustc_hash::FxHashMap;
 
 use crate::{
     token::{Token, TokenChunk},
-    SourceMap,
+    GeneratedRange, OriginalScope, SourceMap,
 };
 
 /// The `SourceMapBuilder` is a helper to generate sourcemap.
@@ -18,6 +18,8 @@ pub struct SourceMapBuilder {
     pub(crate) source_contents: Vec<Arc<str>>,
     pub(crate) tokens: Vec<Token>,
     pub(crate) token_chunks: Option<Vec<TokenChunk>>,
+    pub(crate) original_scopes: Option<Vec<Option<OriginalScope>>>,
+    pub(crate) generated_ranges: Option<Vec<GeneratedRange>>,
 }
 
 #[allow(clippy::cast_possible_truncation)]
@@ -75,8 +77,18 @@ impl SourceMapBuilder {
         self.token_chunks = Some(token_chunks);
     }
 
+    /// Set the `SourceMap::original_scopes`, with the scope tree of each source in the same order as `sources`.
+    pub fn set_original_scopes(&mut self, original_scopes: Vec<Option<OriginalScope>>) {
+        self.original_scopes = Some(original_scopes);
+    }
+
+    /// Set the `SourceMap::generated_ranges`.
+    pub fn set_generated_ranges(&mut self, generated_ranges: Vec<GeneratedRange>) {
+        self.generated_ranges = Some(generated_ranges);
+    }
+
     pub fn into_sourcemap(self) -> SourceMap {
-        SourceMap::new(
+        let mut sourcemap = SourceMap::new(
             self.file,
             self.names,
             None,
@@ -84,7 +96,10 @@ impl SourceMapBuilder {
             Some(self.source_contents),
             self.tokens,
             self.token_chunks,
-        )
+        );
+        sourcemap.original_scopes = self.original_scopes;
+        sourcemap.generated_ranges = self.generated_ranges;
+        sourcemap
     }
 }
 
@@ -51,7 +51,7 @@ use regexp::RegExp;
 
 pub use crate::{
     compiler_assumptions::CompilerAssumptions,
-    env::{EnvOptions, Targets},
+    env::{can_enable_plugin, EnvOptions, Targets, Versions},
     es2015::{ArrowFunctionsOptions, ES2015Options},
     options::{BabelOptions, TransformOptions},
     plugins::*,
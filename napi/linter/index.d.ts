@@ -0,0 +1,83 @@
+/* auto-generated by NAPI-RS */
+/* eslint-disable */
+/**
+ * A linter with a parsed configuration, to lint many files without parsing
+ * the configuration each time.
+ */
+export declare class Linter {
+  /**
+   * @param config The contents of an `.oxlintrc.json` file. `extends` is not
+   * supported. Without a configuration, all `correctness` rules are enabled.
+   *
+   * @throws if the configuration is invalid.
+   */
+  constructor(config?: Record<string, any> | undefined | null)
+  /**
+   * Lint a file.
+   *
+   * @param filename The name of the file being linted, used for the source
+   * type and to match the globs of `overrides`, which are relative to the
+   * directory of the configuration file.
+   * @param sourceText The source code itself.
+   */
+  lint(filename: string, sourceText: string): LintResult
+}
+
+export interface Diagnostic {
+  message: string
+  severity: 'error' | 'warning' | 'advice'
+  /**
+   * Plugin of the rule that reported the problem, e.g. `eslint` or `typescript-eslint`.
+   *
+   * Not set for syntax errors.
+   */
+  plugin?: string
+  /**
+   * Name of the rule that reported the problem, e.g. `no-debugger`.
+   *
+   * Not set for syntax errors.
+   */
+  ruleName?: string
+  help?: string
+  /** Link to the documentation of the rule. */
+  url?: string
+  /** Spans of the source code the problem points at. */
+  labels: Array<Label>
+  /** A fix that can safely be applied to the source code. */
+  fix?: Fix
+}
+
+/** Replace the source text between the UTF-8 byte offsets `start` and `end` with `content`. */
+export interface Fix {
+  content: string
+  start: number
+  end: number
+}
+
+/** A span of the source text, in UTF-8 byte offsets. */
+export interface Label {
+  message?: string
+  start: number
+  end: number
+}
+
+/**
+ * Lint a file.
+ *
+ * Use a {@link Linter} instead to lint many files with the same configuration.
+ *
+ * @param filename The name of the file being linted, used for the source type
+ * and to match the globs of `overrides`, which are relative to the directory
+ * of the configuration file.
+ * @param sourceText The source code itself.
+ * @param config The contents of an `.oxlintrc.json` file. `extends` is not
+ * supported. Without a configuration, all `correctness` rules are enabled.
+ *
+ * @throws if the configuration is invalid.
+ */
+export declare function lint(filename: string, sourceText: string, config?: Record<string, any> | undefined | null): LintResult
+
+export interface LintResult {
+  /** Problems found by the linter, and syntax errors that prevented the file from being linted. */
+  diagnostics: Array<Diagnostic>
+}
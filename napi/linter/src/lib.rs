@@ -0,0 +1,200 @@
+use std::{path::Path, rc::Rc};
+
+use napi_derive::napi;
+use serde_json::Value;
+
+use oxc_allocator::Allocator;
+use oxc_diagnostics::{OxcDiagnostic, Severity};
+use oxc_linter::{
+    loader::{JavaScriptSource, Loader},
+    FixKind, LinterBuilder, Oxlintrc,
+};
+use oxc_parser::{ParseOptions, Parser};
+use oxc_semantic::SemanticBuilder;
+
+#[napi(object)]
+pub struct LintResult {
+    /// Problems found by the linter, and syntax errors that prevented the file from being linted.
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+#[napi(object)]
+pub struct Diagnostic {
+    pub message: String,
+    #[napi(ts_type = "'error' | 'warning' | 'advice'")]
+    pub severity: &'static str,
+    /// Plugin of the rule that reported the problem, e.g. `eslint` or `typescript-eslint`.
+    ///
+    /// Not set for syntax errors.
+    pub plugin: Option<String>,
+    /// Name of the rule that reported the problem, e.g. `no-debugger`.
+    ///
+    /// Not set for syntax errors.
+    pub rule_name: Option<String>,
+    pub help: Option<String>,
+    /// Link to the documentation of the rule.
+    pub url: Option<String>,
+    /// Spans of the source code the problem points at.
+    pub labels: Vec<Label>,
+    /// A fix that can safely be applied to the source code.
+    pub fix: Option<Fix>,
+}
+
+/// A span of the source text, in UTF-8 byte offsets.
+#[napi(object)]
+pub struct Label {
+    pub message: Option<String>,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// Replace the source text between the UTF-8 byte offsets `start` and `end` with `content`.
+#[napi(object)]
+pub struct Fix {
+    pub content: String,
+    pub start: u32,
+    pub end: u32,
+}
+
+/// A linter with a parsed configuration, to lint many files without parsing
+/// the configuration each time.
+#[napi]
+pub struct Linter {
+    linter: oxc_linter::Linter,
+}
+
+#[napi]
+impl Linter {
+    /// @param config The contents of an `.oxlintrc.json` file. `extends` is not
+    /// supported. Without a configuration, all `correctness` rules are enabled.
+    ///
+    /// @throws if the configuration is invalid.
+    #[allow(clippy::missing_errors_doc)] // documented with `@throws`
+    #[napi(constructor)]
+    pub fn new(
+        #[napi(ts_arg_type = "Record<string, any> | undefined | null")] config: Option<Value>,
+    ) -> napi::Result<Self> {
+        let oxlintrc = match config {
+            Some(config) => serde_json::from_value::<Oxlintrc>(config).map_err(|err| {
+                napi::Error::from_reason(format!("Failed to parse config with error {err}"))
+            })?,
+            None => Oxlintrc::default(),
+        };
+        let linter = LinterBuilder::from(oxlintrc).with_fix(FixKind::SafeFix).build();
+        Ok(Self { linter })
+    }
+
+    /// Lint a file.
+    ///
+    /// @param filename The name of the file being linted, used for the source
+    /// type and to match the globs of `overrides`, which are relative to the
+    /// directory of the configuration file.
+    /// @param sourceText The source code itself.
+    #[allow(clippy::needless_pass_by_value)]
+    #[napi]
+    pub fn lint(&self, filename: String, source_text: String) -> LintResult {
+        let path = Path::new(&filename);
+        let sources = match Loader.load_str(path, &source_text) {
+            Ok(sources) => sources,
+            Err(err) => {
+                let error = OxcDiagnostic::error(format!("Failed to load {filename}: {err}"));
+                return LintResult { diagnostics: vec![diagnostic(&error, None, 0)] };
+            }
+        };
+
+        let mut diagnostics = vec![];
+        for source in sources {
+            let JavaScriptSource { source_text, source_type, start, .. } = source;
+            let allocator = Allocator::default();
+            let ret = Parser::new(&allocator, source_text, source_type)
+                .with_options(ParseOptions {
+                    parse_regular_expression: true,
+                    allow_return_outside_function: true,
+                    ..ParseOptions::default()
+                })
+                .parse();
+            if !ret.errors.is_empty() {
+                diagnostics
+                    .extend(ret.errors.into_iter().map(|error| diagnostic(&error, None, start)));
+                continue;
+            }
+
+            let program = allocator.alloc(ret.program);
+            let semantic_ret = SemanticBuilder::new(source_text)
+                .with_cfg(true)
+                .with_trivias(ret.trivias)
+                .with_build_jsdoc(true)
+                .with_check_syntax_error(true)
+                .build_module_record(path, program)
+                .build(program);
+            if !semantic_ret.errors.is_empty() {
+                diagnostics.extend(
+                    semantic_ret.errors.into_iter().map(|error| diagnostic(&error, None, start)),
+                );
+                continue;
+            }
+
+            let messages = self.linter.run_with_overrides(path, Rc::new(semantic_ret.semantic));
+            diagnostics.extend(messages.into_iter().map(|message| {
+                let fix = message.fix.map(|fix| Fix {
+                    content: fix.content.into_owned(),
+                    start: fix.span.start + start,
+                    end: fix.span.end + start,
+                });
+                diagnostic(&message.error, fix, start)
+            }));
+        }
+        LintResult { diagnostics }
+    }
+}
+
+/// Lint a file.
+///
+/// Use a {@link Linter} instead to lint many files with the same configuration.
+///
+/// @param filename The name of the file being linted, used for the source type
+/// and to match the globs of `overrides`, which are relative to the directory
+/// of the configuration file.
+/// @param sourceText The source code itself.
+/// @param config The contents of an `.oxlintrc.json` file. `extends` is not
+/// supported. Without a configuration, all `correctness` rules are enabled.
+///
+/// @throws if the configuration is invalid.
+#[allow(clippy::missing_errors_doc)] // documented with `@throws`
+#[napi]
+pub fn lint(
+    filename: String,
+    source_text: String,
+    #[napi(ts_arg_type = "Record<string, any> | undefined | null")] config: Option<Value>,
+) -> napi::Result<LintResult> {
+    Ok(Linter::new(config)?.lint(filename, source_text))
+}
+
+#[allow(clippy::cast_possible_truncation)] // for `as u32`
+fn diagnostic(error: &OxcDiagnostic, fix: Option<Fix>, start: u32) -> Diagnostic {
+    let severity = match error.severity {
+        Severity::Error => "error",
+        Severity::Warning => "warning",
+        Severity::Advice => "advice",
+    };
+    let labels = error
+        .labels
+        .iter()
+        .flatten()
+        .map(|label| Label {
+            message: label.label().map(ToString::to_string),
+            start: label.offset() as u32 + start,
+            end: (label.offset() + label.len()) as u32 + start,
+        })
+        .collect();
+    Diagnostic {
+        message: error.message.to_string(),
+        severity,
+        plugin: error.code.scope.as_ref().map(ToString::to_string),
+        rule_name: error.code.number.as_ref().map(ToString::to_string),
+        help: error.help.as_ref().map(ToString::to_string),
+        url: error.url.as_ref().map(ToString::to_string),
+        labels,
+        fix,
+    }
+}
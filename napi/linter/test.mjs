@@ -0,0 +1,45 @@
+import assert from 'assert';
+import oxc from './index.js';
+
+console.log(`Testing on ${process.platform}-${process.arch}`);
+
+{
+  const ret = oxc.lint('test.js', 'debugger;\n');
+  assert.equal(ret.diagnostics.length, 1);
+  const [diagnostic] = ret.diagnostics;
+  assert.equal(diagnostic.severity, 'warning');
+  assert.equal(diagnostic.plugin, 'eslint');
+  assert.equal(diagnostic.ruleName, 'no-debugger');
+  assert.match(diagnostic.url, /no-debugger/);
+  assert.equal(diagnostic.labels.length, 1);
+  assert.equal(diagnostic.labels[0].start, 0);
+  assert.equal(diagnostic.labels[0].end, 9);
+  assert.deepEqual(diagnostic.fix, { content: '', start: 0, end: 9 });
+}
+
+{
+  const linter = new oxc.Linter({
+    rules: { 'no-debugger': 'off', 'no-var': 'error' },
+    overrides: [{ files: ['*.test.js'], rules: { 'no-var': 'off' } }],
+  });
+  const ret = linter.lint('test.js', 'debugger;\nexport var a = 1;\n');
+  assert.equal(ret.diagnostics.length, 1);
+  assert.equal(ret.diagnostics[0].severity, 'error');
+  assert.equal(ret.diagnostics[0].ruleName, 'no-var');
+  assert.equal(linter.lint('test.test.js', 'export var a = 1;\n').diagnostics.length, 0);
+}
+
+{
+  const ret = oxc.lint('test.vue', '<template></template>\n<script>\ndebugger;\n</script>\n');
+  assert.equal(ret.diagnostics.length, 1);
+  assert.equal(ret.diagnostics[0].labels[0].start, 31);
+}
+
+{
+  const ret = oxc.lint('test.js', 'function (');
+  assert(ret.diagnostics.length > 0);
+  assert.equal(ret.diagnostics[0].severity, 'error');
+  assert.equal(ret.diagnostics[0].ruleName, undefined);
+}
+
+assert.throws(() => new oxc.Linter({ rules: 1 }), /Failed to parse config/);
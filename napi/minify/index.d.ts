@@ -1,3 +1,191 @@
 /* auto-generated by NAPI-RS */
 /* eslint-disable */
-export declare function minify(filename: string, sourceText: string): string;
+/** Configure how the minified code is printed. */
+export interface CodegenOptions {
+  /**
+   * Remove whitespace.
+   *
+   * @default true
+   */
+  removeWhitespace?: boolean
+  /**
+   * Use single quotes instead of double quotes.
+   *
+   * @default false
+   */
+  singleQuote?: boolean
+}
+
+/**
+ * Configure how the code is compressed.
+ *
+ * Options which are not set use the defaults of `oxc_minifier`.
+ */
+export interface CompressOptions {
+  /**
+   * Browserslist query of the engines which the output runs in, e.g. `'chrome 80'`,
+   * or `'esnext'`. Modern syntax is used when it is shorter and supported by all targets.
+   *
+   * @default no modern syntax is introduced
+   */
+  target?: string
+  /**
+   * Remove `console.*` calls.
+   *
+   * @default false
+   */
+  dropConsole?: boolean
+  /**
+   * Remove `debugger;` statements.
+   *
+   * @default true
+   */
+  dropDebugger?: boolean
+  /**
+   * Remove labeled statements with these labels.
+   *
+   * @default []
+   */
+  dropLabels?: Array<string>
+  /**
+   * Remove calls to these functions when their return value is unused.
+   *
+   * @default []
+   */
+  pureFuncs?: Array<string>
+  /**
+   * Replace global identifiers and member expressions with constant expressions,
+   * e.g. `{ 'process.env.NODE_ENV': '"production"' }`.
+   */
+  globalDefs?: Record<string, string>
+  /**
+   * The maximum number of times the compressor runs, `0` runs until no more changes are made.
+   *
+   * @default 0
+   */
+  passes?: number
+  /**
+   * Keep `Infinity` instead of compressing it into `1/0`.
+   *
+   * @default true
+   */
+  keepInfinity?: boolean
+  /**
+   * Transform function expressions into arrow functions in any position, not only when passed
+   * as call arguments. This breaks functions used as constructors.
+   *
+   * @default false
+   */
+  unsafeArrows?: boolean
+  /**
+   * Transform `!(a < b)` into `a >= b`, which is incorrect for `NaN`.
+   *
+   * @default false
+   */
+  unsafeComps?: boolean
+  /**
+   * Transform `2 * x * 3` into `6 * x`, which may give imprecise floating point results.
+   *
+   * @default false
+   */
+  unsafeMath?: boolean
+  /**
+   * Transform `Array.prototype.slice` into `[].slice`, and likewise for `Object` and `String`.
+   *
+   * @default false
+   */
+  unsafeProto?: boolean
+  /**
+   * Minify CSS, HTML and GraphQL in tagged templates such as `` css`...` ``, `` html`...` ``
+   * and `` gql`...` ``, and in template literals preceded by a `/* css *\/` style comment.
+   *
+   * @default false
+   */
+  embeddedTemplates?: boolean
+}
+
+/**
+ * Minify a JavaScript or TypeScript file.
+ *
+ * @param filename The name of the file being minified, used for the source type and source map.
+ * @param sourceText the source code itself
+ * @param options The options for minification. See {@link MinifyOptions} for more information.
+ *
+ * @returns an object containing the minified code, source map, and any
+ * errors that occurred during parsing.
+ */
+export declare function minify(filename: string, sourceText: string, options?: MinifyOptions | undefined | null): MinifyResult
+
+/**
+ * Options for minifying a JavaScript file.
+ *
+ * @see {@link minify}
+ */
+export interface MinifyOptions {
+  sourceType?: 'script' | 'module' | 'unambiguous' | undefined
+  /**
+   * Compress the code, or `false` to skip compression.
+   *
+   * @default true
+   */
+  compress?: boolean | CompressOptions
+  /**
+   * Shorten variable names.
+   *
+   * @default true
+   */
+  mangle?: boolean
+  /** Configure how the output is printed. */
+  codegen?: CodegenOptions
+  /**
+   * Enable source map generation.
+   *
+   * When `true`, the `map` field of the minify result object will be populated.
+   *
+   * @default false
+   *
+   * @see {@link SourceMap}
+   */
+  sourcemap?: boolean
+  /**
+   * The source map of the input code, e.g. from a previous transform step.
+   *
+   * The generated source map will map back to the sources of this source map.
+   * Only used when {@link MinifyOptions#sourcemap} is `true`.
+   */
+  inputSourceMap?: SourceMap
+}
+
+export interface MinifyResult {
+  /**
+   * The minified code.
+   *
+   * If parsing failed or the options are invalid, this will be an empty string.
+   */
+  code: string
+  /**
+   * The source map for the minified code.
+   *
+   * This will be set if {@link MinifyOptions#sourcemap} is `true`.
+   */
+  map?: SourceMap
+  /**
+   * Parse errors and invalid options.
+   *
+   * Oxc's parser recovers from common syntax errors, meaning that
+   * minified code may still be available even if there are errors in this
+   * list.
+   */
+  errors: Array<string>
+}
+
+export interface SourceMap {
+  file?: string
+  mappings: string
+  names: Array<string>
+  sourceRoot?: string
+  sources: Array<string>
+  sourcesContent?: Array<string>
+  version: number
+  x_google_ignoreList?: Array<number>
+}
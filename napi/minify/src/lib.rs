@@ -1,28 +1,154 @@
+// NOTE: the strange order of struct and `mod` statements is to establish the
+// desired order in generated `index.d.ts` code. We want options to be on top.
+// This is not only for aesthetics, but using declarations before they're parsed
+// breaks NAPI typegen.
+mod options;
+pub use crate::options::*;
+
+mod sourcemap;
+pub use crate::sourcemap::SourceMap;
+
+use std::sync::Arc;
+
+use napi::Either;
 use napi_derive::napi;
 
 use oxc_allocator::Allocator;
-use oxc_codegen::{Codegen, CodegenOptions};
-use oxc_minifier::{CompressOptions, Minifier, MinifierOptions};
-use oxc_parser::Parser;
+use oxc_codegen::Codegen;
+use oxc_diagnostics::{Error, NamedSource, OxcDiagnostic};
+use oxc_mangler::{MangleOptions, Mangler};
+use oxc_minifier::Compressor;
+use oxc_parser::{Parser, ParserReturn};
 use oxc_span::SourceType;
 
+#[napi(object)]
+pub struct MinifyResult {
+    /// The minified code.
+    ///
+    /// If parsing failed or the options are invalid, this will be an empty string.
+    pub code: String,
+
+    /// The source map for the minified code.
+    ///
+    /// This will be set if {@link MinifyOptions#sourcemap} is `true`.
+    pub map: Option<SourceMap>,
+
+    /// Parse errors and invalid options.
+    ///
+    /// Oxc's parser recovers from common syntax errors, meaning that
+    /// minified code may still be available even if there are errors in this
+    /// list.
+    pub errors: Vec<String>,
+}
+
+/// Minify a JavaScript or TypeScript file.
+///
+/// @param filename The name of the file being minified, used for the source type and source map.
+/// @param sourceText the source code itself
+/// @param options The options for minification. See {@link MinifyOptions} for more information.
+///
+/// @returns an object containing the minified code, source map, and any
+/// errors that occurred during parsing.
 #[allow(clippy::needless_pass_by_value)]
 #[napi]
-pub fn minify(filename: String, source_text: String) -> String {
-    let allocator = Allocator::default();
-    let source_type = SourceType::from_path(&filename).unwrap_or_default().with_typescript(true);
+pub fn minify(
+    filename: String,
+    source_text: String,
+    options: Option<MinifyOptions>,
+) -> MinifyResult {
+    let options = options.unwrap_or_default();
+    let source_type = {
+        let mut source_type =
+            SourceType::from_path(&filename).unwrap_or_default().with_typescript(true);
+        // Force `script` or `module`
+        match options.source_type.as_deref() {
+            Some("script") => source_type = source_type.with_script(true),
+            Some("module") => source_type = source_type.with_module(true),
+            _ => {}
+        }
+        source_type
+    };
 
-    let mut program = Parser::new(&allocator, &source_text, source_type).parse().program;
+    let compress = match options.compress {
+        None | Some(Either::A(true)) => Ok(Some(oxc_minifier::CompressOptions::default())),
+        Some(Either::A(false)) => Ok(None),
+        Some(Either::B(compress)) => compress.into_compress_options().map(Some),
+    };
+    let compress = match compress {
+        Ok(compress) => compress,
+        Err(errors) => {
+            return MinifyResult {
+                code: String::new(),
+                map: None,
+                errors: render_diagnostics(&filename, &source_text, errors),
+            }
+        }
+    };
 
-    let mangler =
-        Minifier::new(MinifierOptions { mangle: true, compress: CompressOptions::default() })
-            .build(&allocator, &mut program)
-            .mangler;
+    let allocator = Allocator::default();
+    let ParserReturn { mut program, mut errors, trivias, panicked } =
+        Parser::new(&allocator, &source_text, source_type).parse();
+    if panicked {
+        return MinifyResult {
+            code: String::new(),
+            map: None,
+            errors: render_diagnostics(&filename, &source_text, errors),
+        };
+    }
 
-    Codegen::new()
-        .with_options(CodegenOptions { minify: true, ..CodegenOptions::default() })
+    if let Some(compress) = compress {
+        let ret = Compressor::new(&allocator, compress)
+            .with_comments(&source_text, trivias)
+            .build(&mut program);
+        errors.extend(ret.diagnostics);
+    }
+    let sourcemap = options.sourcemap.unwrap_or_default();
+    let mangler = options.mangle.unwrap_or(true).then(|| {
+        let options = MangleOptions { scope_bindings: sourcemap, ..MangleOptions::default() };
+        Mangler::new().with_options(options).build(&program)
+    });
+    let mut codegen = Codegen::new()
+        .with_options(options.codegen.unwrap_or_default().into())
         .with_mangler(mangler)
-        .with_capacity(source_text.len())
-        .build(&program)
-        .source_text
+        .with_capacity(source_text.len());
+    if sourcemap {
+        codegen = codegen.enable_source_map(&filename, &source_text);
+    }
+    let ret = codegen.build(&program);
+
+    let map = ret.source_map.map(|source_map| match options.input_source_map {
+        Some(input_source_map) => match oxc_sourcemap::SourceMap::try_from(input_source_map) {
+            Ok(input_source_map) => oxc_sourcemap::remapping(&source_map, |source| {
+                (source == filename).then(|| input_source_map.clone())
+            })
+            .into(),
+            Err(err) => {
+                errors.push(OxcDiagnostic::error(format!("Invalid input source map: {err}")));
+                source_map.into()
+            }
+        },
+        None => source_map.into(),
+    });
+
+    MinifyResult {
+        code: ret.source_text,
+        map,
+        errors: render_diagnostics(&filename, &source_text, errors),
+    }
+}
+
+fn render_diagnostics(
+    filename: &str,
+    source_text: &str,
+    diagnostics: Vec<OxcDiagnostic>,
+) -> Vec<String> {
+    if diagnostics.is_empty() {
+        return vec![];
+    }
+    let source = Arc::new(NamedSource::new(filename, source_text.to_string()));
+    diagnostics
+        .into_iter()
+        .map(|diagnostic| Error::from(diagnostic).with_source_code(Arc::clone(&source)))
+        .map(|error| format!("{error:?}"))
+        .collect()
 }
@@ -0,0 +1,188 @@
+use napi::Either;
+use napi_derive::napi;
+use rustc_hash::FxHashMap;
+
+use oxc_diagnostics::OxcDiagnostic;
+use oxc_minifier::{CompressTarget, EmbeddedTemplateOptions};
+use oxc_transformer::{ReplaceGlobalDefinesConfig, Targets};
+
+use crate::SourceMap;
+
+/// Options for minifying a JavaScript file.
+///
+/// @see {@link minify}
+#[napi(object)]
+#[derive(Default)]
+pub struct MinifyOptions {
+    #[napi(ts_type = "'script' | 'module' | 'unambiguous' | undefined")]
+    pub source_type: Option<String>,
+
+    /// Compress the code, or `false` to skip compression.
+    ///
+    /// @default true
+    pub compress: Option<Either<bool, CompressOptions>>,
+
+    /// Shorten variable names.
+    ///
+    /// @default true
+    pub mangle: Option<bool>,
+
+    /// Configure how the output is printed.
+    pub codegen: Option<CodegenOptions>,
+
+    /// Enable source map generation.
+    ///
+    /// When `true`, the `map` field of the minify result object will be populated.
+    ///
+    /// @default false
+    ///
+    /// @see {@link SourceMap}
+    pub sourcemap: Option<bool>,
+
+    /// The source map of the input code, e.g. from a previous transform step.
+    ///
+    /// The generated source map will map back to the sources of this source map.
+    /// Only used when {@link MinifyOptions#sourcemap} is `true`.
+    pub input_source_map: Option<SourceMap>,
+}
+
+/// Configure how the code is compressed.
+///
+/// Options which are not set use the defaults of `oxc_minifier`.
+#[napi(object)]
+#[derive(Default)]
+pub struct CompressOptions {
+    /// Browserslist query of the engines which the output runs in, e.g. `'chrome 80'`,
+    /// or `'esnext'`. Modern syntax is used when it is shorter and supported by all targets.
+    ///
+    /// @default no modern syntax is introduced
+    pub target: Option<String>,
+
+    /// Remove `console.*` calls.
+    ///
+    /// @default false
+    pub drop_console: Option<bool>,
+
+    /// Remove `debugger;` statements.
+    ///
+    /// @default true
+    pub drop_debugger: Option<bool>,
+
+    /// Remove labeled statements with these labels.
+    ///
+    /// @default []
+    pub drop_labels: Option<Vec<String>>,
+
+    /// Remove calls to these functions when their return value is unused.
+    ///
+    /// @default []
+    pub pure_funcs: Option<Vec<String>>,
+
+    /// Replace global identifiers and member expressions with constant expressions,
+    /// e.g. `{ 'process.env.NODE_ENV': '"production"' }`.
+    #[napi(ts_type = "Record<string, string>")]
+    pub global_defs: Option<FxHashMap<String, String>>,
+
+    /// The maximum number of times the compressor runs, `0` runs until no more changes are made.
+    ///
+    /// @default 0
+    pub passes: Option<u32>,
+
+    /// Keep `Infinity` instead of compressing it into `1/0`.
+    ///
+    /// @default true
+    pub keep_infinity: Option<bool>,
+
+    /// Transform function expressions into arrow functions in any position, not only when passed
+    /// as call arguments. This breaks functions used as constructors.
+    ///
+    /// @default false
+    pub unsafe_arrows: Option<bool>,
+
+    /// Transform `!(a < b)` into `a >= b`, which is incorrect for `NaN`.
+    ///
+    /// @default false
+    pub unsafe_comps: Option<bool>,
+
+    /// Transform `2 * x * 3` into `6 * x`, which may give imprecise floating point results.
+    ///
+    /// @default false
+    pub unsafe_math: Option<bool>,
+
+    /// Transform `Array.prototype.slice` into `[].slice`, and likewise for `Object` and `String`.
+    ///
+    /// @default false
+    pub unsafe_proto: Option<bool>,
+
+    /// Minify CSS, HTML and GraphQL in tagged templates such as `` css`...` ``, `` html`...` ``
+    /// and `` gql`...` ``, and in template literals preceded by a `/* css */` style comment.
+    ///
+    /// @default false
+    pub embedded_templates: Option<bool>,
+}
+
+impl CompressOptions {
+    pub(crate) fn into_compress_options(
+        self,
+    ) -> Result<oxc_minifier::CompressOptions, Vec<OxcDiagnostic>> {
+        let ops = oxc_minifier::CompressOptions::default();
+        let target = match self.target.as_deref() {
+            None => ops.target,
+            Some("esnext") => CompressTarget::esnext(),
+            Some(query) => CompressTarget::from_targets(&Targets::from_query(query))
+                .map_err(|err| vec![OxcDiagnostic::error(err.to_string())])?,
+        };
+        let global_defs = self.global_defs.map_or(ops.global_defs, |global_defs| {
+            global_defs.into_iter().collect::<Vec<_>>()
+        });
+        // Report invalid `global_defs` before parsing, the compressor would only warn about them.
+        if !global_defs.is_empty() {
+            ReplaceGlobalDefinesConfig::new(&global_defs)?;
+        }
+        Ok(oxc_minifier::CompressOptions {
+            target,
+            drop_console: self.drop_console.unwrap_or(ops.drop_console),
+            drop_debugger: self.drop_debugger.unwrap_or(ops.drop_debugger),
+            drop_labels: self.drop_labels.unwrap_or(ops.drop_labels),
+            pure_funcs: self.pure_funcs.unwrap_or(ops.pure_funcs),
+            global_defs,
+            passes: self.passes.map_or(ops.passes, |passes| passes as usize),
+            keep_infinity: self.keep_infinity.unwrap_or(ops.keep_infinity),
+            unsafe_arrows: self.unsafe_arrows.unwrap_or(ops.unsafe_arrows),
+            unsafe_comps: self.unsafe_comps.unwrap_or(ops.unsafe_comps),
+            unsafe_math: self.unsafe_math.unwrap_or(ops.unsafe_math),
+            unsafe_proto: self.unsafe_proto.unwrap_or(ops.unsafe_proto),
+            embedded_templates: if self.embedded_templates.unwrap_or(false) {
+                Some(EmbeddedTemplateOptions::default())
+            } else {
+                ops.embedded_templates
+            },
+            ..ops
+        })
+    }
+}
+
+/// Configure how the minified code is printed.
+#[napi(object)]
+#[derive(Default)]
+pub struct CodegenOptions {
+    /// Remove whitespace.
+    ///
+    /// @default true
+    pub remove_whitespace: Option<bool>,
+
+    /// Use single quotes instead of double quotes.
+    ///
+    /// @default false
+    pub single_quote: Option<bool>,
+}
+
+impl From<CodegenOptions> for oxc_codegen::CodegenOptions {
+    fn from(options: CodegenOptions) -> Self {
+        Self {
+            single_quote: options.single_quote.unwrap_or(false),
+            minify: options.remove_whitespace.unwrap_or(true),
+            ..Self::default()
+        }
+    }
+}
@@ -0,0 +1,69 @@
+import assert from 'assert';
+import oxc from './index.js';
+
+console.log(`Testing on ${process.platform}-${process.arch}`);
+
+const code = 'function foo(bar) { if (DEBUG) { console.log(bar); } return bar + Infinity; }\nfoo(1);\n';
+
+{
+  const ret = oxc.minify('test.js', code);
+  assert.equal(ret.code, 'function foo(b){if(DEBUG)console.log(b);return b+Infinity}foo(1);');
+  assert.equal(ret.map, undefined);
+  assert.equal(ret.errors.length, 0);
+}
+
+{
+  const ret = oxc.minify('test.js', code, {
+    compress: { globalDefs: { DEBUG: 'false' }, keepInfinity: false },
+    mangle: false,
+    codegen: { singleQuote: true },
+    sourcemap: true,
+  });
+  assert.equal(ret.code, 'function foo(bar){return bar+1/0}foo(1);');
+  assert.deepEqual(ret.map.sources, ['test.js']);
+  assert.equal(ret.errors.length, 0);
+}
+
+{
+  const ret = oxc.minify('test.js', 'x(css`\n  a { b: c; }\n`, /* html */ `<p>  a  </p>`, gql`{ a\n  b }`);', {
+    compress: { embeddedTemplates: true },
+    mangle: false,
+  });
+  assert.equal(ret.code, 'x(css`a{b:c}`,`<p> a </p>`,gql`{a b}`);');
+  assert.equal(ret.errors.length, 0);
+
+  const bail = oxc.minify('test.js', 'x(css`a { /* ${b} */ }`);', { compress: { embeddedTemplates: true } });
+  assert.equal(bail.code, 'x(css`a { /* ${b} */ }`);');
+  assert.equal(bail.errors.length, 1);
+  assert.match(bail.errors[0], /Cannot minify embedded CSS/);
+}
+
+{
+  const ret = oxc.minify('test.js', 'let x = 1;\nx++;\n', {
+    compress: false,
+    mangle: false,
+    sourcemap: true,
+    inputSourceMap: {
+      mappings: 'AAAA,IAAI,CAAC,GAAG,CAAC,CAAC;AACV,CAAC,EAAE,CAAC',
+      names: [],
+      sources: ['original.ts'],
+      sourcesContent: ['let x: number = 1;\nx++;\n'],
+      version: 3,
+    },
+  });
+  assert.equal(ret.code, 'let x=1;x++;');
+  assert.deepEqual(ret.map.sources, ['original.ts']);
+  assert.deepEqual(ret.map.sourcesContent, ['let x: number = 1;\nx++;\n']);
+}
+
+{
+  const ret = oxc.minify('test.js', 'function (');
+  assert.equal(ret.code, '');
+  assert(ret.errors.length > 0);
+}
+
+{
+  const ret = oxc.minify('test.js', 'a', { compress: { globalDefs: { DEBUG: '(' } } });
+  assert.equal(ret.code, '');
+  assert(ret.errors.length > 0);
+}
@@ -72,7 +72,11 @@ pub(crate) fn build_declarations(
         .enable_comment(
             ctx.source_text(),
             ctx.trivias.clone(),
-            CommentOptions { preserve_annotate_comments: false },
+            CommentOptions {
+                preserve_annotate_comments: false,
+                preserve_normal_comments: false,
+                ..CommentOptions::default()
+            },
         )
         .build(&transformed_ret.program)
 }
@@ -303,7 +303,7 @@ impl TestCase for ConformanceTestCase {
                         // .enable_comment(
                         // &output,
                         // ret.trivias,
-                        // CommentOptions { preserve_annotate_comments: true },
+                        // CommentOptions { preserve_annotate_comments: true, ..CommentOptions::default() },
                         // )
                         .build(&ret.program)
                         .source_text